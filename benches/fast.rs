@@ -0,0 +1,38 @@
+//! Compares accurate and fast conversion of all 8-bit sRGB colours to
+//! L*u*v*.  Run with `cargo bench --bench fast`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Converts all 8-bit sRGB colours with given function and returns the
+/// shortest of a few runs.
+fn time(convert: impl Fn(&[u8; 3]) -> luv::Luv) -> Duration {
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            for i in 0..1u32 << 24 {
+                let [_, r, g, b] = i.to_be_bytes();
+                black_box(convert(black_box(&[r, g, b])));
+            }
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let mut max_error = 0.0f32;
+    for i in 0..1u32 << 24 {
+        let [_, r, g, b] = i.to_be_bytes();
+        let want = luv::Luv::from_rgb(&[r, g, b]);
+        let got = luv::Luv::from_rgb_fast(&[r, g, b]);
+        max_error = max_error
+            .max((want.l - got.l).abs())
+            .max((want.u - got.u).abs())
+            .max((want.v - got.v).abs());
+    }
+
+    println!("accurate:  {:?}", time(luv::Luv::from_rgb));
+    println!("fast:      {:?}", time(luv::Luv::from_rgb_fast));
+    println!("max error: {}", max_error);
+}
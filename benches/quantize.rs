@@ -0,0 +1,33 @@
+//! Compares serial and parallel quantisation of a 1 MP image against
+//! a 256-colour palette.  Run with `cargo bench --features rayon`.
+
+use std::time::Instant;
+
+fn main() {
+    let palette: Vec<luv::Luv> = (0..=255u8)
+        .map(|i| {
+            luv::Luv::from_rgb(&[i, i.wrapping_mul(37), i.wrapping_mul(101)])
+        })
+        .collect();
+    let pixels: Vec<luv::Luv> = (0..1024 * 1024u32)
+        .map(|i| {
+            let [_, r, g, b] = i.wrapping_mul(2_654_435_761).to_be_bytes();
+            luv::Luv::from_rgb(&[r, g, b])
+        })
+        .collect();
+
+    let start = Instant::now();
+    let serial: Vec<usize> = pixels
+        .iter()
+        .map(|luv| luv::nearest(luv, &palette).unwrap().0)
+        .collect();
+    let serial_time = start.elapsed();
+
+    let start = Instant::now();
+    let parallel = luv::par_quantize(&pixels, &palette);
+    let parallel_time = start.elapsed();
+
+    assert_eq!(serial, parallel);
+    println!("serial:   {:?}", serial_time);
+    println!("parallel: {:?}", parallel_time);
+}
@@ -0,0 +1,389 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Colours with alpha channel.
+
+use crate::Luv;
+
+/// Struct representing a color in L\*u\*v\* colour space with alpha channel.
+///
+/// The colour is stored with straight (i.e. not premultiplied) alpha.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct LuvA {
+    /// The colour.
+    pub luv: Luv,
+    /// Opacity of the colour in 0–1 range with zero meaning fully
+    /// transparent and one fully opaque colour.
+    pub alpha: f32,
+}
+
+impl LuvA {
+    /// Constructs a new `LuvA` from a four-element array of `u8`s
+    ///
+    /// The first three elements are sRGB components of the colour while the
+    /// last one is (straight) alpha with 255 meaning fully opaque colour.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luva = luv::LuvA::from_rgba(&[240, 33, 95, 51]);
+    /// assert_eq!(luv::Luv::from_rgb(&[240, 33, 95]), luva.luv);
+    /// assert_eq!(0.2, luva.alpha);
+    /// ```
+    pub fn from_rgba(rgba: &[u8; 4]) -> Self {
+        Self {
+            luv: Luv::from_rgba(rgba),
+            alpha: rgba[3] as f32 / 255.0,
+        }
+    }
+
+    /// Constructs a new `LuvA` from a four-element array of `u8`s with
+    /// premultiplied alpha
+    ///
+    /// The first three elements are sRGB components of the colour already
+    /// multiplied by alpha while the last one is alpha with 255 meaning fully
+    /// opaque colour.  The components are divided by alpha before conversion
+    /// (and clamped to full intensity in case they exceed alpha).  Unlike
+    /// [`LuvA::from_rgba`], this gives correct colours for semi-transparent
+    /// pixels stored with premultiplied alpha.
+    ///
+    /// If alpha is zero, the colour information is lost and the result is
+    /// fully transparent black.
+    ///
+    /// Note that premultiplication here refers to gamma-compressed sRGB
+    /// components, which is how most image formats and libraries store such
+    /// pixels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luva = luv::LuvA::from_premultiplied_rgba(&[128, 0, 0, 128]);
+    /// assert_eq!([255, 0, 0, 128], luva.to_rgba());
+    ///
+    /// let luva = luv::LuvA::from_premultiplied_rgba(&[0, 0, 0, 0]);
+    /// assert_eq!(luv::Luv::BLACK, luva.luv);
+    /// ```
+    pub fn from_premultiplied_rgba(rgba: &[u8; 4]) -> Self {
+        if rgba[3] == 0 {
+            return Self {
+                luv: Luv::BLACK,
+                alpha: 0.0,
+            };
+        }
+        let alpha = rgba[3] as f32;
+        let unmultiply = |c: u8| (c as f32 / alpha).min(1.0);
+        let rgb = [
+            unmultiply(rgba[0]),
+            unmultiply(rgba[1]),
+            unmultiply(rgba[2]),
+        ];
+        Self {
+            luv: Luv::from_rgb_normalized(&rgb),
+            alpha: alpha / 255.0,
+        }
+    }
+
+    /// Returns the colour in RGBA, in a 4-element array.
+    ///
+    /// Alpha is clamped to the 0–1 range before conversion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luva = luv::LuvA::from_rgba(&[240, 33, 95, 51]);
+    /// assert_eq!([240, 33, 95, 51], luva.to_rgba());
+    /// ```
+    pub fn to_rgba(&self) -> [u8; 4] {
+        let [r, g, b] = self.luv.to_rgb();
+        let alpha = self.alpha.clamp(0.0, 1.0) * 255.0 + 0.5;
+        [r, g, b, alpha as u8]
+    }
+
+    /// Interpolates between the colour and an `other` colour.
+    ///
+    /// `t` is the interpolation factor with zero corresponding to `self` and
+    /// one to `other`.  Alpha is always interpolated linearly.
+    ///
+    /// With straight interpolation (`premultiplied` false), each of the
+    /// L\*u\*v\* coordinates is interpolated linearly independently of alpha.
+    /// As a result, a nearly transparent colour contributes to the result as
+    /// much as an opaque one; for example, mixing transparent red with opaque
+    /// blue produces purple.
+    ///
+    /// With premultiplied interpolation, the coordinates are multiplied by
+    /// alpha before interpolation and the result is divided by the
+    /// interpolated alpha.  Colours thus contribute proportionally to their
+    /// opacity and transparent colours don’t bleed into the result.  If the
+    /// interpolated alpha is zero, the colour is interpolated as in the
+    /// straight case.  Note that the premultiplication is done on L\*u\*v\*
+    /// coordinates rather than on linear light.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let red = luv::Luv::from_rgb(&[255, 0, 0]);
+    /// let red = luv::LuvA { luv: red, alpha: 0.0 };
+    /// let blue = luv::Luv::from_rgb(&[0, 0, 255]);
+    /// let blue = luv::LuvA { luv: blue, alpha: 1.0 };
+    ///
+    /// let mix = red.mix(&blue, 0.5, true);
+    /// assert_eq!([0, 0, 255, 128], mix.to_rgba());
+    ///
+    /// let mix = red.mix(&blue, 0.5, false);
+    /// assert_eq!([190, 0, 144, 128], mix.to_rgba());
+    /// ```
+    pub fn mix(&self, other: &LuvA, t: f32, premultiplied: bool) -> LuvA {
+        let lerp = |a: f32, b: f32| crate::mul_add(b - a, t, a);
+        let alpha = lerp(self.alpha, other.alpha);
+        let (wa, wb, scale) = if premultiplied && alpha != 0.0 {
+            (self.alpha, other.alpha, 1.0 / alpha)
+        } else {
+            (1.0, 1.0, 1.0)
+        };
+        let mix = |a: f32, b: f32| lerp(a * wa, b * wb) * scale;
+        LuvA {
+            luv: Luv {
+                l: mix(self.luv.l, other.luv.l),
+                u: mix(self.luv.u, other.luv.u),
+                v: mix(self.luv.v, other.luv.v),
+            },
+            alpha,
+        }
+    }
+
+    /// Returns alpha-weighted average of given colours.
+    ///
+    /// The average is calculated using premultiplied alpha: L\*u\*v\*
+    /// coordinates of each colour are multiplied by its alpha, summed and
+    /// divided by the sum of all alphas.  More opaque colours thus contribute
+    /// more to the result and fully transparent ones don’t contribute at all.
+    /// This is the correct way of averaging pixels with transparency, e.g.
+    /// when downsampling an image.  Alpha of the result is the plain average
+    /// of the alphas.  If all colours are fully transparent, the coordinates
+    /// are averaged without weighting.  As with [`LuvA::mix`], the
+    /// premultiplication is done on L\*u\*v\* coordinates rather than on
+    /// linear light.
+    ///
+    /// Returns `None` if `colors` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let colors = [
+    ///     luv::LuvA::from_rgba(&[255, 0, 0, 0]),
+    ///     luv::LuvA::from_rgba(&[0, 0, 255, 255]),
+    /// ];
+    /// let avg = luv::LuvA::average(&colors).unwrap();
+    /// assert_eq!([0, 0, 255, 128], avg.to_rgba());
+    /// assert_eq!(None, luv::LuvA::average(&[]));
+    /// ```
+    pub fn average(colors: &[LuvA]) -> Option<LuvA> {
+        if colors.is_empty() {
+            return None;
+        }
+        let total: f32 = colors.iter().map(|c| c.alpha).sum();
+        let weight = |c: &LuvA| if total != 0.0 { c.alpha } else { 1.0 };
+        let (mut l, mut u, mut v, mut sum) = (0.0, 0.0, 0.0, 0.0);
+        for color in colors {
+            let w = weight(color);
+            l = crate::mul_add(color.luv.l, w, l);
+            u = crate::mul_add(color.luv.u, w, u);
+            v = crate::mul_add(color.luv.v, w, v);
+            sum += w;
+        }
+        Some(LuvA {
+            luv: Luv {
+                l: l / sum,
+                u: u / sum,
+                v: v / sum,
+            },
+            alpha: total / colors.len() as f32,
+        })
+    }
+}
+
+
+/// RGBA to LuvA conversion that operates on a flat `&[u8]` of consecutive
+/// RGBA quadruples.
+///
+/// Alpha is straight (i.e. not premultiplied) as in [`LuvA::from_rgba`].  If
+/// length of the slice isn’t a multiple of four, the trailing bytes are
+/// ignored.
+///
+/// # Example
+/// ```
+/// let rgbas = &[255u8, 0, 0, 255, 0, 0, 255, 51];
+/// let luvas = luv::rgba_bytes_to_luvas(rgbas);
+/// assert_eq!(vec![
+///     luv::LuvA::from_rgba(&[255, 0, 0, 255]),
+///     luv::LuvA::from_rgba(&[0, 0, 255, 51]),
+/// ], luvas);
+/// ```
+pub fn rgba_bytes_to_luvas(bytes: &[u8]) -> Vec<LuvA> {
+    use std::convert::TryInto;
+    bytes
+        .chunks_exact(4)
+        .map(|rgba| LuvA::from_rgba(rgba.try_into().unwrap()))
+        .collect()
+}
+
+/// LuvA to RGBA conversion that outputs a flat `Vec<u8>` of consecutive
+/// RGBA quadruples.
+///
+/// This is an inverse of [`rgba_bytes_to_luvas`]; each colour is converted
+/// with [`LuvA::to_rgba`].
+///
+/// # Example
+/// ```
+/// let luvas = [
+///     luv::LuvA::from_rgba(&[255, 0, 0, 255]),
+///     luv::LuvA::from_rgba(&[0, 0, 255, 51]),
+/// ];
+/// let bytes = luv::luvas_to_rgba_bytes(&luvas);
+/// assert_eq!(vec![255u8, 0, 0, 255, 0, 0, 255, 51], bytes);
+/// ```
+pub fn luvas_to_rgba_bytes(luvas: &[LuvA]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(luvas.len() * 4);
+    for luva in luvas {
+        bytes.extend_from_slice(&luva.to_rgba());
+    }
+    bytes
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LCh;
+
+    #[test]
+    fn test_mix() {
+        let red = LuvA {
+            luv: Luv::from_rgb(&[255, 0, 0]),
+            alpha: 0.0,
+        };
+        let blue = LuvA {
+            luv: Luv::from_rgb(&[0, 0, 255]),
+            alpha: 1.0,
+        };
+
+        let mix = red.mix(&blue, 0.5, true);
+        assert_eq!(0.5, mix.alpha);
+        approx::assert_abs_diff_eq!(blue.luv, mix.luv, epsilon = 0.001);
+
+        let red = LuvA { alpha: 0.25, ..red };
+        let mix = red.mix(&blue, 0.5, true);
+        assert_eq!(0.625, mix.alpha);
+        assert!(
+            blue.luv.squared_distance(&mix.luv) <
+                red.luv.squared_distance(&mix.luv)
+        );
+        let mix = red.mix(&blue, 0.5, false);
+        approx::assert_abs_diff_eq!(
+            blue.luv.squared_distance(&mix.luv),
+            red.luv.squared_distance(&mix.luv),
+            epsilon = 0.01
+        );
+
+        for premultiplied in [false, true] {
+            assert_eq!(red, red.mix(&blue, 0.0, premultiplied));
+            let mix = red.mix(&blue, 1.0, premultiplied);
+            approx::assert_abs_diff_eq!(blue.luv, mix.luv, epsilon = 0.001);
+        }
+
+        let clear = LuvA {
+            luv: blue.luv,
+            alpha: 0.0,
+        };
+        let mix = clear.mix(&LuvA { alpha: 0.0, ..red }, 0.5, true);
+        assert_eq!(0.0, mix.alpha);
+        assert!(mix.luv.l.is_finite());
+    }
+
+    #[test]
+    fn test_from_premultiplied_rgba() {
+        let red = LuvA::from_premultiplied_rgba(&[128, 0, 0, 128]);
+        assert_eq!([255, 0, 0, 128], red.to_rgba());
+        approx::assert_abs_diff_eq!(
+            Luv::from_rgb(&[255, 0, 0]),
+            red.luv,
+            epsilon = 0.001
+        );
+
+        for rgba in [[255, 0, 0, 255], [12, 200, 99, 255], [0, 0, 0, 255]] {
+            let want = LuvA::from_rgba(&rgba);
+            let got = LuvA::from_premultiplied_rgba(&rgba);
+            assert_eq!(want.alpha, got.alpha);
+            approx::assert_abs_diff_eq!(want.luv, got.luv, epsilon = 0.001);
+        }
+
+        let got = LuvA::from_premultiplied_rgba(&[40, 20, 0, 170]);
+        assert_eq!([60, 30, 0, 170], got.to_rgba());
+
+        // Components larger than alpha are clamped.
+        let got = LuvA::from_premultiplied_rgba(&[255, 0, 0, 128]);
+        assert_eq!([255, 0, 0, 128], got.to_rgba());
+
+        let clear = LuvA::from_premultiplied_rgba(&[10, 20, 30, 0]);
+        assert_eq!(LuvA::default(), clear);
+    }
+
+    #[test]
+    fn test_average() {
+        assert_eq!(None, LuvA::average(&[]));
+
+        let red = LuvA::from_rgba(&[255, 0, 0, 0]);
+        let blue = LuvA::from_rgba(&[0, 0, 255, 255]);
+        let avg = LuvA::average(&[red, blue]).unwrap();
+        assert_eq!(0.5, avg.alpha);
+        approx::assert_abs_diff_eq!(blue.luv, avg.luv, epsilon = 0.001);
+        let (want, got) = (LCh::from_luv(blue.luv), LCh::from_luv(avg.luv));
+        assert!(want.hue_arc_length(&got) < 1e-5);
+
+        // Averaging two colours is the same as mixing them half-way.
+        let red = LuvA { alpha: 0.25, ..red };
+        let avg = LuvA::average(&[red, blue]).unwrap();
+        let mix = red.mix(&blue, 0.5, true);
+        approx::assert_abs_diff_eq!(mix.luv, avg.luv, epsilon = 0.001);
+        assert_eq!(mix.alpha, avg.alpha);
+
+        // All transparent colours are averaged without weighting.
+        let red = LuvA { alpha: 0.0, ..red };
+        let clear = LuvA { alpha: 0.0, ..blue };
+        let avg = LuvA::average(&[red, clear]).unwrap();
+        let mix = red.mix(&clear, 0.5, false);
+        approx::assert_abs_diff_eq!(mix.luv, avg.luv, epsilon = 0.001);
+        assert_eq!(0.0, avg.alpha);
+    }
+
+    #[test]
+    fn test_rgba_bytes() {
+        let bytes = [255u8, 0, 0, 255, 0, 128, 255, 0, 9, 9, 9, 100, 42];
+        let luvas = rgba_bytes_to_luvas(&bytes);
+        assert_eq!(3, luvas.len());
+        for (luva, rgba) in luvas.iter().zip(bytes.chunks_exact(4)) {
+            assert_eq!(Luv::from_rgb(&[rgba[0], rgba[1], rgba[2]]), luva.luv);
+            assert_eq!(rgba[3] as f32 / 255.0, luva.alpha);
+        }
+        assert_eq!(&bytes[..12], &luvas_to_rgba_bytes(&luvas)[..]);
+    }
+}
@@ -20,6 +20,52 @@
  * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
  * SOFTWARE. */
 
+//! Approximate equality of colours.
+//!
+//! The comparison logic is shared between inherent `approx_eq` methods and
+//! implementation of [`approx`](https://crates.io/crates/approx) crate’s
+//! traits (enabled with `approx` feature).
+
+impl crate::Luv {
+    /// Returns whether the colour is approximately equal to an `other`.
+    ///
+    /// Components are compared with absolute tolerance of `epsilon` using the
+    /// same rules as `==` operator, i.e. u\* and v\* are ignored if L\* is
+    /// (approximately) zero.  This is a dependency-free alternative to
+    /// `approx` traits implemented with `approx` feature.
+    ///
+    /// # Example
+    /// ```
+    /// let a = luv::Luv { l: 50.0, u: 10.0, v: -10.0 };
+    /// let b = luv::Luv { l: 50.00001, u: 10.0, v: -9.99999 };
+    /// assert!(a.approx_eq(&b, 1e-4));
+    /// assert!(!a.approx_eq(&b, 1e-6));
+    /// ```
+    pub fn approx_eq(&self, other: &crate::Luv, epsilon: f32) -> bool {
+        luv_eq(self, other, |a, b| (a - b).abs() <= epsilon)
+    }
+}
+
+impl crate::LCh {
+    /// Returns whether the colour is approximately equal to an `other`.
+    ///
+    /// Components are compared with absolute tolerance of `epsilon` using the
+    /// same rules as `==` operator, i.e. chroma and hue are ignored if L\* is
+    /// (approximately) zero, hue is ignored if C\* is (approximately) zero
+    /// and hues are compared modulo τ.  This is a dependency-free alternative
+    /// to `approx` traits implemented with `approx` feature.
+    ///
+    /// # Example
+    /// ```
+    /// let a = luv::LCh { l: 50.0, c: 30.0, h: 0.0 };
+    /// let b = luv::LCh { l: 50.0, c: 30.0, h: std::f32::consts::TAU - 1e-6 };
+    /// assert!(a.approx_eq(&b, 1e-5));
+    /// ```
+    pub fn approx_eq(&self, other: &crate::LCh, epsilon: f32) -> bool {
+        lch_eq(self, other, |a, b| (a - b).abs() <= epsilon)
+    }
+}
+
 fn luv_eq(
     lhs: &crate::Luv,
     rhs: &crate::Luv,
@@ -45,14 +91,21 @@ fn lch_eq(
         true
     } else if !eq(lhs.c, rhs.c) {
         false
-    } else if eq(rhs.c, 0.0) || eq(rhs.c, 0.0) {
+    } else if eq(lhs.c, 0.0) || eq(rhs.c, 0.0) {
         true
     } else {
+        // Hues are compared modulo τ.  After normalising to [0, τ] range
+        // values straddling the wrap point (e.g. 0 and τ−ε) end up at
+        // opposite ends of the range so also compare them after moving the
+        // lower one by a full turn.
         use std::f32::consts::TAU;
-        eq(lhs.h.rem_euclid(TAU), rhs.h.rem_euclid(TAU))
+        let (a, b) = (lhs.h.rem_euclid(TAU), rhs.h.rem_euclid(TAU));
+        let (lo, hi) = if a < b { (a, b) } else { (b, a) };
+        eq(lo, hi) || eq(lo + TAU, hi)
     }
 }
 
+#[cfg(any(test, feature = "approx"))]
 macro_rules! approx_impl {
     ($t:ty, $eq:ident) => {
         impl approx::AbsDiffEq<$t> for $t {
@@ -92,5 +145,47 @@ macro_rules! approx_impl {
     };
 }
 
+#[cfg(any(test, feature = "approx"))]
 approx_impl!(crate::Luv, luv_eq);
+#[cfg(any(test, feature = "approx"))]
 approx_impl!(crate::LCh, lch_eq);
+
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::TAU;
+
+    use crate::LCh;
+
+    #[test]
+    fn test_hue_wrap() {
+        let eps = 1e-6;
+        let lch = |h| LCh {
+            l: 50.0,
+            c: 30.0,
+            h,
+        };
+        for (a, b) in [
+            (-eps, TAU - eps),
+            (0.0, TAU - eps),
+            (0.0, TAU),
+            (0.0, -TAU),
+            (-f32::EPSILON, 0.0),
+            (eps, TAU - eps),
+        ] {
+            let (a, b) = (lch(a), lch(b));
+            approx::assert_abs_diff_eq!(a, b, epsilon = 4.0 * eps);
+            approx::assert_abs_diff_eq!(b, a, epsilon = 4.0 * eps);
+            approx::assert_relative_eq!(a, b, epsilon = 4.0 * eps);
+            approx::assert_relative_eq!(b, a, epsilon = 4.0 * eps);
+            approx::assert_ulps_eq!(a, b, epsilon = 4.0 * eps);
+            approx::assert_ulps_eq!(b, a, epsilon = 4.0 * eps);
+        }
+
+        approx::assert_ulps_eq!(lch(-eps), lch(TAU - eps), epsilon = 0.0);
+
+        approx::assert_abs_diff_ne!(lch(0.1), lch(TAU - 0.1));
+        approx::assert_relative_ne!(lch(0.1), lch(TAU - 0.1));
+        approx::assert_ulps_ne!(lch(0.1), lch(TAU - 0.1));
+    }
+}
@@ -0,0 +1,174 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Compact binary encoding of palettes.
+//!
+//! A palette is encoded as a header followed by the colours.  The header
+//! consists of the four-byte magic `LUVP`, a single version byte (currently
+//! one) and the number of colours encoded as a 32-bit little-endian unsigned
+//! integer.  Each colour is then encoded as its L\*, u\* and v\* coordinates
+//! in that order, each as a 32-bit little-endian IEEE 754 floating point
+//! number.  A palette of `n` colours thus takes `9 + 12·n` bytes.
+
+use std::convert::TryFrom;
+use std::io;
+
+use crate::Luv;
+
+/// Magic bytes identifying an encoded palette.
+const MAGIC: [u8; 4] = *b"LUVP";
+
+/// Version of the encoding.
+const VERSION: u8 = 1;
+
+/// Writes palette to given writer using compact binary encoding.
+///
+/// See [`read_palette`] for the description of the format.
+///
+/// # Errors
+///
+/// Returns an error if writing fails or the palette has more than 2³² − 1
+/// colours.
+///
+/// # Example
+/// ```
+/// let palette = luv::rgbs_to_luvs(&[[255, 0, 0], [0, 0, 255]]);
+/// let mut buf = Vec::new();
+/// luv::write_palette(&palette, &mut buf).unwrap();
+/// assert_eq!(9 + 2 * 12, buf.len());
+/// assert_eq!(palette, luv::read_palette(&mut &buf[..]).unwrap());
+/// ```
+pub fn write_palette(luvs: &[Luv], w: &mut impl io::Write) -> io::Result<()> {
+    let count = u32::try_from(luvs.len()).map_err(|_| {
+        io::Error::new(io::ErrorKind::InvalidInput, "palette too large")
+    })?;
+    w.write_all(&MAGIC)?;
+    w.write_all(&[VERSION])?;
+    w.write_all(&count.to_le_bytes())?;
+    for luv in luvs {
+        w.write_all(&luv.l.to_le_bytes())?;
+        w.write_all(&luv.u.to_le_bytes())?;
+        w.write_all(&luv.v.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+/// Reads palette encoded with [`write_palette`] from given reader.
+///
+/// The encoding starts with a nine-byte header: the `LUVP` magic, version
+/// byte equal one and number of colours as 32-bit little-endian integer.  It’s
+/// followed by L\*, u\* and v\* coordinates of each colour encoded as 32-bit
+/// little-endian floating point numbers.  Data past the last colour is not
+/// read.
+///
+/// # Errors
+///
+/// Returns an error with [`io::ErrorKind::InvalidData`] kind if the magic or
+/// version don’t match and [`io::ErrorKind::UnexpectedEof`] if the data is
+/// truncated.  Other errors from the reader are passed through.
+pub fn read_palette(r: &mut impl io::Read) -> io::Result<Vec<Luv>> {
+    let mut header = [0; 9];
+    r.read_exact(&mut header)?;
+    if header[..4] != MAGIC {
+        return Err(invalid_data("invalid palette magic"));
+    }
+    if header[4] != VERSION {
+        return Err(invalid_data("unsupported palette version"));
+    }
+    let count =
+        u32::from_le_bytes([header[5], header[6], header[7], header[8]]);
+
+    // Don’t trust the count when allocating memory in case data is corrupt.
+    let mut luvs = Vec::with_capacity((count as usize).min(4096));
+    let mut buf = [0; 12];
+    for _ in 0..count {
+        r.read_exact(&mut buf)?;
+        let read = |idx: usize| {
+            f32::from_le_bytes([
+                buf[idx],
+                buf[idx + 1],
+                buf[idx + 2],
+                buf[idx + 3],
+            ])
+        };
+        luvs.push(Luv {
+            l: read(0),
+            u: read(4),
+            v: read(8),
+        });
+    }
+    Ok(luvs)
+}
+
+/// Returns an [`io::ErrorKind::InvalidData`] error with given message.
+pub(crate) fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    #[test]
+    fn test_round_trip() {
+        let palette = crate::rgbs_to_luvs(&[
+            [253, 120, 138],
+            [127, 0, 0],
+            [0, 127, 127],
+            [255, 255, 0],
+            [0, 0, 0],
+            [255, 255, 255],
+        ]);
+        let mut buf = Vec::new();
+        super::write_palette(&palette, &mut buf).unwrap();
+        assert_eq!(9 + palette.len() * 12, buf.len());
+        assert_eq!(b"LUVP\x01\x06\x00\x00\x00", &buf[..9]);
+        let got = super::read_palette(&mut &buf[..]).unwrap();
+        assert_eq!(palette, got);
+
+        let mut buf = Vec::new();
+        super::write_palette(&[], &mut buf).unwrap();
+        assert_eq!(
+            Vec::<crate::Luv>::new(),
+            super::read_palette(&mut &buf[..]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut buf = Vec::new();
+        super::write_palette(&crate::rgbs_to_luvs(&[[1, 2, 3]]), &mut buf)
+            .unwrap();
+
+        let kind =
+            |buf: &[u8]| super::read_palette(&mut &buf[..]).unwrap_err().kind();
+        assert_eq!(io::ErrorKind::UnexpectedEof, kind(&buf[..buf.len() - 1]));
+        assert_eq!(io::ErrorKind::UnexpectedEof, kind(&buf[..5]));
+        let mut bad = buf.clone();
+        bad[0] = b'X';
+        assert_eq!(io::ErrorKind::InvalidData, kind(&bad));
+        let mut bad = buf;
+        bad[4] = 2;
+        assert_eq!(io::ErrorKind::InvalidData, kind(&bad));
+    }
+}
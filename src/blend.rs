@@ -0,0 +1,150 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Blend modes for compositing colours.
+
+use crate::Luv;
+
+/// Blend mode used by [`Luv::blend`].
+///
+/// The modes are modelled after the separable blend modes found in image
+/// editors.  In the descriptions, ‘base’ refers to the colour `blend` is
+/// called on and ‘source’ to the colour passed as argument.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// Multiplies linear sRGB components of the colours.  The result is at
+    /// most as light as either of the colours; blending with white leaves the
+    /// colour unchanged.
+    Multiply,
+    /// Multiplies complements of linear sRGB components of the colours, i.e.
+    /// computes `1 − (1 − b)·(1 − s)`.  The result is at least as light as
+    /// either of the colours; blending with black leaves the colour unchanged.
+    Screen,
+    /// Multiplies or screens linear sRGB components depending on the base
+    /// component.  Where the base component is at most one half, the result
+    /// is `2·b·s`; otherwise it is `1 − 2·(1 − b)·(1 − s)`.
+    Overlay,
+    /// Chooses the colour with the greater L\* value.  If both colours have
+    /// the same lightness, the base colour is returned.
+    Lighten,
+    /// Chooses the colour with the smaller L\* value.  If both colours have
+    /// the same lightness, the base colour is returned.
+    Darken,
+}
+
+impl Luv {
+    /// Blends colour with `other` using given blend mode.
+    ///
+    /// [`BlendMode::Multiply`], [`BlendMode::Screen`] and
+    /// [`BlendMode::Overlay`] operate on linear sRGB components (i.e. before
+    /// gamma compression) which are obtained by converting the colours to XYZ
+    /// colour space.  Components are clamped to the 0–1 range before blending
+    /// so the result is always within the sRGB gamut.  [`BlendMode::Lighten`]
+    /// and [`BlendMode::Darken`] compare L\* values directly and return one of
+    /// the colours unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luv::BlendMode;
+    ///
+    /// let red = luv::Luv::from_rgb(&[255, 0, 0]);
+    /// let yellow = luv::Luv::from_rgb(&[255, 255, 0]);
+    /// assert_eq!(yellow, red.blend(&yellow, BlendMode::Lighten));
+    /// assert_eq!([255, 0, 0], red.blend(&yellow, BlendMode::Multiply).to_rgb());
+    /// assert_eq!([255, 255, 0], red.blend(&yellow, BlendMode::Screen).to_rgb());
+    /// ```
+    pub fn blend(&self, other: &Luv, mode: BlendMode) -> Luv {
+        let func: fn(f32, f32) -> f32 = match mode {
+            BlendMode::Multiply => |b, s| b * s,
+            BlendMode::Screen => |b, s| 1.0 - (1.0 - b) * (1.0 - s),
+            BlendMode::Overlay => |b, s| {
+                if b <= 0.5 {
+                    2.0 * b * s
+                } else {
+                    1.0 - 2.0 * (1.0 - b) * (1.0 - s)
+                }
+            },
+            BlendMode::Lighten => {
+                return if other.l > self.l { *other } else { *self }
+            }
+            BlendMode::Darken => {
+                return if other.l < self.l { *other } else { *self }
+            }
+        };
+        let base = linear_from_luv(self);
+        let source = linear_from_luv(other);
+        let linear = [
+            func(base[0], source[0]),
+            func(base[1], source[1]),
+            func(base[2], source[2]),
+        ];
+        Luv::from_xyz(&srgb::xyz::xyz_from_linear(linear))
+    }
+}
+
+/// Converts colour to linear sRGB components clamped to the 0–1 range.
+pub(crate) fn linear_from_luv(luv: &Luv) -> [f32; 3] {
+    let [r, g, b] = srgb::xyz::linear_from_xyz(luv.to_xyz());
+    [r.clamp(0.0, 1.0), g.clamp(0.0, 1.0), b.clamp(0.0, 1.0)]
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::BlendMode;
+    use crate::Luv;
+
+    #[test]
+    fn test_lighten_darken() {
+        let dark = Luv::from_rgb(&[20, 40, 160]);
+        let light = Luv::from_rgb(&[230, 200, 40]);
+        assert_eq!(light, dark.blend(&light, BlendMode::Lighten));
+        assert_eq!(light, light.blend(&dark, BlendMode::Lighten));
+        assert_eq!(dark, dark.blend(&light, BlendMode::Darken));
+        assert_eq!(dark, light.blend(&dark, BlendMode::Darken));
+
+        let other = Luv { l: dark.l, ..light };
+        assert_eq!(dark, dark.blend(&other, BlendMode::Lighten));
+        assert_eq!(dark, dark.blend(&other, BlendMode::Darken));
+    }
+
+    #[test]
+    fn test_blend_linear() {
+        let white = Luv::from_rgb(&[255, 255, 255]);
+        let black = Luv::from_rgb(&[0, 0, 0]);
+        let colour = Luv::from_rgb(&[200, 100, 50]);
+
+        let blend = |mode, other| colour.blend(other, mode).to_rgb();
+        assert_eq!([200, 100, 50], blend(BlendMode::Multiply, &white));
+        assert_eq!([0, 0, 0], blend(BlendMode::Multiply, &black));
+        assert_eq!([255, 255, 255], blend(BlendMode::Screen, &white));
+        assert_eq!([200, 100, 50], blend(BlendMode::Screen, &black));
+
+        let grey = Luv::from_rgb(&[128, 128, 128]);
+        let multiply = colour.blend(&grey, BlendMode::Multiply);
+        let screen = colour.blend(&grey, BlendMode::Screen);
+        let overlay = colour.blend(&grey, BlendMode::Overlay);
+        assert!(multiply.l < colour.l && colour.l < screen.l);
+        assert!(multiply.l < overlay.l && overlay.l < screen.l);
+    }
+}
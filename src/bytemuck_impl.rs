@@ -0,0 +1,57 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Implementation of bytemuck traits for the colour types.
+
+// SAFETY: Both types are repr(C) structs with three f32 fields so they have
+// no padding and any bit pattern is a valid value.
+unsafe impl bytemuck::Zeroable for crate::Luv {}
+unsafe impl bytemuck::Pod for crate::Luv {}
+unsafe impl bytemuck::Zeroable for crate::LCh {}
+unsafe impl bytemuck::Pod for crate::LCh {}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{LCh, Luv};
+
+    #[test]
+    fn test_cast() {
+        let luvs =
+            crate::rgbs_to_luvs(&[[255, 0, 0], [0, 128, 255], [9, 9, 9]]);
+        let floats: &[f32] = bytemuck::cast_slice(&luvs);
+        assert_eq!(luvs.len() * 3, floats.len());
+        for (luv, chunk) in luvs.iter().zip(floats.chunks_exact(3)) {
+            assert_eq!(&[luv.l, luv.u, luv.v], chunk);
+        }
+        let back: &[Luv] = bytemuck::cast_slice(floats);
+        assert_eq!(&luvs[..], back);
+
+        let arrays: &[[f32; 3]] = bytemuck::cast_slice(&luvs);
+        assert_eq!(luvs[1].as_array(), &arrays[1]);
+
+        let lch = LCh::from_luv(luvs[1]);
+        let arr: [f32; 3] = bytemuck::cast(lch);
+        assert_eq!([lch.l, lch.c, lch.h], arr);
+        assert_eq!(LCh::default(), <LCh as bytemuck::Zeroable>::zeroed());
+    }
+}
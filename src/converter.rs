@@ -0,0 +1,283 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Conversion between XYZ and L\*u\*v\* colour spaces with configurable
+//! reference white point.
+
+use crate::{mul_add, Luv};
+use crate::{EPSILON, KAPPA, KAPPA_EPSILON, ONE_OVER_KAPPA};
+
+/// CIE standard colorimetric observer.
+///
+/// XYZ coordinates of a colour depend on the colour matching functions used to
+/// compute them.  The CIE defines two standard observers: the 1931 2° one
+/// (used by sRGB and most other colour spaces) and the 1964 10° one (used
+/// when colours subtending larger field of view are assessed).  Each has
+/// different XYZ coordinates of the D65 white point and thus of the neutral
+/// axis in L\*u\*v\* colour space.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Observer {
+    /// CIE 1931 2° standard observer.
+    Cie1931_2,
+    /// CIE 1964 10° standard observer.
+    Cie1964_10,
+}
+
+impl Observer {
+    /// Returns XYZ coordinates of the D65 white point for the observer.
+    ///
+    /// The coordinates are normalised such that Y equals one.  For the 2°
+    /// observer this is [`srgb::xyz::D65_XYZ`].
+    pub fn d65_white(self) -> [f32; 3] {
+        match self {
+            Observer::Cie1931_2 => srgb::xyz::D65_XYZ,
+            Observer::Cie1964_10 => [0.94811, 1.0, 1.07304],
+        }
+    }
+}
+
+/// Converter between XYZ, sRGB and L\*u\*v\* colour spaces.
+///
+/// L\*u\*v\* coordinates are defined relative to a reference white point.
+/// Functions and methods of [`Luv`] use the D65 white point (which is what
+/// sRGB is defined with) and correspond to the converter returned by
+/// [`Converter::d65`].  A converter with a different white point can be
+/// constructed with [`Converter::with_white`].  The chromaticity of the white
+/// point is computed once when the converter is constructed rather than on
+/// each conversion.
+///
+/// Note that no chromatic adaptation is performed.  sRGB colours are always
+/// converted to XYZ assuming the D65 white point and the XYZ coordinates are
+/// then interpreted relative to the converter’s white point.
+///
+/// # Example
+///
+/// ```
+/// let converter = luv::Converter::d65();
+/// let rgb = [240, 33, 95];
+/// assert_eq!(luv::Luv::from_rgb(&rgb), converter.luv_from_rgb(&rgb));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Converter {
+    white: [f32; 3],
+    u_prime: f32,
+    v_prime: f32,
+}
+
+impl Converter {
+    /// Returns converter using the D65 reference white point.
+    ///
+    /// The conversions are the same as ones performed by methods of [`Luv`],
+    /// e.g. `Converter::d65().luv_from_rgb(rgb)` gives the exact same result
+    /// as `Luv::from_rgb(rgb)`.
+    pub const fn d65() -> Self {
+        Self {
+            white: srgb::xyz::D65_XYZ,
+            u_prime: crate::D65_U_PRIME,
+            v_prime: crate::D65_V_PRIME,
+        }
+    }
+
+    /// Returns converter using reference white point with given XYZ
+    /// coordinates.
+    ///
+    /// The Y coordinate of the white point must be positive.  Lightness of
+    /// colours is computed relative to it so the white point maps to L\*
+    /// equal 100.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// // CIE standard illuminant D50.
+    /// let d50 = [0.96422, 1.0, 0.82521];
+    /// let converter = luv::Converter::with_white(&d50);
+    /// let luv = converter.luv_from_xyz(&d50);
+    /// assert_eq!(100.0, luv.l);
+    /// assert!(luv.u.abs() < 0.001 && luv.v.abs() < 0.001);
+    /// ```
+    pub fn with_white(xyz: &[f32; 3]) -> Self {
+        let [x, y, z] = *xyz;
+        let d = x + 15.0 * y + 3.0 * z;
+        Self {
+            white: *xyz,
+            u_prime: 4.0 * x / d,
+            v_prime: 9.0 * y / d,
+        }
+    }
+
+    /// Returns converter using the D65 white point for given observer.
+    ///
+    /// The XYZ coordinates passed to and returned from the converter are
+    /// assumed to have been computed for the same observer.  Colours which
+    /// are neutral for one observer aren’t necessarily neutral for another
+    /// since the white points have different chromaticities.  For the 2°
+    /// observer, this is the same as [`Converter::d65`].
+    ///
+    /// Note that sRGB is defined using the 2° observer and conversion from or
+    /// to sRGB doesn’t account for the observer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use luv::{Converter, Observer};
+    ///
+    /// let white = Observer::Cie1964_10.d65_white();
+    /// let converter = Converter::with_observer(Observer::Cie1964_10);
+    /// let luv = converter.luv_from_xyz(&white);
+    /// assert!(luv.u.hypot(luv.v) < 0.001);
+    /// let luv = Converter::d65().luv_from_xyz(&white);
+    /// assert!(luv.u.hypot(luv.v) > 1.0);
+    /// ```
+    pub fn with_observer(observer: Observer) -> Self {
+        match observer {
+            Observer::Cie1931_2 => Self::d65(),
+            Observer::Cie1964_10 => Self::with_white(&observer.d65_white()),
+        }
+    }
+
+    /// Returns XYZ coordinates of the reference white point.
+    pub fn white(&self) -> [f32; 3] { self.white }
+
+    /// Converts colour from XYZ to L\*u\*v\* colour space.
+    pub fn luv_from_xyz(&self, xyz: &[f32; 3]) -> Luv {
+        let [x, y, z] = *xyz;
+
+        let y_r = y / self.white[1];
+        let l = if y_r <= 0.0 {
+            return Luv::default();
+        } else if y_r <= EPSILON {
+            KAPPA * y_r
+        } else {
+            mul_add(y_r.powf(1.0 / 3.0), 116.0, -16.0)
+        };
+
+        let d = mul_add(y, 15.0, mul_add(z, 3.0, x));
+        let ll = 13.0 * l;
+        let u = ll * mul_add(x / d, 4.0, -self.u_prime);
+        let v = ll * mul_add(y / d, 9.0, -self.v_prime);
+
+        Luv { l, u, v }
+    }
+
+    /// Converts colour from L\*u\*v\* to XYZ colour space.
+    pub fn xyz_from_luv(&self, luv: &Luv) -> [f32; 3] {
+        if luv.l <= 0.0 {
+            return [0.0, 0.0, 0.0];
+        }
+        let ll = 13.0 * luv.l;
+        let u_prime = luv.u / ll + self.u_prime;
+        let v_prime = luv.v / ll + self.v_prime;
+
+        let y = if luv.l > KAPPA_EPSILON {
+            ((luv.l + 16.0) / 116.0).powi(3)
+        } else {
+            luv.l * ONE_OVER_KAPPA
+        } * self.white[1];
+
+        let a = 0.75 * y * u_prime / v_prime;
+        let x = 3.0 * a;
+        let z = y * (3.0 - 5.0 * v_prime) / v_prime - a;
+
+        [x, y, z]
+    }
+
+    /// Converts colour from 8-bit sRGB to L\*u\*v\* colour space.
+    pub fn luv_from_rgb(&self, rgb: &[u8; 3]) -> Luv {
+        self.luv_from_xyz(&srgb::xyz_from_u8(*rgb))
+    }
+
+    /// Converts colour from L\*u\*v\* to 8-bit sRGB colour space.
+    ///
+    /// Colours outside of the sRGB gamut are clamped.
+    pub fn rgb_from_luv(&self, luv: &Luv) -> [u8; 3] {
+        srgb::u8_from_xyz(self.xyz_from_luv(luv))
+    }
+}
+
+impl Default for Converter {
+    fn default() -> Self { Self::d65() }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::Converter;
+    use crate::Luv;
+
+    #[test]
+    fn test_d65_matches_luv() {
+        let converter = Converter::d65();
+        assert_eq!(converter, Converter::default());
+
+        let mut rng: rand::rngs::StdRng =
+            rand::SeedableRng::from_seed([1u8; 32]);
+        for _ in 0..10000 {
+            let rgb: [u8; 3] = rand::Rng::gen(&mut rng);
+            let luv = Luv::from_rgb(&rgb);
+            assert_eq!(luv, converter.luv_from_rgb(&rgb));
+            assert_eq!(luv.to_rgb(), converter.rgb_from_luv(&luv));
+            assert_eq!(luv.to_xyz(), converter.xyz_from_luv(&luv));
+        }
+    }
+
+    #[test]
+    fn test_with_white() {
+        let d65 = Converter::with_white(&srgb::xyz::D65_XYZ);
+        approx::assert_abs_diff_eq!(
+            Converter::d65().u_prime,
+            d65.u_prime,
+            epsilon = 1e-6
+        );
+        approx::assert_abs_diff_eq!(
+            Converter::d65().v_prime,
+            d65.v_prime,
+            epsilon = 1e-6
+        );
+
+        let white = [95.047, 100.0, 108.883];
+        let converter = Converter::with_white(&white);
+        let luv = converter.luv_from_xyz(&white);
+        approx::assert_abs_diff_eq!(crate::D65_LUV, luv, epsilon = 0.001);
+        let xyz = [20.0, 30.0, 40.0];
+        let got = converter.xyz_from_luv(&converter.luv_from_xyz(&xyz));
+        approx::assert_abs_diff_eq!(&xyz[..], &got[..], epsilon = 0.001);
+    }
+
+    #[test]
+    fn test_with_observer() {
+        use super::Observer;
+
+        let two = Converter::with_observer(Observer::Cie1931_2);
+        let ten = Converter::with_observer(Observer::Cie1964_10);
+        assert_eq!(Converter::d65(), two);
+
+        let xyz = [0.3, 0.4, 0.2];
+        let (a, b) = (two.luv_from_xyz(&xyz), ten.luv_from_xyz(&xyz));
+        assert_eq!(a.l, b.l);
+        assert_ne!(a, b);
+        approx::assert_abs_diff_eq!(a, b, epsilon = 5.0);
+
+        let white = Observer::Cie1964_10.d65_white();
+        let luv = ten.luv_from_xyz(&white);
+        approx::assert_abs_diff_eq!(crate::D65_LUV, luv, epsilon = 0.001);
+    }
+}
@@ -0,0 +1,288 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Parsing and formatting colours using CSS syntax.
+
+use crate::Luv;
+
+/// Error returned when parsing a colour from a string fails.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The string doesn’t match the expected syntax.
+    InvalidSyntax,
+    /// One of the numeric components is not a valid number.
+    InvalidNumber,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, fmtr: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmtr.write_str(match self {
+            ParseError::InvalidSyntax => "invalid colour syntax",
+            ParseError::InvalidNumber => "invalid number in colour",
+        })
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+
+/// Parses a CSS number or percentage with 100% mapping to one.
+fn parse_number(value: &str) -> Result<f32, ParseError> {
+    let (value, scale) = match value.strip_suffix('%') {
+        Some(value) => (value, 0.01),
+        None => (value, 1.0),
+    };
+    match value.parse::<f32>() {
+        Ok(value) if value.is_finite() => Ok(value * scale),
+        _ => Err(ParseError::InvalidNumber),
+    }
+}
+
+impl Luv {
+    /// Formats the colour using CSS `color()` function with `xyz-d65` colour
+    /// space.
+    ///
+    /// The coordinates are obtained with [`Luv::to_xyz`] and formatted as
+    /// plain decimal numbers with as many digits as necessary to represent the
+    /// value exactly.  Parsing the result with [`Luv::from_css_xyz`] gives the
+    /// same colour up to floating point rounding errors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 100.0, u: 0.0, v: 0.0 };
+    /// assert_eq!("color(xyz-d65 0.95044917 1 1.0889165)", luv.to_css_xyz());
+    /// ```
+    pub fn to_css_xyz(&self) -> String {
+        let [x, y, z] = self.to_xyz();
+        format!("color(xyz-d65 {} {} {})", x, y, z)
+    }
+
+    /// Parses a colour given using CSS `color()` function with XYZ colour
+    /// space.
+    ///
+    /// The accepted syntax is `color(<space> <x> <y> <z>)` where `<space>` is
+    /// either `xyz-d65` or `xyz` (which in CSS is an alias of the former).
+    /// Function and colour space names are case-insensitive.  The components
+    /// must be separated by one or more ASCII whitespace characters and
+    /// whitespace is allowed around the parenthesis.  Each component is
+    /// a decimal number as accepted by [`str::parse::<f32>`] (which includes
+    /// the exponent notation) optionally followed by a percent sign in which
+    /// case 100% corresponds to one.  Alpha component and the `none` keyword
+    /// aren’t supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv::from_css_xyz("color(xyz-d65 0.95044917 1 1.0889165)");
+    /// assert_eq!([255, 255, 255], luv.unwrap().to_rgb());
+    ///
+    /// let luv = luv::Luv::from_css_xyz("color(xyz 50% 50% 50%)");
+    /// assert_eq!(Ok(luv::Luv::from_xyz(&[0.5, 0.5, 0.5])), luv);
+    ///
+    /// let err = luv::Luv::from_css_xyz("color(srgb 1 1 1)");
+    /// assert_eq!(Err(luv::ParseError::InvalidSyntax), err);
+    /// ```
+    pub fn from_css_xyz(s: &str) -> Result<Luv, ParseError> {
+        let s = s.trim();
+        let (func, args) =
+            s.split_at(s.find('(').ok_or(ParseError::InvalidSyntax)?);
+        if !func.trim_end().eq_ignore_ascii_case("color") {
+            return Err(ParseError::InvalidSyntax);
+        }
+        let args = args[1..]
+            .strip_suffix(')')
+            .ok_or(ParseError::InvalidSyntax)?;
+        let mut args = args.split_ascii_whitespace();
+        match args.next() {
+            Some(space)
+                if space.eq_ignore_ascii_case("xyz-d65") ||
+                    space.eq_ignore_ascii_case("xyz") => {}
+            _ => return Err(ParseError::InvalidSyntax),
+        }
+        let mut xyz = [0.0; 3];
+        for coord in xyz.iter_mut() {
+            *coord =
+                parse_number(args.next().ok_or(ParseError::InvalidSyntax)?)?;
+        }
+        if args.next().is_some() {
+            return Err(ParseError::InvalidSyntax);
+        }
+        Ok(Luv::from_xyz(&xyz))
+    }
+
+    /// Parses a colour given in CSS hex notation discarding alpha.
+    ///
+    /// This is like [`Luv::from_hex_with_alpha`] except that the alpha
+    /// component, if present, is parsed but ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv::from_hex("#ff0000").unwrap();
+    /// assert_eq!(luv::Luv::from_rgb(&[255, 0, 0]), luv);
+    /// assert_eq!(Ok(luv), luv::Luv::from_hex("#f008"));
+    /// ```
+    pub fn from_hex(s: &str) -> Result<Luv, ParseError> {
+        Luv::from_hex_with_alpha(s).map(|(luv, _)| luv)
+    }
+
+    /// Parses a colour given in CSS hex notation returning alpha separately.
+    ///
+    /// The string must start with a `#` followed by three, four, six or eight
+    /// hexadecimal digits (in either case).  The six-digit form `#rrggbb`
+    /// gives red, green and blue components of an sRGB colour while the
+    /// eight-digit form `#rrggbbaa` additionally gives alpha.  The three- and
+    /// four-digit forms are shorthands where each digit is repeated, e.g.
+    /// `#f08` is the same as `#ff0088`.  For forms without alpha component,
+    /// the returned alpha is 255, i.e. full opacity.  Whitespace around the
+    /// colour is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let red = luv::Luv::from_rgb(&[255, 0, 0]);
+    /// assert_eq!(Ok((red, 255)), luv::Luv::from_hex_with_alpha("#ff0000"));
+    /// assert_eq!(Ok((red, 128)), luv::Luv::from_hex_with_alpha("#FF000080"));
+    /// assert_eq!(Ok((red, 136)), luv::Luv::from_hex_with_alpha("#f008"));
+    ///
+    /// let err = luv::Luv::from_hex_with_alpha("#ff00000");
+    /// assert_eq!(Err(luv::ParseError::InvalidSyntax), err);
+    /// ```
+    pub fn from_hex_with_alpha(s: &str) -> Result<(Luv, u8), ParseError> {
+        let digits = s
+            .trim()
+            .strip_prefix('#')
+            .ok_or(ParseError::InvalidSyntax)?
+            .as_bytes();
+        let digit = |idx: usize| match digits[idx] {
+            ch @ b'0'..=b'9' => Ok(ch - b'0'),
+            ch @ b'a'..=b'f' => Ok(ch - b'a' + 10),
+            ch @ b'A'..=b'F' => Ok(ch - b'A' + 10),
+            _ => Err(ParseError::InvalidNumber),
+        };
+        let mut rgba = [255; 4];
+        match digits.len() {
+            3 | 4 => {
+                for (idx, out) in rgba[..digits.len()].iter_mut().enumerate() {
+                    *out = digit(idx)? * 17;
+                }
+            }
+            6 | 8 => {
+                for (idx, out) in
+                    rgba[..digits.len() / 2].iter_mut().enumerate()
+                {
+                    *out = digit(idx * 2)? * 16 + digit(idx * 2 + 1)?;
+                }
+            }
+            _ => return Err(ParseError::InvalidSyntax),
+        }
+        let [r, g, b, a] = rgba;
+        Ok((Luv::from_rgb(&[r, g, b]), a))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::ParseError;
+    use crate::Luv;
+
+    #[test]
+    fn test_css_xyz_round_trip() {
+        for rgb in [[253, 120, 138], [0, 127, 127], [255, 255, 0], [0, 0, 0]] {
+            let luv = Luv::from_rgb(&rgb);
+            let css = luv.to_css_xyz();
+            let got = Luv::from_css_xyz(&css).unwrap();
+            approx::assert_abs_diff_eq!(luv, got, epsilon = 0.001);
+            assert_eq!(rgb, got.to_rgb(), "{}", css);
+        }
+    }
+
+    #[test]
+    fn test_from_css_xyz() {
+        let want = Ok(Luv::from_xyz(&[0.25, 0.5, 0.125]));
+        for s in [
+            "color(xyz-d65 0.25 0.5 0.125)",
+            "  COLOR( XYZ-D65\t0.25\n0.5   0.125 )  ",
+            "color(xyz 25% 50% 12.5%)",
+            "color(xyz 2.5e-1 .5 0.125)",
+        ] {
+            assert_eq!(want, Luv::from_css_xyz(s), "{}", s);
+        }
+
+        for s in [
+            "",
+            "xyz(0.25 0.5 0.125)",
+            "color(xyz-d50 0.25 0.5 0.125)",
+            "color(xyz 0.25 0.5)",
+            "color(xyz 0.25 0.5 0.125 1)",
+            "color(xyz 0.25 0.5 0.125",
+            "color(xyz 0.25 0.5 0.125 / 0.5)",
+        ] {
+            assert_eq!(
+                Err(ParseError::InvalidSyntax),
+                Luv::from_css_xyz(s),
+                "{}",
+                s
+            );
+        }
+
+        for s in ["color(xyz 0.25 foo 0.125)", "color(xyz 0.25 0.5 inf)"] {
+            assert_eq!(
+                Err(ParseError::InvalidNumber),
+                Luv::from_css_xyz(s),
+                "{}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_hex() {
+        let rgb = |r, g, b| Luv::from_rgb(&[r, g, b]);
+        for (want, s) in [
+            ((rgb(255, 0, 0), 255), "#ff0000"),
+            ((rgb(255, 0, 0), 128), "#ff000080"),
+            ((rgb(18, 52, 86), 255), "#123456"),
+            ((rgb(171, 205, 239), 1), "  #ABCDEF01 "),
+            ((rgb(255, 0, 136), 255), "#f08"),
+            ((rgb(255, 0, 136), 0), "#F080"),
+        ] {
+            assert_eq!(Ok(want), Luv::from_hex_with_alpha(s), "{}", s);
+            assert_eq!(Ok(want.0), Luv::from_hex(s), "{}", s);
+        }
+
+        let bad =
+            ["", "#", "ff0000", "#ff", "#ff000", "#ff00000", "#ff0000800"];
+        for s in bad {
+            let want = Err(ParseError::InvalidSyntax);
+            assert_eq!(want, Luv::from_hex_with_alpha(s), "{}", s);
+            assert_eq!(Err(ParseError::InvalidSyntax), Luv::from_hex(s));
+        }
+
+        for s in ["#ff00zz", "#+f0000", "#f0g", "# f00"] {
+            let got = Luv::from_hex_with_alpha(s);
+            assert_eq!(Err(ParseError::InvalidNumber), got, "{}", s);
+        }
+    }
+}
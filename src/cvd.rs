@@ -0,0 +1,242 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Simulation and correction of colour vision deficiencies.
+
+use crate::Luv;
+
+/// Type of colour vision deficiency (CVD).
+///
+/// Only dichromacy, i.e. complete lack of one type of cones, is modelled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CvdType {
+    /// Lack of long-wavelength (red) cones.
+    Protanopia,
+    /// Lack of medium-wavelength (green) cones.
+    Deuteranopia,
+    /// Lack of short-wavelength (blue) cones.
+    Tritanopia,
+}
+
+impl CvdType {
+    /// Returns matrix simulating the deficiency in linear sRGB space.
+    ///
+    /// The matrices come from Machado, Oliveira and Fernandes, ‘A
+    /// Physiologically-based Model for Simulation of Color Vision
+    /// Deficiency’ (2009) with severity one.
+    #[rustfmt::skip]
+    fn simulation_matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            CvdType::Protanopia => [
+                [ 0.152286,  1.052583, -0.204868],
+                [ 0.114503,  0.786281,  0.099216],
+                [-0.003882, -0.048116,  1.051998],
+            ],
+            CvdType::Deuteranopia => [
+                [ 0.367322,  0.860646, -0.227968],
+                [ 0.280085,  0.672501,  0.047413],
+                [-0.011820,  0.042940,  0.968881],
+            ],
+            CvdType::Tritanopia => [
+                [ 1.255528, -0.076749, -0.178779],
+                [-0.078411,  0.930809,  0.147602],
+                [ 0.004733,  0.691367,  0.303900],
+            ],
+        }
+    }
+
+    /// Returns matrix shifting information lost due to the deficiency into
+    /// channels which are still distinguishable.
+    #[rustfmt::skip]
+    fn shift_matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            CvdType::Protanopia | CvdType::Deuteranopia => [
+                [0.0, 0.0, 0.0],
+                [0.7, 1.0, 0.0],
+                [0.7, 0.0, 1.0],
+            ],
+            CvdType::Tritanopia => [
+                [1.0, 0.0, 0.7],
+                [0.0, 1.0, 0.7],
+                [0.0, 0.0, 0.0],
+            ],
+        }
+    }
+}
+
+impl Luv {
+    /// Returns how the colour looks to a person with given colour vision
+    /// deficiency.
+    ///
+    /// The colour is converted to linear sRGB components (clamped to the 0–1
+    /// range) which are then transformed with simulation matrices from
+    /// Machado et al. (2009).  Note that the simulation is only meaningful
+    /// for displayable colours.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luv::CvdType;
+    ///
+    /// let red = luv::Luv::from_rgb(&[200, 40, 40]);
+    /// let green = luv::Luv::from_rgb(&[110, 110, 40]);
+    /// let (a, b) = (red.simulate_cvd(CvdType::Protanopia),
+    ///               green.simulate_cvd(CvdType::Protanopia));
+    /// assert!(a.squared_distance(&b) < red.squared_distance(&green) / 4.0);
+    /// ```
+    pub fn simulate_cvd(&self, kind: CvdType) -> Luv {
+        let linear = crate::blend::linear_from_luv(self);
+        luv_from_linear(crate::matrix_product(
+            &kind.simulation_matrix(),
+            &linear,
+        ))
+    }
+}
+
+/// Adjusts colours to make them easier to distinguish for people with given
+/// colour vision deficiency.
+///
+/// This implements the error-redistribution daltonisation algorithm:
+///
+/// 1. Each colour is converted to linear sRGB and the deficiency is
+///    simulated (see [`Luv::simulate_cvd`]).
+/// 2. The difference between the original and simulated colour, i.e. the
+///    information lost due to the deficiency, is computed.
+/// 3. The difference is mapped into channels the person can still
+///    distinguish — for protanopia and deuteranopia the lost red-green
+///    information is shifted into green and blue; for tritanopia the lost
+///    blue-yellow information is shifted into red and green — scaled by
+///    `strength` and added to the original colour.
+///
+/// The result is clamped to the sRGB gamut.  `strength` of zero leaves
+/// colours unchanged (up to clamping to the gamut) and one applies the full
+/// correction; values in between give partial correction.  Note that the
+/// corrected colours look different to people with normal colour vision as
+/// well.
+///
+/// # Example
+/// ```
+/// use luv::CvdType;
+///
+/// let pixels = luv::rgbs_to_luvs(&[[200, 40, 40], [110, 110, 40]]);
+/// let fixed = luv::daltonize(&pixels, CvdType::Protanopia, 1.0);
+/// let seen = |luvs: &[luv::Luv]| {
+///     let (a, b) = (luvs[0].simulate_cvd(CvdType::Protanopia),
+///                   luvs[1].simulate_cvd(CvdType::Protanopia));
+///     a.squared_distance(&b)
+/// };
+/// assert!(seen(&fixed) > seen(&pixels));
+/// ```
+pub fn daltonize(pixels: &[Luv], kind: CvdType, strength: f32) -> Vec<Luv> {
+    let simulation = kind.simulation_matrix();
+    let shift = kind.shift_matrix();
+    pixels
+        .iter()
+        .map(|luv| {
+            let linear = crate::blend::linear_from_luv(luv);
+            let simulated = crate::matrix_product(&simulation, &linear);
+            let error = [
+                linear[0] - simulated[0],
+                linear[1] - simulated[1],
+                linear[2] - simulated[2],
+            ];
+            let shift = crate::matrix_product(&shift, &error);
+            let correct =
+                |i: usize| crate::mul_add(shift[i], strength, linear[i]);
+            luv_from_linear([correct(0), correct(1), correct(2)])
+        })
+        .collect()
+}
+
+/// Converts linear sRGB components clamped to the 0–1 range to `Luv`.
+fn luv_from_linear(linear: [f32; 3]) -> Luv {
+    let linear = linear.map(|c| c.clamp(0.0, 1.0));
+    Luv::from_xyz(&srgb::xyz::xyz_from_linear(linear))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KINDS: [CvdType; 3] = [
+        CvdType::Protanopia,
+        CvdType::Deuteranopia,
+        CvdType::Tritanopia,
+    ];
+
+    #[test]
+    fn test_simulate_cvd() {
+        // Greys look the same regardless of the deficiency.
+        for kind in KINDS {
+            for i in [0, 50, 128, 255] {
+                let grey = Luv::from_rgb(&[i, i, i]);
+                let got = grey.simulate_cvd(kind);
+                approx::assert_abs_diff_eq!(grey, got, epsilon = 0.5);
+            }
+        }
+
+        // Red and green are confused by protanopes and deuteranopes but not
+        // by tritanopes.
+        let red = Luv::from_rgb(&[200, 40, 40]);
+        let green = Luv::from_rgb(&[110, 110, 40]);
+        let seen = |kind| {
+            red.simulate_cvd(kind)
+                .squared_distance(&green.simulate_cvd(kind))
+        };
+        let normal = red.squared_distance(&green);
+        assert!(seen(CvdType::Protanopia) < normal / 4.0);
+        assert!(seen(CvdType::Deuteranopia) < normal / 4.0);
+        assert!(seen(CvdType::Tritanopia) > normal / 4.0);
+    }
+
+    #[test]
+    fn test_daltonize() {
+        let pairs = [
+            ([200, 40, 40], [110, 110, 40]),
+            ([180, 90, 60], [110, 120, 60]),
+            ([230, 120, 120], [160, 160, 120]),
+        ];
+        for (a, b) in pairs {
+            let pixels = crate::rgbs_to_luvs(&[a, b]);
+            let seen = |luvs: &[Luv]| {
+                let a = luvs[0].simulate_cvd(CvdType::Protanopia);
+                a.squared_distance(&luvs[1].simulate_cvd(CvdType::Protanopia))
+            };
+            let before = seen(&pixels);
+            let half = daltonize(&pixels, CvdType::Protanopia, 0.5);
+            let full = daltonize(&pixels, CvdType::Protanopia, 1.0);
+            assert!(seen(&half) > before, "{:?} {:?}", a, b);
+            assert!(seen(&full) > seen(&half), "{:?} {:?}", a, b);
+        }
+
+        // Zero strength leaves displayable colours unchanged.
+        let pixels = crate::rgbs_to_luvs(&[[200, 40, 40], [12, 200, 99]]);
+        for kind in KINDS {
+            let got = daltonize(&pixels, kind, 0.0);
+            for (want, got) in pixels.iter().zip(got.iter()) {
+                approx::assert_abs_diff_eq!(*want, *got, epsilon = 0.01);
+            }
+        }
+        assert_eq!(Vec::<Luv>::new(), daltonize(&[], CvdType::Tritanopia, 1.0));
+    }
+}
@@ -0,0 +1,108 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Fast approximate conversion from sRGB to L\*u\*v\* colour space.
+
+use crate::Luv;
+use crate::{EPSILON, KAPPA};
+
+impl Luv {
+    /// Constructs a new `Luv` from a three-element array of `u8`s using fast
+    /// approximate arithmetic.
+    ///
+    /// Gamma expansion uses the same lookup table as [`Luv::from_rgb`] but the
+    /// rest of the conversion takes shortcuts.  The accurate conversion goes
+    /// through the generic matrix product of the `srgb` crate, which picks an
+    /// implementation based on CPU features detected at run time, and then
+    /// performs two divisions and a `powf` call.  This function instead
+    /// multiplies by the sRGB to XYZ matrix using plain arithmetic, divides
+    /// only once and calculates lightness with [`f32::cbrt`].  On x86-64 in
+    /// release builds this makes it about three times faster than `from_rgb`
+    /// (see the `fast` benchmark which can be run with `cargo bench --bench
+    /// fast`).
+    ///
+    /// For every 8-bit sRGB colour, each coordinate of the result differs from
+    /// the one returned by `from_rgb` by less than 0.001.  That’s well below
+    /// the just-noticeable difference but the result is not suitable where
+    /// exact values matter, e.g. when comparing against reference data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let rgb = [240, 33, 95];
+    /// let fast = luv::Luv::from_rgb_fast(&rgb);
+    /// let accurate = luv::Luv::from_rgb(&rgb);
+    /// assert!((fast.l - accurate.l).abs() < 0.001);
+    /// assert!((fast.u - accurate.u).abs() < 0.001);
+    /// assert!((fast.v - accurate.v).abs() < 0.001);
+    /// ```
+    pub fn from_rgb_fast(rgb: &[u8; 3]) -> Self {
+        use srgb::xyz::XYZ_FROM_SRGB_MATRIX as M;
+        let r = srgb::gamma::expand_u8(rgb[0]);
+        let g = srgb::gamma::expand_u8(rgb[1]);
+        let b = srgb::gamma::expand_u8(rgb[2]);
+        let x = M[0][0] * r + M[0][1] * g + M[0][2] * b;
+        let y = M[1][0] * r + M[1][1] * g + M[1][2] * b;
+        let z = M[2][0] * r + M[2][1] * g + M[2][2] * b;
+
+        let l = if y <= 0.0 {
+            return Luv::default();
+        } else if y <= EPSILON {
+            KAPPA * y
+        } else {
+            116.0 * y.cbrt() - 16.0
+        };
+        let ll = 13.0 * l;
+        let scale = ll / (x + 15.0 * y + 3.0 * z);
+        let u = scale * 4.0 * x - ll * crate::D65_U_PRIME;
+        let v = scale * 9.0 * y - ll * crate::D65_V_PRIME;
+        Luv { l, u, v }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::Luv;
+
+    #[test]
+    fn test_from_rgb_fast() {
+        let mut max_error = 0.0f32;
+        // Checking all colours takes too long in debug builds so go through
+        // a sample including all the extremes.
+        for r in (0..=255).step_by(5) {
+            for g in (0..=255).step_by(5) {
+                for b in (0..=255).step_by(5) {
+                    let rgb = [r, g, b];
+                    let want = Luv::from_rgb(&rgb);
+                    let got = Luv::from_rgb_fast(&rgb);
+                    let error = (want.l - got.l)
+                        .abs()
+                        .max((want.u - got.u).abs())
+                        .max((want.v - got.v).abs());
+                    max_error = max_error.max(error);
+                }
+            }
+        }
+        assert!(max_error < 0.001, "{}", max_error);
+    }
+}
@@ -0,0 +1,259 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Fixed-point conversion from sRGB to L\*u\*v\* colour space.
+//!
+//! The module provides conversion which uses integer arithmetic only and is
+//! meant for platforms without hardware floating point support where the
+//! regular `f32` conversion is prohibitively slow.  The conversion is
+//! approximate; see [`Luv::from_rgb_fixed`] for precision bounds.
+
+use crate::Luv;
+
+/// Struct representing a color in L\*u\*v\* colour space using fixed-point
+/// numbers.
+///
+/// Each coordinate is a signed 16-bit number with [`LuvFixed::FRAC_BITS`]
+/// fractional bits.  For example, L\* value of 50.5 is represented as `6464`
+/// (i.e. 50.5 × 128).
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct LuvFixed {
+    /// The L\* value of the colour in 0–100 range scaled by 2⁷.
+    pub l: i16,
+    /// The u\* value of the colour scaled by 2⁷.
+    pub u: i16,
+    /// The v\* value of the colour scaled by 2⁷.
+    pub v: i16,
+}
+
+impl LuvFixed {
+    /// Number of fractional bits in each of the coordinates.
+    pub const FRAC_BITS: u32 = 7;
+
+    /// Converts the colour into a floating point representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::LuvFixed { l: 6464, u: -64, v: 256 };
+    /// assert_eq!(luv::Luv { l: 50.5, u: -0.5, v: 2.0 }, luv.to_luv());
+    /// ```
+    pub fn to_luv(&self) -> Luv {
+        const SCALE: f32 = 1.0 / (1 << LuvFixed::FRAC_BITS) as f32;
+        Luv {
+            l: self.l as f32 * SCALE,
+            u: self.u as f32 * SCALE,
+            v: self.v as f32 * SCALE,
+        }
+    }
+}
+
+
+/// Value representing one in linear sRGB and XYZ coordinates.
+const ONE: i64 = 65535;
+
+/// Number of fractional bits in the conversion matrix coefficients and u′ and
+/// v′ chromaticity coordinates.
+const Q: u32 = 16;
+
+/// Linear sRGB values corresponding to 8-bit gamma-encoded values, scaled such
+/// that [`ONE`] represents full intensity.
+#[rustfmt::skip]
+static LINEAR_LUT: [u16; 256] = [
+        0,    20,    40,    60,    80,    99,   119,   139,
+      159,   179,   199,   219,   241,   264,   288,   313,
+      340,   367,   396,   427,   458,   491,   526,   562,
+      599,   637,   677,   718,   761,   805,   851,   898,
+      947,   997,  1048,  1101,  1156,  1212,  1270,  1330,
+     1391,  1453,  1517,  1583,  1651,  1720,  1790,  1863,
+     1937,  2013,  2090,  2170,  2250,  2333,  2418,  2504,
+     2592,  2681,  2773,  2866,  2961,  3058,  3157,  3258,
+     3360,  3464,  3570,  3678,  3788,  3900,  4014,  4129,
+     4247,  4366,  4488,  4611,  4736,  4864,  4993,  5124,
+     5257,  5392,  5530,  5669,  5810,  5953,  6099,  6246,
+     6395,  6547,  6700,  6856,  7014,  7174,  7335,  7500,
+     7666,  7834,  8004,  8177,  8352,  8528,  8708,  8889,
+     9072,  9258,  9445,  9635,  9828, 10022, 10219, 10417,
+    10619, 10822, 11028, 11235, 11446, 11658, 11873, 12090,
+    12309, 12530, 12754, 12980, 13209, 13440, 13673, 13909,
+    14146, 14387, 14629, 14874, 15122, 15371, 15623, 15878,
+    16135, 16394, 16656, 16920, 17187, 17456, 17727, 18001,
+    18277, 18556, 18837, 19121, 19407, 19696, 19987, 20281,
+    20577, 20876, 21177, 21481, 21787, 22096, 22407, 22721,
+    23038, 23357, 23678, 24002, 24329, 24658, 24990, 25325,
+    25662, 26001, 26344, 26688, 27036, 27386, 27739, 28094,
+    28452, 28813, 29176, 29542, 29911, 30282, 30656, 31033,
+    31412, 31794, 32179, 32567, 32957, 33350, 33745, 34143,
+    34544, 34948, 35355, 35764, 36176, 36591, 37008, 37429,
+    37852, 38278, 38706, 39138, 39572, 40009, 40449, 40891,
+    41337, 41785, 42236, 42690, 43147, 43606, 44069, 44534,
+    45002, 45473, 45947, 46423, 46903, 47385, 47871, 48359,
+    48850, 49344, 49841, 50341, 50844, 51349, 51858, 52369,
+    52884, 53401, 53921, 54445, 54971, 55500, 56032, 56567,
+    57105, 57646, 58190, 58737, 59287, 59840, 60396, 60955,
+    61517, 62082, 62650, 63221, 63795, 64372, 64952, 65535,
+];
+
+/// [`srgb::xyz::XYZ_FROM_SRGB_MATRIX`] with coefficients in fixed-point
+/// representation with [`Q`] fractional bits, i.e. each coefficient multiplied
+/// by 2¹⁶ and rounded to the nearest integer.
+#[rustfmt::skip]
+const MATRIX: [[i64; 3]; 3] = [
+    [27028, 23435, 11826],
+    [13936, 46869,  4730],
+    [ 1267,  7812, 62285],
+];
+
+/// [`crate::D65_U_PRIME`] (≈ 0.197833) multiplied by 2¹⁶ and rounded.
+const D65_U_PRIME: i64 = 12965;
+/// [`crate::D65_V_PRIME`] (≈ 0.468330) multiplied by 2¹⁶ and rounded.
+const D65_V_PRIME: i64 = 30693;
+
+/// Divides two non-negative numbers rounding to nearest integer.
+fn div_round(numerator: i64, denominator: i64) -> i64 {
+    (numerator + denominator / 2) / denominator
+}
+
+/// Returns cube root of `y / ONE` scaled by `ONE`.
+fn cbrt(y: i64) -> i64 {
+    let target = (y * ONE * ONE) as u64;
+    let (mut lo, mut hi) = (0u64, ONE as u64);
+    while lo < hi {
+        // u64::div_ceil requires Rust 1.73.
+        #[allow(clippy::manual_div_ceil)]
+        let mid = (lo + hi + 1) / 2;
+        if mid * mid * mid <= target {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    lo as i64
+}
+
+impl Luv {
+    /// Converts a three-element array of `u8`s into a [`LuvFixed`] using
+    /// integer arithmetic only.
+    ///
+    /// This is an alternative to [`Luv::from_rgb`] for platforms without
+    /// hardware floating point support.  Gamma expansion is done with
+    /// a look-up table and the rest of the conversion uses 64-bit integer
+    /// arithmetic.  Narrower types don’t suffice: a product of a 16-bit
+    /// linear value and a Q16 matrix coefficient already takes 32 bits and
+    /// the sum of three such products doesn’t fit in `i32`; similarly the
+    /// cube root is computed over values scaled by 2³².  On 32-bit targets the
+    /// 64-bit multiplications and divisions may be emulated in software but
+    /// that is still considerably cheaper than emulating floating point.
+    ///
+    /// Compared to the `f32` conversion (i.e. `Luv::from_rgb(rgb)`) each
+    /// coordinate of the result differs by no more than 0.06 for every 8-bit
+    /// sRGB colour.  For reference, the quantisation step of the fixed-point
+    /// representation is 1/128 ≈ 0.0078.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv::from_rgb_fixed(&[240, 33, 95]);
+    /// assert_eq!(luv::LuvFixed { l: 6699, u: 17791, v: 1003 }, luv);
+    /// ```
+    pub fn from_rgb_fixed(rgb: &[u8; 3]) -> LuvFixed {
+        let linear = [
+            LINEAR_LUT[rgb[0] as usize] as i64,
+            LINEAR_LUT[rgb[1] as usize] as i64,
+            LINEAR_LUT[rgb[2] as usize] as i64,
+        ];
+        let [x, y, z] = MATRIX.map(|row| {
+            let sum =
+                row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2];
+            (sum + (1 << (Q - 1))) >> Q
+        });
+        if y <= 0 {
+            return LuvFixed::default();
+        }
+
+        let one = 1 << LuvFixed::FRAC_BITS;
+        // See comment on crate::KAPPA and crate::EPSILON.
+        let l = if y * 24389 <= 216 * ONE {
+            div_round(24389 * one * y, 27 * ONE)
+        } else {
+            div_round(116 * one * cbrt(y), ONE) - 16 * one
+        };
+
+        let d = x + 15 * y + 3 * z;
+        let u_prime = div_round((4 * x) << Q, d);
+        let v_prime = div_round((9 * y) << Q, d);
+        let scale = |delta: i64| {
+            let value = 13 * l * delta;
+            let half = 1 << (Q - 1);
+            let value = if value < 0 {
+                value - half
+            } else {
+                value + half
+            };
+            (value / (1 << Q)) as i16
+        };
+        LuvFixed {
+            l: l as i16,
+            u: scale(u_prime - D65_U_PRIME),
+            v: scale(v_prime - D65_V_PRIME),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::Luv;
+
+    fn check(rgb: [u8; 3], max_error: &mut f32) {
+        let want = Luv::from_rgb(&rgb);
+        let got = Luv::from_rgb_fixed(&rgb).to_luv();
+        let error = (want.l - got.l)
+            .abs()
+            .max((want.u - got.u).abs())
+            .max((want.v - got.v).abs());
+        assert!(error <= 0.06, "{:?}: {:?} vs {:?}", rgb, want, got);
+        *max_error = max_error.max(error);
+    }
+
+    #[test]
+    fn test_greys() {
+        let mut max_error = 0.0;
+        for i in 0..=255 {
+            check([i, i, i], &mut max_error);
+        }
+        assert!(max_error > 0.0);
+    }
+
+    #[test]
+    fn test_colours() {
+        use rand::Rng;
+        let mut max_error = 0.0;
+        let rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([1u8; 32]);
+        for rgb in rng.sample_iter(&rand::distributions::Standard).take(10000) {
+            check(rgb, &mut max_error);
+        }
+        for rgb in [[255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 255, 0]] {
+            check(rgb, &mut max_error);
+        }
+    }
+}
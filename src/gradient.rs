@@ -0,0 +1,162 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Approximating gradients with sRGB stops.
+
+use crate::Luv;
+
+/// Approximates a gradient given as samples in L\*u\*v\* space with a small
+/// number of sRGB colour stops.
+///
+/// Samples in `luv_gradient` are assumed to be evenly spaced with the first
+/// sample at position zero and the last at position one.  Returns a list of
+/// `(position, rgb)` pairs suitable for formats which only support sRGB
+/// stops, e.g. CSS `linear-gradient`.  The first and last samples always
+/// become stops.
+///
+/// Stops are placed greedily: starting from a stop, the next one is put at
+/// the farthest sample such that all samples in between are within
+/// `max_error` from the colour obtained by linearly interpolating
+/// gamma-encoded sRGB components of the two stops (which is how CSS
+/// interpolates gradients by default).  The error is the Euclidean distance
+/// in L\*u\*v\* space, i.e. the CIE 1976 ΔE\*uv.  Since stops are quantised
+/// to `u8`s, the sample colours themselves aren’t reproduced exactly and the
+/// error bound only applies to samples between stops.  The greedy approach
+/// doesn’t guarantee the minimal number of stops but in practice is close.
+///
+/// # Examples
+///
+/// ```
+/// let black = luv::Luv::from_rgb(&[0, 0, 0]);
+/// let white = luv::Luv::from_rgb(&[255, 255, 255]);
+/// let gradient: Vec<luv::Luv> =
+///     (0..=10).map(|i| black.fade_to(&white, i as f32 / 10.0)).collect();
+/// let stops = luv::fit_srgb_stops(&gradient, 1.0);
+/// assert!(stops.len() > 2);
+/// assert_eq!((0.0, [0, 0, 0]), stops[0]);
+/// assert_eq!((1.0, [255, 255, 255]), stops[stops.len() - 1]);
+/// ```
+pub fn fit_srgb_stops(
+    luv_gradient: &[Luv],
+    max_error: f32,
+) -> Vec<(f32, [u8; 3])> {
+    let rgbs: Vec<[u8; 3]> = luv_gradient.iter().map(Luv::to_rgb).collect();
+    let last = match rgbs.len() {
+        0 => return Vec::new(),
+        1 => return vec![(0.0, rgbs[0])],
+        n => n - 1,
+    };
+    let max_error = max_error * max_error;
+    let fits = |start: usize, end: usize| {
+        let (from, to) = (&rgbs[start], &rgbs[end]);
+        (start + 1..end).all(|k| {
+            let t = (k - start) as f32 / (end - start) as f32;
+            let lerp = |i: usize| {
+                let (from, to) = (from[i] as f32, to[i] as f32);
+                crate::mul_add(to - from, t, from) / 255.0
+            };
+            let rgb = [lerp(0), lerp(1), lerp(2)];
+            Luv::from_rgb_normalized(&rgb).squared_distance(&luv_gradient[k]) <=
+                max_error
+        })
+    };
+
+    let position = |index: usize| index as f32 / last as f32;
+    let mut stops = vec![(0.0, rgbs[0])];
+    let mut start = 0;
+    for end in 2..=last {
+        if !fits(start, end) {
+            start = end - 1;
+            stops.push((position(start), rgbs[start]));
+        }
+    }
+    stops.push((1.0, rgbs[last]));
+    stops
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LCh;
+
+    #[test]
+    fn test_fit_srgb_stops() {
+        assert_eq!(Vec::<(f32, [u8; 3])>::new(), fit_srgb_stops(&[], 1.0));
+        let red = Luv::from_rgb(&[255, 0, 0]);
+        assert_eq!(vec![(0.0, [255, 0, 0])], fit_srgb_stops(&[red], 1.0));
+
+        // Gradient which is linear in sRGB needs only two stops.
+        let greys: Vec<Luv> =
+            (0..=255).map(|i| Luv::from_rgb(&[i, i, i])).collect();
+        assert_eq!(
+            vec![(0.0, [0, 0, 0]), (1.0, [255, 255, 255])],
+            fit_srgb_stops(&greys, 0.5)
+        );
+
+        // Hue sweep needs many.
+        let sweep: Vec<Luv> = (0..=100)
+            .map(|i| {
+                let h = i as f32 * std::f32::consts::TAU / 100.0;
+                LCh {
+                    l: 60.0,
+                    c: 40.0,
+                    h,
+                }
+                .to_luv()
+            })
+            .collect();
+        let stops = fit_srgb_stops(&sweep, 1.0);
+        assert!(stops.len() > 2, "{:?}", stops);
+        assert!(stops.len() < 50, "{:?}", stops);
+        assert_eq!(0.0, stops[0].0);
+        assert_eq!(1.0, stops[stops.len() - 1].0);
+        assert!(stops.windows(2).all(|w| w[0].0 < w[1].0), "{:?}", stops);
+
+        // Verify the error bound.
+        for pair in stops.windows(2) {
+            let ((from, from_rgb), (to, to_rgb)) = (pair[0], pair[1]);
+            for (i, luv) in sweep.iter().enumerate() {
+                let pos = i as f32 / 100.0;
+                if pos <= from || pos >= to {
+                    continue;
+                }
+                let t = (pos - from) / (to - from);
+                let lerp = |i: usize| {
+                    let (a, b) = (from_rgb[i] as f32, to_rgb[i] as f32);
+                    (a + (b - a) * t) / 255.0
+                };
+                let got =
+                    Luv::from_rgb_normalized(&[lerp(0), lerp(1), lerp(2)]);
+                let err = got.squared_distance(luv).sqrt();
+                assert!(
+                    err <= 1.001,
+                    "{}: {:?} vs {:?}: {}",
+                    pos,
+                    got,
+                    luv,
+                    err
+                );
+            }
+        }
+    }
+}
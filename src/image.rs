@@ -0,0 +1,772 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Operations on images, i.e. two-dimensional buffers of pixels.
+
+use crate::Luv;
+
+/// Blurs an image with a Gaussian kernel in L\*u\*v\* colour space.
+///
+/// `pixels` holds `height` rows of `width` pixels each stored in row-major
+/// order.  All three channels are convolved with a separable Gaussian kernel
+/// with standard deviation `sigma` (in pixels) truncated at three standard
+/// deviations.  Blurring in L\*u\*v\* rather than sRGB space avoids dark and
+/// desaturated fringes along edges between contrasting colours.
+///
+/// Pixels outside of the image are treated as copies of the nearest edge
+/// pixel (i.e. coordinates are clamped).  If `sigma` is not positive, the
+/// image is returned unchanged.
+///
+/// # Panics
+///
+/// Panics if length of `pixels` is not `width * height`.
+///
+/// # Example
+/// ```
+/// let black = luv::Luv::default();
+/// let white = luv::Luv::from_rgb(&[255, 255, 255]);
+/// let pixels = [black, white, black];
+/// let blurred = luv::gaussian_blur(&pixels, 3, 1, 1.0);
+/// assert!(blurred[0].l > 0.0 && blurred[1].l < white.l);
+/// assert_eq!(blurred[0], blurred[2]);
+/// ```
+pub fn gaussian_blur(
+    pixels: &[Luv],
+    width: usize,
+    height: usize,
+    sigma: f32,
+) -> Vec<Luv> {
+    assert_eq!(width * height, pixels.len(), "invalid image dimensions");
+    if sigma.is_nan() || sigma <= 0.0 || pixels.is_empty() {
+        return pixels.to_vec();
+    }
+
+    let kernel = gaussian_kernel(sigma);
+    let radius = (kernel.len() / 2) as isize;
+    let clamp = |idx: usize, offset: isize, len: usize| {
+        (idx as isize + offset).clamp(0, len as isize - 1) as usize
+    };
+    let convolve = |get: &dyn Fn(isize) -> Luv| {
+        let mut sum = [0.0f32; 3];
+        for (i, &weight) in kernel.iter().enumerate() {
+            let luv = get(i as isize - radius);
+            sum[0] += weight * luv.l;
+            sum[1] += weight * luv.u;
+            sum[2] += weight * luv.v;
+        }
+        Luv {
+            l: sum[0],
+            u: sum[1],
+            v: sum[2],
+        }
+    };
+
+    let horizontal = (0..pixels.len())
+        .map(|idx| {
+            let (x, row) = (idx % width, idx - idx % width);
+            convolve(&|off| pixels[row + clamp(x, off, width)])
+        })
+        .collect::<Vec<_>>();
+    (0..pixels.len())
+        .map(|idx| {
+            let (x, y) = (idx % width, idx / width);
+            convolve(&|off| horizontal[clamp(y, off, height) * width + x])
+        })
+        .collect()
+}
+
+/// Adjusts lightness of pixels in place leaving their u\* and v\* unchanged.
+///
+/// For each pixel, `f` is called with the pixel’s index in `pixels` and its
+/// current L\* and the result becomes the new L\*.  The slice is modified in
+/// place; no new buffer is allocated.  For images stored in row-major order
+/// the index can be used to compute pixel’s coordinates which allows
+/// implementing position-dependent corrections such as vignetting.
+///
+/// Since u\* and v\* are left as is, chroma and hue don’t change.  Note that
+/// this means that the u′v′ chromaticity of the pixels does change (compare
+/// with [`Luv::with_relative_luminance`] which scales u\* and v\*
+/// together with lightness).  Also note that the result isn’t clamped so
+/// callers need to make sure `f` keeps lightness in the 0–100 range if
+/// required.
+///
+/// # Example
+/// ```
+/// let mut pixels = luv::rgbs_to_luvs(&[[200, 40, 40], [10, 90, 200]]);
+/// let before = pixels.clone();
+/// // Exposure compensation.
+/// luv::adjust_lightness(&mut pixels, |_, l| l + 10.0);
+/// for (got, src) in pixels.iter().zip(before.iter()) {
+///     assert_eq!(src.l + 10.0, got.l);
+///     assert_eq!((src.u, src.v), (got.u, got.v));
+/// }
+/// ```
+pub fn adjust_lightness(pixels: &mut [Luv], f: impl Fn(usize, f32) -> f32) {
+    for (idx, luv) in pixels.iter_mut().enumerate() {
+        luv.l = f(idx, luv.l);
+    }
+}
+
+/// Multiplies chroma of pixels by given factor in place.
+///
+/// This is an allocation-free bulk counterpart of [`Luv::scale_chroma`]: u\*
+/// and v\* of each pixel are multiplied by `factor` while L\* is left
+/// unchanged.  It can be used to implement a global saturation adjustment.
+/// Factor below one desaturates the image (zero turns it into greyscale) and
+/// factor above one makes it more colourful.  Note that the latter may push
+/// colours outside of the sRGB gamut; they are then clamped when converted
+/// to sRGB (see [`Luv::to_rgb`]).
+///
+/// # Example
+/// ```
+/// let mut pixels = luv::rgbs_to_luvs(&[[200, 40, 40], [10, 90, 200]]);
+/// let src = pixels.clone();
+/// luv::scale_chroma_in_place(&mut pixels, 0.5);
+/// assert_eq!(src[0].scale_chroma(0.5), pixels[0]);
+/// assert_eq!(src[1].scale_chroma(0.5), pixels[1]);
+/// ```
+pub fn scale_chroma_in_place(pixels: &mut [Luv], factor: f32) {
+    for luv in pixels.iter_mut() {
+        luv.u *= factor;
+        luv.v *= factor;
+    }
+}
+
+/// Adjusts white balance of pixels in place.
+///
+/// This implements the classic temperature and tint controls by shifting
+/// colours along the chromatic axes of L\*u\*v\* space:
+/// `temperature_shift` is added to v\* (positive values move colours towards
+/// yellow, i.e. make the image warmer, negative towards blue) and
+/// `tint_shift` is added to u\* (positive values move colours towards
+/// magenta, negative towards green).  L\* is left unchanged so lightness of
+/// the pixels is preserved.  Note that the result isn’t clamped so large
+/// shifts may push colours outside of the sRGB gamut.
+///
+/// # Example
+/// ```
+/// let mut pixels = luv::rgbs_to_luvs(&[[128, 128, 128], [10, 90, 200]]);
+/// let src = pixels.clone();
+/// luv::white_balance(&mut pixels, 5.0, -2.0);
+/// for (got, src) in pixels.iter().zip(src.iter()) {
+///     assert_eq!((src.l, src.u - 2.0, src.v + 5.0), (got.l, got.u, got.v));
+/// }
+/// ```
+pub fn white_balance(
+    pixels: &mut [Luv],
+    temperature_shift: f32,
+    tint_shift: f32,
+) {
+    for luv in pixels.iter_mut() {
+        luv.u += tint_shift;
+        luv.v += temperature_shift;
+    }
+}
+
+/// Returns indices of pixels which fall outside of the sRGB gamut after
+/// applying given transform.
+///
+/// The `transform` is evaluated for each pixel independently and the index
+/// of the pixel is included in the result if the transformed colour isn’t
+/// displayable (see [`Luv::is_displayable`]), i.e. it would be clipped when
+/// converted to sRGB.  `original` isn’t modified which makes the function
+/// suitable for previewing an edit before it’s applied.  Indices are
+/// returned in increasing order.
+///
+/// # Example
+/// ```
+/// let pixels = luv::rgbs_to_luvs(&[[255, 0, 0], [128, 128, 128]]);
+/// let boost = |luv: luv::Luv| luv.scale_chroma(1.5);
+/// assert_eq!(vec![0], luv::clipping_pixels(&pixels, boost));
+/// ```
+pub fn clipping_pixels(
+    original: &[Luv],
+    transform: impl Fn(Luv) -> Luv,
+) -> Vec<usize> {
+    original
+        .iter()
+        .enumerate()
+        .filter(|(_, luv)| !transform(**luv).is_displayable())
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Computes perceptual energy map of an image, e.g. for seam carving.
+///
+/// `pixels` holds `height` rows of `width` pixels each stored in row-major
+/// order.  The energy of a pixel is the magnitude of Sobel gradient computed
+/// over all three L\*u\*v\* channels at once: horizontal and vertical
+/// gradients are vectors in L\*u\*v\* space and the energy is
+/// `√(|Gx|² + |Gy|²)` where `|·|` is the Euclidean norm.  Because chroma
+/// contributes to the gradient, edges between colours of the same lightness
+/// but different hue have high energy as well, unlike in gradient maps
+/// computed on lightness (or grey levels) only.
+///
+/// The gradients are scaled by ¼ so that on a sharp straight edge between
+/// two flat regions the pixels adjacent to the edge have energy equal to
+/// the distance between the colours (see [`Luv::squared_distance`]).  Flat
+/// regions have zero energy.
+///
+/// Pixels outside of the image are treated as copies of the nearest edge
+/// pixel (i.e. coordinates are clamped) so the image border doesn’t add any
+/// energy by itself.
+///
+/// # Panics
+///
+/// Panics if length of `pixels` is not `width * height`.
+///
+/// # Example
+/// ```
+/// let red = luv::Luv::from_rgb(&[255, 0, 0]);
+/// let grey = luv::Luv::from_rgb(&[128, 128, 128]);
+/// let pixels = [red, red, grey, grey];
+/// let energy = luv::perceptual_energy(&pixels, 4, 1);
+/// let distance = red.squared_distance(&grey).sqrt();
+/// assert_eq!(0.0, energy[0]);
+/// assert!((energy[1] - distance).abs() < 0.001);
+/// assert!((energy[2] - distance).abs() < 0.001);
+/// assert_eq!(0.0, energy[3]);
+/// ```
+pub fn perceptual_energy(
+    pixels: &[Luv],
+    width: usize,
+    height: usize,
+) -> Vec<f32> {
+    assert_eq!(width * height, pixels.len(), "invalid image dimensions");
+    let at = |x: usize, dx: isize, y: usize, dy: isize| {
+        let x = (x as isize + dx).clamp(0, width as isize - 1) as usize;
+        let y = (y as isize + dy).clamp(0, height as isize - 1) as usize;
+        pixels[y * width + x].as_array()
+    };
+    (0..pixels.len())
+        .map(|idx| {
+            let (x, y) = (idx % width, idx / width);
+            let mut energy = 0.0;
+            for ch in 0..3 {
+                let d = |from: (isize, isize), to: (isize, isize)| {
+                    at(x, to.0, y, to.1)[ch] - at(x, from.0, y, from.1)[ch]
+                };
+                let gx = d((-1, -1), (1, -1)) +
+                    2.0 * d((-1, 0), (1, 0)) +
+                    d((-1, 1), (1, 1));
+                let gy = d((-1, -1), (-1, 1)) +
+                    2.0 * d((0, -1), (0, 1)) +
+                    d((1, -1), (1, 1));
+                energy += gx * gx + gy * gy;
+            }
+            energy.sqrt() * 0.25
+        })
+        .collect()
+}
+
+/// Returns normalised Gaussian kernel truncated at three standard deviations.
+fn gaussian_kernel(sigma: f32) -> Vec<f32> {
+    let radius = (sigma * 3.0).ceil() as isize;
+    let scale = -0.5 / (sigma * sigma);
+    let kernel = (-radius..=radius)
+        .map(|i| ((i * i) as f32 * scale).exp())
+        .collect::<Vec<_>>();
+    let sum: f32 = kernel.iter().sum();
+    kernel.into_iter().map(|weight| weight / sum).collect()
+}
+
+/// Enhances local contrast of an image by equalising its lightness.
+///
+/// This performs contrast limited adaptive histogram equalisation (CLAHE) on
+/// the L\* channel leaving u\* and v\* untouched.  Since lightness is
+/// perceptually uniform and independent of chromaticity, this avoids hue
+/// shifts which equalising sRGB channels causes.
+///
+/// `pixels` holds `height` rows of `width` pixels each stored in row-major
+/// order.  The image is divided into a grid of `tiles` × `tiles` rectangles
+/// (zero is treated as one and the number of tiles along each axis is at
+/// most the image’s size) and for each a histogram of lightness with 256
+/// bins covering the 0–100 range is computed.  Each histogram is then
+/// clipped such that no bin has more than `clip_limit` times the average
+/// number of pixels per bin and the excess is distributed evenly among all
+/// bins.  Cumulative distribution of the clipped histogram defines the
+/// mapping of lightness in given tile.  Finally, each pixel’s new lightness
+/// is bilinearly interpolated between mappings of the (up to) four nearest
+/// tile centres which avoids visible tile boundaries.
+///
+/// More tiles make the equalisation more local.  Larger `clip_limit` allows
+/// stronger contrast enhancement (and noise amplification); a limit of zero
+/// (or less) makes the histogram flat which results in (up to quantisation)
+/// no change while infinity results in plain adaptive histogram
+/// equalisation.  Values between two and four are typical.
+///
+/// # Panics
+///
+/// Panics if length of `pixels` is not `width * height`.
+///
+/// # Example
+/// ```
+/// let pixels: Vec<luv::Luv> = (0..64)
+///     .map(|i| luv::Luv { l: 40.0 + i as f32 / 8.0, u: 10.0, v: 5.0 })
+///     .collect();
+/// let got = luv::equalize_lightness(&pixels, 8, 8, 2, 3.0);
+/// let spread = |pixels: &[luv::Luv]| pixels[63].l - pixels[0].l;
+/// assert!(spread(&got) > spread(&pixels));
+/// assert!(got.iter().all(|luv| luv.u == 10.0 && luv.v == 5.0));
+/// ```
+pub fn equalize_lightness(
+    pixels: &[Luv],
+    width: usize,
+    height: usize,
+    tiles: usize,
+    clip_limit: f32,
+) -> Vec<Luv> {
+    const BINS: usize = 256;
+    assert_eq!(width * height, pixels.len(), "invalid image dimensions");
+    if pixels.is_empty() {
+        return Vec::new();
+    }
+    let (tiles_x, tiles_y) = (tiles.clamp(1, width), tiles.clamp(1, height));
+    let bin = |luv: &Luv| {
+        let bin = (luv.l.clamp(0.0, 100.0) * (BINS as f32 / 100.0)) as usize;
+        bin.min(BINS - 1)
+    };
+    // Tile `i` along an axis of length `len` spans [start(i), start(i + 1)).
+    let start = |i: usize, len: usize, count: usize| i * len / count;
+
+    // Lightness mapping for each tile.
+    let mut mappings = Vec::with_capacity(tiles_x * tiles_y);
+    for ty in 0..tiles_y {
+        for tx in 0..tiles_x {
+            let mut histogram = [0.0f32; BINS];
+            let (x0, x1) =
+                (start(tx, width, tiles_x), start(tx + 1, width, tiles_x));
+            let (y0, y1) =
+                (start(ty, height, tiles_y), start(ty + 1, height, tiles_y));
+            for y in y0..y1 {
+                for luv in &pixels[y * width + x0..y * width + x1] {
+                    histogram[bin(luv)] += 1.0;
+                }
+            }
+
+            let total = ((x1 - x0) * (y1 - y0)) as f32;
+            let limit = (clip_limit * total / BINS as f32).max(0.0);
+            let mut excess = 0.0;
+            for count in histogram.iter_mut() {
+                excess += (*count - limit).max(0.0);
+                *count = count.min(limit);
+            }
+            let extra = excess / BINS as f32;
+
+            let mut mapping = [0.0f32; BINS];
+            let mut cdf = 0.0;
+            for (out, count) in mapping.iter_mut().zip(histogram.iter()) {
+                cdf += count + extra;
+                *out = (cdf / total * 100.0).min(100.0);
+            }
+            mappings.push(mapping);
+        }
+    }
+
+    // Position of a pixel relative to tile centres as a pair of indices of
+    // the neighbouring tiles and interpolation factor between them.
+    let locate = |pos: usize, len: usize, count: usize| {
+        let pos = (pos as f32 + 0.5) * count as f32 / len as f32 - 0.5;
+        let pos = pos.clamp(0.0, (count - 1) as f32);
+        let lo = pos as usize;
+        (lo, (lo + 1).min(count - 1), pos - lo as f32)
+    };
+    let lerp = |a: f32, b: f32, t: f32| crate::mul_add(b - a, t, a);
+
+    pixels
+        .iter()
+        .enumerate()
+        .map(|(idx, luv)| {
+            let (x0, x1, tx) = locate(idx % width, width, tiles_x);
+            let (y0, y1, ty) = locate(idx / width, height, tiles_y);
+            let b = bin(luv);
+            let map = |x: usize, y: usize| mappings[y * tiles_x + x][b];
+            let top = lerp(map(x0, y0), map(x1, y0), tx);
+            let bottom = lerp(map(x0, y1), map(x1, y1), tx);
+            Luv {
+                l: lerp(top, bottom, ty),
+                ..*luv
+            }
+        })
+        .collect()
+}
+
+/// Returns per-pixel colour difference between two images.
+///
+/// The difference of each pair of pixels is the Euclidean distance in
+/// L\*u\*v\* space, i.e. CIE 1976 ΔE\*uv, which is the square root of
+/// [`Luv::squared_distance`].  Unlike differences of sRGB components, it
+/// approximates how different the pixels look.  Use [`DiffStats::new`] to
+/// summarise the result.
+///
+/// # Panics
+///
+/// Panics if the two images don’t have the same number of pixels.
+///
+/// # Example
+/// ```
+/// let a = luv::rgbs_to_luvs(&[[255, 0, 0], [0, 0, 0]]);
+/// let b = luv::rgbs_to_luvs(&[[255, 0, 0], [255, 255, 255]]);
+/// assert_eq!(vec![0.0, 100.0], luv::difference_map(&a, &b));
+/// ```
+pub fn difference_map(a: &[Luv], b: &[Luv]) -> Vec<f32> {
+    assert_eq!(a.len(), b.len(), "images must have the same size");
+    a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| a.squared_distance(b).sqrt())
+        .collect()
+}
+
+/// Summary statistics of a difference map.
+///
+/// See [`difference_map`].
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct DiffStats {
+    /// The largest difference.
+    pub max: f32,
+    /// Arithmetic mean of the differences.
+    pub mean: f32,
+    /// The 95th percentile of the differences, i.e. the smallest difference
+    /// which is greater or equal to at least 95% of the differences.
+    pub p95: f32,
+}
+
+impl DiffStats {
+    /// Calculates statistics of given differences.
+    ///
+    /// For an empty slice all statistics are zero.
+    ///
+    /// # Example
+    /// ```
+    /// let diffs = (1..=100).map(|d| d as f32).collect::<Vec<_>>();
+    /// let stats = luv::DiffStats::new(&diffs);
+    /// assert_eq!(luv::DiffStats { max: 100.0, mean: 50.5, p95: 95.0 }, stats);
+    /// ```
+    pub fn new(differences: &[f32]) -> Self {
+        if differences.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = differences.to_vec();
+        sorted.sort_by(f32::total_cmp);
+        let sum = sorted.iter().map(|&d| d as f64).sum::<f64>();
+        // usize::div_ceil requires Rust 1.73.
+        #[allow(clippy::manual_div_ceil)]
+        let rank = (sorted.len() * 95 + 99) / 100;
+        Self {
+            max: sorted[sorted.len() - 1],
+            mean: (sum / sorted.len() as f64) as f32,
+            p95: sorted[rank - 1],
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::Luv;
+
+    #[test]
+    fn test_equalize_lightness() {
+        assert_eq!(
+            Vec::<Luv>::new(),
+            super::equalize_lightness(&[], 0, 0, 4, 2.0)
+        );
+
+        // Low-contrast horizontal gradient.
+        const WIDTH: usize = 64;
+        const HEIGHT: usize = 16;
+        let pixels: Vec<Luv> = (0..WIDTH * HEIGHT)
+            .map(|idx| Luv {
+                l: 40.0 + (idx % WIDTH) as f32 * 20.0 / WIDTH as f32,
+                u: (idx / WIDTH) as f32,
+                v: -5.0,
+            })
+            .collect();
+        let spread = |pixels: &[Luv]| {
+            let (min, max) =
+                pixels.iter().fold((100.0f32, 0.0f32), |acc, luv| {
+                    (acc.0.min(luv.l), acc.1.max(luv.l))
+                });
+            max - min
+        };
+
+        for &tiles in &[0, 1, 2, 4] {
+            let got =
+                super::equalize_lightness(&pixels, WIDTH, HEIGHT, tiles, 3.0);
+            assert!(spread(&got) > 1.5 * spread(&pixels), "{}", tiles);
+            for (src, dst) in pixels.iter().zip(got.iter()) {
+                assert_eq!((src.u, src.v), (dst.u, dst.v));
+                assert!((0.0..=100.0).contains(&dst.l), "{:?}", dst);
+            }
+            // With a single tile, the mapping is the same for all pixels so
+            // order of lightness along each row is preserved.
+            for row in got.chunks_exact(WIDTH).filter(|_| tiles <= 1) {
+                assert!(row.windows(2).all(|w| w[0].l <= w[1].l + 1e-4));
+            }
+        }
+
+        // Clip limit of zero flattens the histogram so the mapping is close
+        // to identity.
+        let got = super::equalize_lightness(&pixels, WIDTH, HEIGHT, 2, 0.0);
+        for (src, dst) in pixels.iter().zip(got.iter()) {
+            assert!((src.l - dst.l).abs() < 0.5, "{:?} {:?}", src, dst);
+        }
+    }
+
+    #[test]
+    fn test_gaussian_blur() {
+        const SIZE: usize = 11;
+        const CENTRE: usize = SIZE / 2;
+        let bright = Luv {
+            l: 100.0,
+            u: 50.0,
+            v: -20.0,
+        };
+        let mut pixels = vec![Luv::default(); SIZE * SIZE];
+        pixels[CENTRE * SIZE + CENTRE] = bright;
+
+        let got = super::gaussian_blur(&pixels, SIZE, SIZE, 1.0);
+        let at = |x: usize, y: usize| got[y * SIZE + x];
+
+        // Energy is preserved since the kernel doesn’t reach the edges.
+        let total = got.iter().map(|luv| luv.l).sum::<f32>();
+        approx::assert_abs_diff_eq!(100.0, total, epsilon = 0.001);
+        let total = got.iter().map(|luv| luv.u).sum::<f32>();
+        approx::assert_abs_diff_eq!(50.0, total, epsilon = 0.001);
+
+        // The result is symmetric and decreases away from the centre.
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let luv = at(x, y);
+                let eq = |other: Luv| {
+                    approx::abs_diff_eq!(luv, other, epsilon = 1e-6)
+                };
+                assert!(eq(at(SIZE - 1 - x, y)), "({}, {})", x, y);
+                assert!(eq(at(x, SIZE - 1 - y)), "({}, {})", x, y);
+                assert!(eq(at(y, x)), "({}, {})", x, y);
+            }
+        }
+        for d in 0..CENTRE {
+            assert!(at(CENTRE + d, CENTRE).l >= at(CENTRE + d + 1, CENTRE).l);
+        }
+        assert!(at(CENTRE, CENTRE).l < 100.0);
+        assert!(at(CENTRE + 1, CENTRE + 1).l > 0.0);
+        assert_eq!(0.0, at(0, 0).l);
+
+        assert_eq!(pixels, super::gaussian_blur(&pixels, SIZE, SIZE, 0.0));
+    }
+
+    #[test]
+    fn test_adjust_lightness() {
+        let src = crate::rgbs_to_luvs(&[
+            [200, 40, 40],
+            [10, 90, 200],
+            [128, 128, 128],
+            [0, 0, 0],
+        ]);
+
+        let mut pixels = src.clone();
+        super::adjust_lightness(&mut pixels, |_, l| l + 10.0);
+        for (got, src) in pixels.iter().zip(src.iter()) {
+            assert!(got.l > src.l);
+            approx::assert_abs_diff_eq!(src.l + 10.0, got.l);
+            let (got, src) =
+                (crate::LCh::from_luv(*got), crate::LCh::from_luv(*src));
+            assert_eq!((src.c, src.h), (got.c, got.h));
+        }
+
+        // Vignette depending on position.
+        let mut pixels = src.clone();
+        super::adjust_lightness(&mut pixels, |idx, l| {
+            l * (1.0 - idx as f32 / 8.0)
+        });
+        for (idx, (got, src)) in pixels.iter().zip(src.iter()).enumerate() {
+            assert_eq!(src.l * (1.0 - idx as f32 / 8.0), got.l);
+            assert_eq!((src.u, src.v), (got.u, got.v));
+        }
+
+        super::adjust_lightness(&mut [], |_, _| unreachable!());
+    }
+
+    #[test]
+    fn test_white_balance() {
+        let src = crate::rgbs_to_luvs(&[[0, 0, 0], [128, 128, 128], [255; 3]]);
+
+        // Positive temperature makes neutral pixels warmer.
+        let mut pixels = src.clone();
+        super::white_balance(&mut pixels, 10.0, 0.0);
+        for (got, src) in pixels.iter().zip(src.iter()) {
+            assert_eq!((src.l, src.u), (got.l, got.u));
+            assert!(got.v > 9.9, "{:?}", got);
+        }
+        let rgb = pixels[1].to_rgb();
+        assert!(rgb[0] > rgb[2], "{:?}", rgb);
+
+        // Tint shifts u* only.
+        let mut pixels = src.clone();
+        super::white_balance(&mut pixels, 0.0, -10.0);
+        for (got, src) in pixels.iter().zip(src.iter()) {
+            assert_eq!((src.l, src.v), (got.l, got.v));
+            assert!(got.u < -9.9, "{:?}", got);
+        }
+
+        let mut pixels = src.clone();
+        super::white_balance(&mut pixels, 0.0, 0.0);
+        assert_eq!(src, pixels);
+    }
+
+    #[test]
+    fn test_clipping_pixels() {
+        let pixels = crate::rgbs_to_luvs(&[
+            [255, 0, 0],
+            [150, 120, 110],
+            [0, 0, 255],
+            [128, 128, 128],
+            [0, 255, 0],
+        ]);
+        let boost = |luv: Luv| luv.scale_chroma(1.5);
+        assert_eq!(vec![0, 2, 4], super::clipping_pixels(&pixels, boost));
+        assert_eq!(Vec::<usize>::new(), super::clipping_pixels(&pixels, |l| l));
+        let brighten = |mut luv: Luv| {
+            luv.l += 50.0;
+            luv
+        };
+        let got = super::clipping_pixels(&pixels[1..4], brighten);
+        assert_eq!(vec![0, 1, 2], got);
+    }
+
+    #[test]
+    fn test_scale_chroma_in_place() {
+        let src = crate::rgbs_to_luvs(&[
+            [200, 40, 40],
+            [10, 90, 200],
+            [128, 128, 128],
+            [255, 255, 0],
+        ]);
+
+        let mut pixels = src.clone();
+        super::scale_chroma_in_place(&mut pixels, 0.0);
+        for (got, src) in pixels.iter().zip(src.iter()) {
+            assert_eq!(src.neutral(), *got);
+        }
+
+        for factor in [0.5, 1.0, 1.5] {
+            let mut pixels = src.clone();
+            super::scale_chroma_in_place(&mut pixels, factor);
+            for (got, src) in pixels.iter().zip(src.iter()) {
+                assert_eq!(src.scale_chroma(factor), *got);
+                assert_eq!(src.l, got.l);
+            }
+        }
+
+        super::scale_chroma_in_place(&mut [], 2.0);
+    }
+
+    #[test]
+    fn test_perceptual_energy() {
+        const SIZE: usize = 8;
+        // Two halves with the same lightness but opposite hues.
+        let lch = |h: f32| {
+            crate::LCh {
+                l: 60.0,
+                c: 50.0,
+                h,
+            }
+            .to_luv()
+        };
+        let (left, right) = (lch(0.5), lch(0.5 + std::f32::consts::PI));
+        assert_eq!(left.l, right.l);
+        let pixels = (0..SIZE * SIZE)
+            .map(|idx| if idx % SIZE < SIZE / 2 { left } else { right })
+            .collect::<Vec<_>>();
+
+        let energy = super::perceptual_energy(&pixels, SIZE, SIZE);
+        let distance = left.squared_distance(&right).sqrt();
+        assert!(distance > 90.0, "{}", distance);
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let got = energy[y * SIZE + x];
+                if x == SIZE / 2 - 1 || x == SIZE / 2 {
+                    approx::assert_abs_diff_eq!(distance, got, epsilon = 0.01);
+                } else {
+                    assert_eq!(0.0, got, "({}, {})", x, y);
+                }
+            }
+        }
+
+        // Lightness-only gradient would miss the edge entirely.
+        let lightness = pixels
+            .iter()
+            .map(|luv| Luv {
+                l: luv.l,
+                u: 0.0,
+                v: 0.0,
+            })
+            .collect::<Vec<_>>();
+        let energy = super::perceptual_energy(&lightness, SIZE, SIZE);
+        assert!(energy.iter().all(|&e| e == 0.0));
+
+        // Diagonal edges are detected as well.
+        let pixels = (0..SIZE * SIZE)
+            .map(|idx| if idx % SIZE > idx / SIZE { left } else { right })
+            .collect::<Vec<_>>();
+        let energy = super::perceptual_energy(&pixels, SIZE, SIZE);
+        assert!(energy[SIZE + 1] > distance, "{}", energy[SIZE + 1]);
+        assert_eq!(0.0, energy[SIZE - 1]);
+
+        assert_eq!(Vec::<f32>::new(), super::perceptual_energy(&[], 0, 0));
+    }
+
+    #[test]
+    fn test_difference_map() {
+        let image =
+            crate::rgbs_to_luvs(&[[255, 0, 0], [0, 128, 255], [30, 30, 30], [
+                200, 200, 100,
+            ]]);
+        let diffs = super::difference_map(&image, &image);
+        assert_eq!(vec![0.0; 4], diffs);
+        assert_eq!(super::DiffStats::default(), super::DiffStats::new(&diffs));
+
+        let shifted = image
+            .iter()
+            .map(|luv| Luv {
+                l: luv.l + 3.0,
+                u: luv.u - 4.0,
+                v: luv.v,
+            })
+            .collect::<Vec<_>>();
+        let diffs = super::difference_map(&image, &shifted);
+        for &diff in diffs.iter() {
+            approx::assert_abs_diff_eq!(5.0, diff, epsilon = 0.0001);
+        }
+        let stats = super::DiffStats::new(&diffs);
+        approx::assert_abs_diff_eq!(5.0, stats.max, epsilon = 0.0001);
+        approx::assert_abs_diff_eq!(5.0, stats.mean, epsilon = 0.0001);
+        approx::assert_abs_diff_eq!(5.0, stats.p95, epsilon = 0.0001);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_difference_map_size_mismatch() {
+        super::difference_map(&[Luv::default()], &[]);
+    }
+}
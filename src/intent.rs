@@ -0,0 +1,196 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Gamut mapping of colours when converting to sRGB.
+
+use crate::{LCh, Luv};
+
+/// Strategy for converting colours outside of the sRGB gamut.
+///
+/// See [`Luv::to_rgb_with_intent`] and [`LCh::to_rgb_with_intent`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RenderingIntent {
+    /// Clamps each of the sRGB components to the valid range.  This is what
+    /// [`Luv::to_rgb`] does.  It’s the cheapest option but it may change hue
+    /// and lightness of out-of-gamut colours considerably.
+    Clip,
+    /// Clamps lightness to the 0–100 range and then reduces chroma until the
+    /// colour becomes displayable while preserving hue.  Displayable colours
+    /// are converted unchanged.
+    PreserveHueReduceChroma,
+}
+
+impl Luv {
+    /// Converts the colour to sRGB mapping it into the gamut according to
+    /// given rendering intent.
+    ///
+    /// With [`RenderingIntent::PreserveHueReduceChroma`], chroma is reduced
+    /// by scaling u\* and v\* proportionally.  The scale is found with
+    /// a binary search stopping once chroma is known with precision of 0.001.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luv::RenderingIntent;
+    ///
+    /// let luv = luv::Luv { l: 50.0, u: -150.0, v: 0.0 };
+    /// let rgb = luv.to_rgb_with_intent(RenderingIntent::Clip);
+    /// assert_eq!([0, 167, 136], rgb);
+    /// let intent = RenderingIntent::PreserveHueReduceChroma;
+    /// assert_eq!([0, 134, 124], luv.to_rgb_with_intent(intent));
+    /// ```
+    pub fn to_rgb_with_intent(&self, intent: RenderingIntent) -> [u8; 3] {
+        if intent == RenderingIntent::Clip || self.is_displayable() {
+            return self.to_rgb();
+        }
+        let l = self.l.clamp(0.0, 100.0);
+        // At the extremes of lightness, only black and white are displayable.
+        let chroma = if l > 0.0 && l < 100.0 {
+            self.u.hypot(self.v)
+        } else {
+            0.0
+        };
+        let scaled = |c: f32| {
+            let scale = if chroma > 0.0 { c / chroma } else { 0.0 };
+            Luv {
+                l,
+                u: self.u * scale,
+                v: self.v * scale,
+            }
+        };
+        let (mut lo, mut hi) = (0.0, chroma);
+        while hi - lo > 0.001 {
+            let c = (lo + hi) * 0.5;
+            if scaled(c).is_displayable() {
+                lo = c;
+            } else {
+                hi = c;
+            }
+        }
+        scaled(lo).to_rgb()
+    }
+}
+
+impl LCh {
+    /// Converts the colour to sRGB mapping it into the gamut according to
+    /// given rendering intent.
+    ///
+    /// This is equivalent to [`Luv::to_rgb_with_intent`] except that with
+    /// [`RenderingIntent::PreserveHueReduceChroma`], the chroma is reduced
+    /// directly by lowering `c` to [`LCh::max_chroma`] rather than by scaling
+    /// u\* and v\* coordinates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luv::RenderingIntent;
+    ///
+    /// let lch = luv::LCh { l: 50.0, c: 150.0, h: std::f32::consts::PI };
+    /// let intent = RenderingIntent::PreserveHueReduceChroma;
+    /// assert_eq!([0, 134, 124], lch.to_rgb_with_intent(intent));
+    /// ```
+    pub fn to_rgb_with_intent(&self, intent: RenderingIntent) -> [u8; 3] {
+        if intent == RenderingIntent::Clip || self.is_displayable() {
+            return self.to_rgb();
+        }
+        let l = self.l.clamp(0.0, 100.0);
+        let h = if self.c < 0.0 {
+            self.h + std::f32::consts::PI
+        } else {
+            self.h
+        };
+        let c = self.c.abs().min(LCh::max_chroma(l, h));
+        LCh { l, c, h }.to_rgb()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::RenderingIntent;
+    use crate::{LCh, Luv};
+
+    #[test]
+    fn test_to_rgb_with_intent() {
+        const INTENT: RenderingIntent =
+            RenderingIntent::PreserveHueReduceChroma;
+
+        // Displayable colours are converted unchanged.
+        for rgb in [[0, 0, 0], [255, 255, 255], [255, 0, 0], [12, 200, 99]] {
+            let luv = Luv::from_rgb(&rgb);
+            assert_eq!(rgb, luv.to_rgb_with_intent(INTENT));
+            assert_eq!(rgb, luv.to_rgb_with_intent(RenderingIntent::Clip));
+            assert_eq!(rgb, LCh::from_luv(luv).to_rgb_with_intent(INTENT));
+        }
+
+        // Chroma reduction in Luv and LCh agree and preserves hue.
+        for i in 0..64 {
+            let h = i as f32 * std::f32::consts::TAU / 64.0;
+            for l in [-5.0, 10.0, 30.0, 50.0, 70.0, 90.0, 105.0] {
+                let lch = LCh { l, c: 190.0, h };
+                let luv = lch.to_luv();
+                assert_eq!(
+                    luv.to_rgb(),
+                    luv.to_rgb_with_intent(RenderingIntent::Clip)
+                );
+                assert_eq!(
+                    lch.to_rgb(),
+                    lch.to_rgb_with_intent(RenderingIntent::Clip)
+                );
+
+                let from_lch = lch.to_rgb_with_intent(INTENT);
+                let from_luv = luv.to_rgb_with_intent(INTENT);
+                for (a, b) in from_lch.iter().zip(from_luv.iter()) {
+                    assert!(
+                        (*a as i32 - *b as i32).abs() <= 1,
+                        "{:?}: {:?} vs {:?}",
+                        lch,
+                        from_lch,
+                        from_luv
+                    );
+                }
+
+                if l > 15.0 && l < 95.0 {
+                    let got = LCh::from_rgb(&from_lch);
+                    let dh = crate::hue_delta(h, got.h).abs();
+                    assert!(dh < 0.05, "{:?} {:?}", lch, got);
+                }
+            }
+        }
+
+        // Negative chroma flips hue.
+        let lch = LCh {
+            l: 50.0,
+            c: -150.0,
+            h: 0.0,
+        };
+        let want = LCh {
+            l: 50.0,
+            c: 150.0,
+            h: std::f32::consts::PI,
+        };
+        assert_eq!(
+            want.to_rgb_with_intent(INTENT),
+            lch.to_rgb_with_intent(INTENT)
+        );
+    }
+}
@@ -52,7 +52,17 @@
 //! * `luv::rgbs_to_luvs(rgbs: &[[u8; 3]]) -> Vec<Luv>`
 //! * `luv::luvs_to_rgbs(luvs: &[Luv]) -> Vec<[u8; 3]>`
 //! * `luv::rgb_bytes_to_luvs(bytes: &[u8]) -> Vec<Luv>`
+//! * `luv::rgb_bytes_to_lchs(bytes: &[u8]) -> Vec<LCh>`
+//! * `luv::grey_bytes_to_luvs(bytes: &[u8]) -> Vec<Luv>`
+//!   (one byte per pixel)
+//! * `luv::lchs_from_rgb_bytes(bytes: &[u8]) -> impl Iterator<Item = LCh>`
+//!   (converts lazily)
+//! * `luv::luvs_from_rgba_bytes(bytes: &[u8]) -> impl Iterator<Item = Luv>`
+//!   (converts lazily, ignores alpha)
 //! * `luv::luvs_to_rgb_bytes(luvs: &[Luv]) -> Vec<u8>`
+//! * `luv::luvs_to_rgba_bytes(luvs: &[Luv], alpha: u8) -> Vec<u8>`
+//! * `luv::luvs_to_rgb_bytes_ordered(luvs: &[Luv], width: usize) -> Vec<u8>`
+//!   (applies ordered dithering)
 //!
 //! ```rust
 //! let rgbs = vec![
@@ -92,6 +102,26 @@
 //! defined by [`approx` crate](https://crates.io/crates/approx) will be
 //! implemented for the `Luv` and `LCh` types.
 //!
+//! The `fixed` feature enables `Luv::from_rgb_fixed` function which converts
+//! sRGB colours into L\*u\*v\* colour space using integer arithmetic only.
+//! This is useful on platforms without hardware floating point support.
+//!
+//! The `rand` feature enables `random_displayable` and `random_luv` functions
+//! which generate random colours using [`rand`
+//! crate](https://crates.io/crates/rand).
+//!
+//! The `bytemuck` feature implements `Pod` and `Zeroable` traits from
+//! [`bytemuck` crate](https://crates.io/crates/bytemuck) for the `Luv` and
+//! `LCh` types which allows safely casting slices of colours to slices of
+//! `f32`s (e.g. for uploading to GPU).
+//!
+//! The `rayon` feature enables `par_quantize` function which maps pixels to
+//! nearest palette colours in parallel using [`rayon`
+//! crate](https://crates.io/crates/rayon).
+//!
+//! The `palette_io` feature enables `read_gpl` and `write_gpl` functions
+//! which read and write GIMP palette (`.gpl`) files.
+//!
 //! # Other crates
 //!
 //! The design — and to some degree code — of this crate has been based on the
@@ -101,11 +131,88 @@
 //! For conversion between sRGB and XYZ colour spaces this crate relies on the
 //! [`srgb` crate](https://crates.io/crates/srgb).
 
-#[cfg(any(test, feature = "approx"))]
+mod alpha;
 mod approx_impl;
+mod binary;
+mod blend;
+#[cfg(any(test, feature = "bytemuck"))]
+mod bytemuck_impl;
+mod converter;
+mod css;
+mod cvd;
+mod fast;
+#[cfg(feature = "fixed")]
+mod fixed;
+mod gradient;
+mod image;
+mod intent;
+mod metric;
+mod named;
+mod palette;
+#[cfg(feature = "palette_io")]
+mod palette_io;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod precision;
+mod primaries;
+#[cfg(feature = "rand")]
+mod random;
+mod srgb_luv;
+
+pub use alpha::{luvas_to_rgba_bytes, rgba_bytes_to_luvas, LuvA};
+pub use binary::{read_palette, write_palette};
+pub use blend::BlendMode;
+pub use converter::{Converter, Observer};
+pub use css::ParseError;
+pub use cvd::{daltonize, CvdType};
+#[cfg(feature = "fixed")]
+pub use fixed::LuvFixed;
+pub use gradient::fit_srgb_stops;
+pub use image::{
+    adjust_lightness, clipping_pixels, difference_map, equalize_lightness,
+    gaussian_blur, perceptual_energy, scale_chroma_in_place, white_balance,
+    DiffStats,
+};
+pub use intent::RenderingIntent;
+pub use metric::{nearest_with, LuvMetricSpace, Metric};
+pub use named::{nearest_named, NAMED_COLORS};
+pub use palette::{
+    bracket_by_lightness, chroma_extremes, dedup, distances_to, dominant_color,
+    gamut_coverage, gamut_volume, hue_histogram, lightness_extremes, nearest,
+    order_for_smoothness, retarget_palette, select_similar, within,
+};
+#[cfg(feature = "palette_io")]
+pub use palette_io::{read_gpl, write_gpl};
+#[cfg(feature = "rayon")]
+pub use parallel::par_quantize;
+pub use precision::{
+    grey_chroma_residuals, lch_roundtrip_error, precision_report,
+    region_roundtrip_error, ErrorStats, GamutRegion, PrecisionReport,
+};
+pub use primaries::Primaries;
+#[cfg(feature = "rand")]
+pub use random::{random_displayable, random_luv};
+pub use srgb_luv::{NotDisplayable, SrgbLuv};
 
 /// Struct representing a color in CIALuv, a.k.a. L\*u\*v\*, color space
+///
+/// The struct has C representation with the fields laid out in order with no
+/// padding, i.e. it has the same layout as `[f32; 3]`.  This makes it
+/// suitable for passing to C code or uploading to GPU buffers.  See
+/// [`Luv::as_array`] and [`Luv::from_array_ref`] for reinterpreting single
+/// objects.  Similarly, a `&[Luv]` slice can be safely viewed as `&[f32]`
+/// slice three times as long:
+///
+/// ```
+/// let luvs = luv::rgbs_to_luvs(&[[255, 0, 0], [0, 0, 255]]);
+/// let floats: &[f32] = unsafe {
+///     std::slice::from_raw_parts(luvs.as_ptr().cast::<f32>(), luvs.len() * 3)
+/// };
+/// assert_eq!(6, floats.len());
+/// assert_eq!([luvs[1].l, luvs[1].u, luvs[1].v], floats[3..]);
+/// ```
 #[derive(Debug, Copy, Clone, Default)]
+#[repr(C)]
 pub struct Luv {
     /// The L\* value (achromatic luminance) of the colour in 0–100 range.
     pub l: f32,
@@ -128,7 +235,11 @@ pub struct Luv {
 }
 
 /// Struct representing a color in cylindrical CIELCh(uv) color space
+///
+/// Like [`Luv`], the struct has C representation with the same layout as
+/// `[f32; 3]`.
 #[derive(Debug, Copy, Clone, Default)]
+#[repr(C)]
 pub struct LCh {
     /// The L\* value (achromatic luminance) of the colour in 0–100 range.
     ///
@@ -139,7 +250,8 @@ pub struct LCh {
     /// Together with h_uv, it defines chromaticity of the colour.  The typical
     /// values of the coordinate go from zero up to around 150 (but exact range
     /// for ‘valid’ colours depends on luminance and hue).  Zero represents
-    /// shade of grey.
+    /// shade of grey.  Negative values are accepted and are equivalent to
+    /// a positive chroma with hue rotated by π; see [`LCh::normalized`].
     pub c: f32,
     /// The h_uv value (hue) of the colour measured in radians.
     ///
@@ -152,18 +264,43 @@ pub struct LCh {
 
 // κ and ε parameters used in conversion between XYZ and L*u*v*.  See
 // http://www.brucelindbloom.com/LContinuity.html for explanation as to why
-// those are different values than those provided by CIE standard.
+// those are different values than those provided by CIE standard.  See also
+// documentation of `l_star_from_luminance`.
 const KAPPA: f32 = 24389.0 / 27.0;
 const ONE_OVER_KAPPA: f32 = 27.0 / 24389.0;
 const EPSILON: f32 = 216.0 / 24389.0;
 const KAPPA_EPSILON: f32 = /* κ * ε = 216 / 27 = 8 */ 8.0;
 
 use srgb::xyz::D65_XYZ;
-const WHITE_U_PRIME: f32 =
+
+/// The u′ chromaticity coordinate of the D65 reference white point.
+///
+/// The value is derived from [`srgb::xyz::D65_XYZ`] using the CIE 1976 UCS
+/// formula u′ = 4X / (X + 15Y + 3Z).  It’s the u′ₙ value used when
+/// converting between XYZ and L\*u\*v\* colour spaces.
+pub const D65_U_PRIME: f32 =
     4.0 * D65_XYZ[0] / (D65_XYZ[0] + 15.0 * D65_XYZ[1] + 3.0 * D65_XYZ[2]);
-const WHITE_V_PRIME: f32 =
+
+/// The v′ chromaticity coordinate of the D65 reference white point.
+///
+/// The value is derived from [`srgb::xyz::D65_XYZ`] using the CIE 1976 UCS
+/// formula v′ = 9Y / (X + 15Y + 3Z).  It’s the v′ₙ value used when
+/// converting between XYZ and L\*u\*v\* colour spaces.
+pub const D65_V_PRIME: f32 =
     9.0 * D65_XYZ[1] / (D65_XYZ[0] + 15.0 * D65_XYZ[1] + 3.0 * D65_XYZ[2]);
 
+/// The D65 reference white point in L\*u\*v\* colour space.
+///
+/// By definition of the colour space, the reference white has L\* equal 100
+/// and u\* and v\* equal zero.  Because of floating point imprecision,
+/// converting [`srgb::xyz::D65_XYZ`] with [`Luv::from_xyz`] may give
+/// a slightly different result.
+pub const D65_LUV: Luv = Luv {
+    l: 100.0,
+    u: 0.0,
+    v: 0.0,
+};
+
 
 #[cfg(any(target_feature = "fma", test))]
 fn mul_add(multiplier: f32, multiplicand: f32, addend: f32) -> f32 {
@@ -175,47 +312,126 @@ fn mul_add(multiplier: f32, multiplicand: f32, addend: f32) -> f32 {
     multiplier * multiplicand + addend
 }
 
+/// Multiplies a 3×3 matrix by a vector.
+pub(crate) fn matrix_product(
+    matrix: &[[f32; 3]; 3],
+    vector: &[f32; 3],
+) -> [f32; 3] {
+    let row = |row: &[f32; 3]| {
+        mul_add(
+            row[0],
+            vector[0],
+            mul_add(row[1], vector[1], row[2] * vector[2]),
+        )
+    };
+    [row(&matrix[0]), row(&matrix[1]), row(&matrix[2])]
+}
 
-fn luv_from_xyz(xyz: [f32; 3]) -> Luv {
-    let [x, y, z] = xyz;
 
-    let l = if y <= 0.0 {
-        return Luv::default();
-    } else if y <= EPSILON {
-        KAPPA * y
-    } else {
-        mul_add(y.powf(1.0 / 3.0), 116.0, -16.0)
-    };
+fn luv_from_xyz(xyz: [f32; 3]) -> Luv { Converter::d65().luv_from_xyz(&xyz) }
 
-    let d = mul_add(y, 15.0, mul_add(z, 3.0, x));
-    let ll = 13.0 * l;
-    let u = ll * mul_add(x / d, 4.0, -WHITE_U_PRIME);
-    let v = ll * mul_add(y / d, 9.0, -WHITE_V_PRIME);
+fn xyz_from_luv(luv: &Luv) -> [f32; 3] { Converter::d65().xyz_from_luv(luv) }
 
-    Luv { l, u, v }
-}
 
-fn xyz_from_luv(luv: &Luv) -> [f32; 3] {
-    if luv.l <= 0.0 {
-        return [0.0, 0.0, 0.0];
+/// Converts relative luminance (i.e. the Y coordinate in XYZ colour space
+/// relative to the reference white) to L\* lightness.
+///
+/// This is the CIE L\*(Y) transfer function:
+///
+/// - L\* = 116·∛Y − 16 if Y > ε, and
+/// - L\* = κ·Y otherwise,
+///
+/// where ε = 216/24389 ≈ 0.008856 and κ = 24389/27 ≈ 903.3.  The CIE
+/// standard specifies rounded values (ε = 0.008856 and κ = 903.3) which
+/// make the two pieces of the function meet with a small discontinuity.
+/// The exact rationals used here are the ones the rounded values were
+/// derived from and with them the function is continuous at the boundary
+/// where L\* = κ·ε = 8.  See <http://www.brucelindbloom.com/LContinuity.html>
+/// for details.
+///
+/// White (Y = 1) maps to L\* = 100 and black (Y = 0) to L\* = 0.  The input
+/// isn’t clamped so values outside of the 0–1 range produce lightness
+/// outside of the 0–100 range.  The inverse is [`luminance_from_l_star`].
+///
+/// # Example
+/// ```
+/// assert_eq!(100.0, luv::l_star_from_luminance(1.0));
+/// assert_eq!(0.0, luv::l_star_from_luminance(0.0));
+/// let l = luv::l_star_from_luminance(0.18);
+/// assert!((l - 49.496).abs() < 0.001);
+/// ```
+pub fn l_star_from_luminance(y: f32) -> f32 {
+    if y > EPSILON {
+        mul_add(y.powf(1.0 / 3.0), 116.0, -16.0)
+    } else {
+        KAPPA * y
     }
-    let ll = 13.0 * luv.l;
-    let u_prime = luv.u / ll + WHITE_U_PRIME;
-    let v_prime = luv.v / ll + WHITE_V_PRIME;
+}
 
-    let y = if luv.l > KAPPA_EPSILON {
-        ((luv.l + 16.0) / 116.0).powi(3)
+/// Converts L\* lightness to relative luminance (i.e. the Y coordinate in
+/// XYZ colour space relative to the reference white).
+///
+/// This is the inverse of [`l_star_from_luminance`]:
+///
+/// - Y = ((L\* + 16) / 116)³ if L\* > κ·ε = 8, and
+/// - Y = L\* / κ otherwise.
+///
+/// Like its inverse, the function is continuous and doesn’t clamp its
+/// argument.
+///
+/// # Example
+/// ```
+/// assert_eq!(1.0, luv::luminance_from_l_star(100.0));
+/// assert_eq!(0.0, luv::luminance_from_l_star(0.0));
+/// let y = luv::luminance_from_l_star(50.0);
+/// assert!((y - 0.18419).abs() < 0.00001);
+/// ```
+pub fn luminance_from_l_star(l: f32) -> f32 {
+    if l > KAPPA_EPSILON {
+        ((l + 16.0) / 116.0).powi(3)
     } else {
-        luv.l * ONE_OVER_KAPPA
-    };
+        l * ONE_OVER_KAPPA
+    }
+}
 
-    let a = 0.75 * y * u_prime / v_prime;
-    let x = 3.0 * a;
-    let z = y * (3.0 - 5.0 * v_prime) / v_prime - a;
 
-    [x, y, z]
+/// Converts an sRGB colour to L\*u\*v\*, applies `f` to it and converts the
+/// result back to sRGB.
+///
+/// This is a shorthand for `f(Luv::from_rgb(rgb)).to_rgb()` which is
+/// a common pattern when adjusting sRGB colours perceptually.  As with
+/// [`Luv::to_rgb`], if `f` returns a colour outside of the sRGB gamut, the
+/// components of the result are clamped.
+///
+/// # Example
+/// ```
+/// let lighter = luv::map_rgb(&[40, 90, 200], |luv| luv::Luv {
+///     l: luv.l + 10.0,
+///     ..luv
+/// });
+/// assert_eq!([79, 116, 222], lighter);
+/// ```
+pub fn map_rgb(rgb: &[u8; 3], f: impl FnOnce(Luv) -> Luv) -> [u8; 3] {
+    f(Luv::from_rgb(rgb)).to_rgb()
 }
 
+/// Returns WCAG contrast ratio between two sRGB colours.
+///
+/// This is a shorthand for
+/// `Luv::from_rgb(a).contrast_ratio(&Luv::from_rgb(b))` and gives the same
+/// result; see [`Luv::contrast_ratio`] for details.  The order of arguments
+/// doesn’t matter.
+///
+/// # Example
+/// ```
+/// let ratio = luv::contrast_ratio_rgb(&[0, 0, 0], &[255, 255, 255]);
+/// assert!((ratio - 21.0).abs() < 0.0001);
+/// let ratio = luv::contrast_ratio_rgb(&[0x76, 0x76, 0x76], &[255, 255, 255]);
+/// assert!(ratio >= 4.5);
+/// ```
+pub fn contrast_ratio_rgb(a: &[u8; 3], b: &[u8; 3]) -> f32 {
+    Luv::from_rgb(a).contrast_ratio(&Luv::from_rgb(b))
+}
 
 /// Convenience function to map a slice of RGB values to Luv values in serial
 ///
@@ -255,6 +471,147 @@ pub fn rgb_bytes_to_luvs(bytes: &[u8]) -> Vec<Luv> {
         .collect()
 }
 
+/// Grayscale to Luv conversion that operates on a flat `&[u8]` with one byte
+/// per pixel.
+///
+/// Each byte `b` is treated as the `[b, b, b]` sRGB grey so the result is
+/// the same as replicating each byte three times and passing the result to
+/// [`rgb_bytes_to_luvs`].  In particular, u\* and v\* of the colours are not
+/// exactly zero but have tiny residuals described by
+/// [`grey_chroma_residuals`].  For inputs longer than 256 bytes, each grey
+/// level is converted only once.
+///
+/// # Example
+/// ```
+/// let luvs = luv::grey_bytes_to_luvs(&[0, 255]);
+/// assert_eq!(vec![
+///     luv::Luv::from_rgb(&[0, 0, 0]),
+///     luv::Luv::from_rgb(&[255, 255, 255]),
+/// ], luvs);
+/// ```
+pub fn grey_bytes_to_luvs(bytes: &[u8]) -> Vec<Luv> {
+    let convert = |b: u8| Luv::from_rgb(&[b, b, b]);
+    if bytes.len() <= 256 {
+        return bytes.iter().copied().map(convert).collect();
+    }
+    let table: Vec<Luv> = (0..=255).map(convert).collect();
+    bytes.iter().map(|&b| table[b as usize]).collect()
+}
+
+/// Byte order of multi-byte samples in a byte stream.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// Most significant byte first.  This is the order used by PNG files.
+    Big,
+    /// Least significant byte first.  This is the native order on x86 and
+    /// most ARM platforms.
+    Little,
+}
+
+/// 16-bit RGB to Luv conversion that operates on a flat `&[u8]` of
+/// consecutive RGB triples.
+///
+/// Each pixel occupies six bytes: red, green and blue samples in that order,
+/// each two bytes long and stored in the specified byte order.  Trailing
+/// bytes which don’t form a full pixel are ignored.  See [`Luv::from_rgb16`]
+/// for details of the conversion.
+///
+/// # Example
+/// ```
+/// use luv::Endianness;
+///
+/// let be = &[0xF0, 0xF1, 0x21, 0x22, 0x5F, 0x60];
+/// let le = &[0xF1, 0xF0, 0x22, 0x21, 0x60, 0x5F];
+/// let luvs = luv::rgb16_bytes_to_luvs(be, Endianness::Big);
+/// assert_eq!(luvs, luv::rgb16_bytes_to_luvs(le, Endianness::Little));
+/// assert_eq!([240, 33, 95], luvs[0].to_rgb());
+/// ```
+pub fn rgb16_bytes_to_luvs(bytes: &[u8], endian: Endianness) -> Vec<Luv> {
+    let read: fn([u8; 2]) -> u16 = match endian {
+        Endianness::Big => u16::from_be_bytes,
+        Endianness::Little => u16::from_le_bytes,
+    };
+    bytes
+        .chunks_exact(6)
+        .map(|px| {
+            Luv::from_rgb16(&[
+                read([px[0], px[1]]),
+                read([px[2], px[3]]),
+                read([px[4], px[5]]),
+            ])
+        })
+        .collect()
+}
+
+/// RGB to LCh conversion that operates on a flat `&[u8]` of consecutive RGB
+/// triples.
+///
+/// Trailing bytes which don’t form a full triple are ignored.  See
+/// [`lchs_from_rgb_bytes`] for a variant which doesn’t allocate a vector.
+///
+/// # Example
+/// ```
+/// let rgbs = &[255u8, 0, 0, 255, 0, 255, 0, 255, 255];
+/// let lchs = luv::rgb_bytes_to_lchs(rgbs);
+/// assert_eq!(vec![
+///     luv::LCh::from_rgb(&[255, 0, 0]),
+///     luv::LCh::from_rgb(&[255, 0, 255]),
+///     luv::LCh::from_rgb(&[0, 255, 255]),
+/// ], lchs);
+/// ```
+pub fn rgb_bytes_to_lchs(bytes: &[u8]) -> Vec<LCh> {
+    use std::convert::TryInto;
+    let mut lchs = vec![LCh::default(); bytes.len() / 3];
+    for (rgb, lch) in bytes.chunks_exact(3).zip(lchs.iter_mut()) {
+        Luv::from_rgb(rgb.try_into().unwrap()).to_lch_into(lch);
+    }
+    lchs
+}
+
+/// Returns an iterator converting a flat `&[u8]` of consecutive RGB triples
+/// into LCh values.
+///
+/// Colours are converted lazily as the iterator is advanced so no
+/// intermediate vector is allocated.  Trailing bytes which don’t form a full
+/// triple are ignored.
+///
+/// # Example
+/// ```
+/// let rgbs = &[255u8, 0, 0, 128, 128, 128, 0, 255, 255, 42];
+/// let saturated = luv::lchs_from_rgb_bytes(rgbs)
+///     .filter(|lch| lch.c > 50.0)
+///     .count();
+/// assert_eq!(2, saturated);
+/// ```
+pub fn lchs_from_rgb_bytes(bytes: &[u8]) -> impl Iterator<Item = LCh> + '_ {
+    use std::convert::TryInto;
+    bytes
+        .chunks_exact(3)
+        .map(|rgb| LCh::from_rgb(rgb.try_into().unwrap()))
+}
+
+/// Returns an iterator converting a flat `&[u8]` of consecutive RGBA
+/// quadruples into Luv values ignoring the alpha channel.
+///
+/// Colours are converted lazily with [`Luv::from_rgba`] as the iterator is
+/// advanced so no intermediate vector is allocated.  Trailing bytes which
+/// don’t form a full quadruple are ignored.  To keep the alpha channel use
+/// [`rgba_bytes_to_luvas`].
+///
+/// # Example
+/// ```
+/// let rgba = &[255u8, 0, 0, 255, 128, 128, 128, 0, 0, 255, 255, 128, 42];
+/// let luvs = luv::luvs_from_rgba_bytes(rgba).collect::<Vec<_>>();
+/// let rgbs = &[[255, 0, 0], [128, 128, 128], [0, 255, 255]];
+/// assert_eq!(luv::rgbs_to_luvs(rgbs), luvs);
+/// ```
+pub fn luvs_from_rgba_bytes(bytes: &[u8]) -> impl Iterator<Item = Luv> + '_ {
+    use std::convert::TryInto;
+    bytes
+        .chunks_exact(4)
+        .map(|rgba| Luv::from_rgba(rgba.try_into().unwrap()))
+}
+
 /// Convenience function to map a slice of Luv values to RGB values in serial
 ///
 /// # Example
@@ -274,6 +631,18 @@ pub fn luvs_to_rgbs(luvs: &[Luv]) -> Vec<[u8; 3]> {
 
 /// Luv to RGB conversion that returns RGB triples flattened into a `Vec<u8>`
 ///
+/// Like [`Luv::to_rgb`], the function performs gamma compression with
+/// [`srgb::gamma::compress_u8`] which, rather than evaluating the sRGB
+/// transfer function, interpolates a precomputed table.  The approximation
+/// has over 14 bits of precision so the result is the same as with the exact
+/// formula for all but a small fraction of values which end up off by one.
+/// This is the case around the rounding points of the exact formula.
+///
+/// The size of the output buffer, `3 · luvs.len()` bytes, can’t overflow
+/// `usize` even on 32-bit and wasm targets: each `Luv` occupies 12 bytes so
+/// the input slice is already four times larger than the output.  The same
+/// applies to other functions which flatten colours into bytes.
+///
 /// # Example
 /// ```
 /// let luvs = &[
@@ -295,6 +664,116 @@ pub fn luvs_to_rgb_bytes(luvs: &[Luv]) -> Vec<u8> {
     )
 }
 
+/// Luv to RGBA conversion that returns RGBA quadruples with constant alpha
+/// flattened into a `Vec<u8>`
+///
+/// This is a convenience over [`luvs_to_rgb_bytes`] for APIs which expect
+/// RGBA data: the RGB components are the same as returned by that function
+/// and each triple is followed by `alpha`.  For colours with individual
+/// alpha values, see [`luvas_to_rgba_bytes`].
+///
+/// # Example
+/// ```
+/// let luvs = &[
+///     luv::Luv { l: 53.238235, u: 175.01141, v: 37.75865 },
+///     luv::Luv { l: 91.11428, u: -70.46933, v: -15.203715 }
+/// ];
+/// let rgba_bytes = luv::luvs_to_rgba_bytes(luvs, 255);
+/// assert_eq!(vec![255u8, 0, 0, 255, 0, 255, 255, 255], rgba_bytes);
+/// ```
+pub fn luvs_to_rgba_bytes(luvs: &[Luv], alpha: u8) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(luvs.len() * 4);
+    for luv in luvs {
+        let [r, g, b] = luv.to_rgb();
+        bytes.extend_from_slice(&[r, g, b, alpha]);
+    }
+    bytes
+}
+
+/// 4×4 Bayer threshold matrix used by [`luvs_to_rgb_bytes_ordered`].
+#[rustfmt::skip]
+const BAYER_4X4: [[u8; 4]; 4] = [
+    [ 0,  8,  2, 10],
+    [12,  4, 14,  6],
+    [ 3, 11,  1,  9],
+    [15,  7, 13,  5],
+];
+
+/// Quantises linear sRGB component into an 8-bit value choosing between the
+/// two nearest codes based on given threshold in the (0, 1) range.
+fn dither_channel(linear: f32, threshold: f32) -> u8 {
+    use srgb::gamma::expand_u8;
+    let linear = linear.clamp(0.0, 1.0);
+    let mut lo = srgb::gamma::compress_u8(linear);
+    if lo > 0 && expand_u8(lo) > linear {
+        lo -= 1;
+    }
+    if lo == 255 {
+        return lo;
+    }
+    let (lo_lin, hi_lin) = (expand_u8(lo), expand_u8(lo + 1));
+    if (linear - lo_lin) > threshold * (hi_lin - lo_lin) {
+        lo + 1
+    } else {
+        lo
+    }
+}
+
+/// Luv to RGB conversion with ordered dithering which returns RGB triples
+/// flattened into a `Vec<u8>`
+///
+/// `luvs` is interpreted as an image `width` pixels wide stored row by row.
+/// Before quantisation to 8-bit values, each linear sRGB component is
+/// perturbed based on pixel position using a 4×4 Bayer matrix.  The result
+/// is that smooth gradients which would otherwise collapse into flat bands
+/// are approximated by a pattern of the two nearest 8-bit values.  Since the
+/// threshold depends on position only, the output is deterministic and every
+/// pixel is processed independently.
+///
+/// # Panics
+///
+/// Panics if `width` is zero.
+///
+/// # Example
+/// ```
+/// let luvs = &[
+///     luv::Luv { l: 53.238235, u: 175.01141, v: 37.75865 },
+///     luv::Luv { l: 60.322693, u: 84.063835, v: -108.69038 },
+///     luv::Luv { l: 91.11428, u: -70.46933, v: -15.203715 }
+/// ];
+/// let rgb_bytes = luv::luvs_to_rgb_bytes_ordered(luvs, 3);
+/// assert_eq!(vec![255u8, 0, 0, 255, 0, 255, 0, 255, 255], rgb_bytes);
+/// ```
+pub fn luvs_to_rgb_bytes_ordered(luvs: &[Luv], width: usize) -> Vec<u8> {
+    assert_ne!(0, width, "width must be non-zero");
+    let mut bytes = Vec::with_capacity(luvs.len() * 3);
+    for (idx, luv) in luvs.iter().enumerate() {
+        let (x, y) = (idx % width, idx / width);
+        let threshold = (BAYER_4X4[y % 4][x % 4] as f32 + 0.5) / 16.0;
+        let linear = srgb::xyz::linear_from_xyz(xyz_from_luv(luv));
+        bytes.extend(linear.iter().map(|&c| dither_channel(c, threshold)));
+    }
+    bytes
+}
+
+
+/// Rounding mode used when quantising colour components to 8-bit values.
+///
+/// See [`Luv::to_rgb_with_rounding`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Rounds to the nearest integer.  This is what [`Luv::to_rgb`] does.
+    #[default]
+    Nearest,
+    /// Rounds towards negative infinity.
+    Floor,
+    /// Rounds towards positive infinity.
+    Ceil,
+    /// Rounds towards zero.  Since components are clamped to non-negative
+    /// values before rounding, this is the same as [`Rounding::Floor`].
+    TruncateTowardZero,
+}
+
 
 fn subarray<T>(arr: &[T; 4]) -> &[T; 3] {
     std::convert::TryInto::try_into(&arr[..3]).unwrap()
@@ -302,6 +781,28 @@ fn subarray<T>(arr: &[T; 4]) -> &[T; 3] {
 
 
 impl Luv {
+    /// Black, i.e. colour with all coordinates equal zero.
+    pub const BLACK: Luv = Luv {
+        l: 0.0,
+        u: 0.0,
+        v: 0.0,
+    };
+
+    /// White, i.e. the D65 reference white.  This is the same as [`D65_LUV`].
+    pub const WHITE: Luv = D65_LUV;
+
+    /// Perceptual middle grey, i.e. achromatic colour with L\* equal 50.
+    ///
+    /// Note that this is not the same as the 18% reflectance grey often used
+    /// in photography.  The latter has L\* of approximately 49.5 (relative
+    /// luminance 0.18) and is slightly darker.  In 8-bit sRGB the perceptual
+    /// middle grey is `[119, 119, 119]`.
+    pub const MIDDLE_GREY: Luv = Luv {
+        l: 50.0,
+        u: 0.0,
+        v: 0.0,
+    };
+
     /// Constructs a new `Luv` from a three-element array of `u8`s
     ///
     /// # Examples
@@ -333,359 +834,3180 @@ impl Luv {
     /// ```
     pub fn from_rgba(rgba: &[u8; 4]) -> Self { Luv::from_rgb(subarray(rgba)) }
 
-    #[doc(hidden)]
-    pub fn from_rgba_normalized(rgba: &[f32; 4]) -> Self {
-        Luv::from_rgb_normalized(subarray(rgba))
+    /// Constructs a new `Luv` from a three-element array of `u16`s
+    ///
+    /// The components are gamma-encoded sRGB values with 16 bits per channel,
+    /// i.e. 65535 corresponds to full intensity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv::from_rgb16(&[0xF0F0, 0x2121, 0x5F5F]);
+    /// assert_eq!([240, 33, 95], luv.to_rgb());
+    /// ```
+    pub fn from_rgb16(rgb: &[u16; 3]) -> Self {
+        let scale = |c: u16| c as f32 / 65535.0;
+        Luv::from_rgb_normalized(&[scale(rgb[0]), scale(rgb[1]), scale(rgb[2])])
     }
 
-    /// Returns the `Luv`'s color in RGB, in a 3-element array.
+    /// Constructs a new `Luv` from coordinates in XYZ colour space
+    ///
+    /// The XYZ space must be one where the D65 reference white has Y coordinate
+    /// equal one (see [`srgb::xyz::D65_XYZ`]).  For coordinates where Y is
+    /// given in different scale, e.g. in 0–100 range, use
+    /// [`Luv::from_xyz_scaled`].
     ///
     /// # Examples
     ///
     /// ```
-    /// let luv = luv::Luv { l: 52.334686, u: 138.98636, v: 7.8476787 };
-    /// assert_eq!([240, 33, 95], luv.to_rgb());
+    /// let xyz = [0.4124108, 0.21264932, 0.019331753];
+    /// let luv = luv::Luv::from_xyz(&xyz);
+    /// assert_eq!(luv::Luv { l: 53.238235, u: 175.0114, v: 37.75865 }, luv);
     /// ```
-    pub fn to_rgb(&self) -> [u8; 3] { srgb::u8_from_xyz(xyz_from_luv(self)) }
+    pub fn from_xyz(xyz: &[f32; 3]) -> Self { luv_from_xyz(*xyz) }
 
-    #[doc(hidden)]
-    pub fn to_rgb_normalized(&self) -> [f32; 3] {
-        srgb::normalised_from_xyz(xyz_from_luv(self))
+    /// Constructs a new `Luv` from coordinates in scaled XYZ colour space.
+    ///
+    /// Colorimetric instruments and references often report XYZ coordinates
+    /// with Y in the 0–100 range rather than 0–1.  This function divides all
+    /// coordinates by `y_max` (i.e. the Y coordinate of the reference white)
+    /// and passes the result to [`Luv::from_xyz`].  In other words,
+    /// `Luv::from_xyz_scaled(xyz, 1.0)` is equivalent to `Luv::from_xyz(xyz)`.
+    ///
+    /// `y_max` must be positive.  The function doesn’t check it and with zero
+    /// or negative scale the result is meaningless (coordinates may be
+    /// infinite or NaN).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let xyz = [41.24108, 21.264932, 1.9331753];
+    /// let luv = luv::Luv::from_xyz_scaled(&xyz, 100.0);
+    /// assert_eq!(luv::Luv { l: 53.238235, u: 175.01141, v: 37.75865 }, luv);
+    /// ```
+    pub fn from_xyz_scaled(xyz: &[f32; 3], y_max: f32) -> Self {
+        luv_from_xyz([xyz[0] / y_max, xyz[1] / y_max, xyz[2] / y_max])
     }
 
-    /// Measures the perceptual distance between the colors of one `Luv`
-    /// and an `other`.
+    /// Constructs a new `Luv` from lightness and CIE 1976 UCS chromaticity
+    /// coordinates.
+    ///
+    /// This is an inverse of [`Luv::uv_prime`].  The u\* and v\* components
+    /// are computed as u\* = 13·L\*·(u′ − u′ₙ) and v\* = 13·L\*·(v′ − v′ₙ)
+    /// where u′ₙ and v′ₙ are [`D65_U_PRIME`] and [`D65_V_PRIME`]
+    /// respectively.  In particular, when `l` is zero the chromaticity is
+    /// ignored and the result is black.
     ///
     /// # Examples
     ///
     /// ```
-    /// let pink = luv::Luv { l: 52.334686, u: 138.98636, v: 7.8476787 };
-    /// let websafe_pink = luv::Luv { l: 56.675262, u: 142.3089, v: 10.548637 };
-    /// assert_eq!(37.175053, pink.squared_distance(&websafe_pink));
+    /// let (u_prime, v_prime) = (luv::D65_U_PRIME, luv::D65_V_PRIME);
+    /// let grey = luv::Luv::from_uv_prime(50.0, u_prime, v_prime);
+    /// assert_eq!(luv::Luv { l: 50.0, u: 0.0, v: 0.0 }, grey);
     /// ```
-    pub fn squared_distance(&self, other: &Luv) -> f32 {
-        (self.l - other.l).powi(2) +
-            (self.u - other.u).powi(2) +
-            (self.v - other.v).powi(2)
+    pub fn from_uv_prime(l: f32, u_prime: f32, v_prime: f32) -> Self {
+        let ll = 13.0 * l;
+        let u = ll * (u_prime - D65_U_PRIME);
+        let v = ll * (v_prime - D65_V_PRIME);
+        Luv { l, u, v }
     }
-}
-
 
-impl LCh {
-    /// Constructs a new `LCh` from a three-element array of `u8`s
+    /// Constructs a new `Luv` from cylindrical coordinates with hue given in
+    /// degrees.
+    ///
+    /// This is a shorthand for constructing an [`LCh`] with the hue converted
+    /// to radians and calling [`LCh::to_luv`] on it.  Like CSS `lch()`
+    /// function, it takes hue in degrees which is often more natural when
+    /// writing colours by hand.  Note however that CSS’s `lch()` is based on
+    /// CIELAB rather than CIELUV so the same numbers describe different
+    /// colours.
     ///
     /// # Examples
     ///
     /// ```
-    /// let rgb = [240, 33, 95];
-    /// let lch = luv::LCh::from_rgb(&rgb);
-    /// assert_eq!(luv::LCh {l: 52.334686, c: 139.20776, h: 0.056403756}, lch);
-    /// assert_eq!(lch, luv::LCh::from_luv(luv::Luv::from_rgb(&rgb)));
+    /// let luv = luv::Luv::from_lch_degrees(52.33686, 78.15369, 90.0);
+    /// let lch = luv::LCh { l: 52.33686, c: 78.15369, h: 90f32.to_radians() };
+    /// assert_eq!(lch.to_luv(), luv);
+    /// assert!(luv.u.abs() < 0.0001);
+    /// assert!((luv.v - 78.15369).abs() < 0.0001);
     /// ```
-    pub fn from_rgb(rgb: &[u8; 3]) -> Self {
-        LCh::from_luv(Luv::from_rgb(rgb))
+    pub fn from_lch_degrees(l: f32, c: f32, h_deg: f32) -> Self {
+        let h = h_deg.to_radians();
+        LCh { l, c, h }.to_luv()
     }
 
-    /// Constructs a new `LCh` from a four-element array of `u8`s
+    /// Returns reference to the colour’s coordinates as an array.
     ///
-    /// The `LCh` struct does not store alpha channel information, so the last
-    /// `u8` representing alpha is discarded. This convenience method exists
-    /// in order to easily measure colors already stored in an RGBA array.
+    /// The array holds L\*, u\* and v\* coordinates in that order.  No
+    /// copying takes place; the array is a view of the object.
     ///
     /// # Examples
     ///
     /// ```
-    /// let rgba = [240, 33, 95, 255];
-    /// let lch = luv::LCh::from_rgba(&rgba);
-    /// assert_eq!(luv::LCh {l: 52.334686, c: 139.20776, h: 0.056403756}, lch);
-    /// assert_eq!(lch, luv::LCh::from_luv(luv::Luv::from_rgba(&rgba)));
+    /// let luv = luv::Luv { l: 50.0, u: 10.0, v: -20.0 };
+    /// assert_eq!(&[50.0, 10.0, -20.0], luv.as_array());
     /// ```
-    pub fn from_rgba(rgba: &[u8; 4]) -> Self {
-        LCh::from_luv(Luv::from_rgba(rgba))
+    pub fn as_array(&self) -> &[f32; 3] {
+        // SAFETY: Luv is repr(C) with three f32 fields so it has the same
+        // size, alignment and layout as [f32; 3].
+        unsafe { &*(self as *const Luv).cast::<[f32; 3]>() }
     }
 
-    /// Constructs a new `LCh` from a `Luv`
+    /// Reinterprets reference to an array of coordinates as a `Luv`.
+    ///
+    /// This is an inverse of [`Luv::as_array`].  The array must hold L\*,
+    /// u\* and v\* coordinates in that order.
     ///
     /// # Examples
     ///
     /// ```
-    /// let luv = luv::Luv { l: 52.33686, u: 75.5516, v: 19.998878 };
-    /// let lch = luv::LCh::from_luv(luv);
-    /// assert_eq!(luv::LCh { l: 52.33686, c: 78.15369, h: 0.25877 }, lch);
-    ///
-    /// let luv = luv::Luv { l: 52.33686, u: 0.0, v: 0.0 };
-    /// let lch = luv::LCh::from_luv(luv);
-    /// assert_eq!(luv::LCh { l: 52.33686, c: 0.0, h: 0.0 }, lch);
+    /// let arr = [50.0, 10.0, -20.0];
+    /// let luv = luv::Luv::from_array_ref(&arr);
+    /// assert_eq!(luv::Luv { l: 50.0, u: 10.0, v: -20.0 }, *luv);
     /// ```
-    pub fn from_luv(luv: Luv) -> Self {
-        LCh {
-            l: luv.l,
-            c: luv.u.hypot(luv.v),
-            h: luv.v.atan2(luv.u),
-        }
+    pub fn from_array_ref(arr: &[f32; 3]) -> &Luv {
+        // SAFETY: See as_array.
+        unsafe { &*(arr as *const [f32; 3]).cast::<Luv>() }
     }
 
-    /// Returns the `LCh`'s color in RGB, in a 3-element array
+    /// Packs the colour into a 32-bit integer.
+    ///
+    /// This is a lossy representation taking four bytes rather than twelve
+    /// which is useful for storing large palettes compactly.  Going from
+    /// the most significant bit, the layout is:
+    ///
+    /// - bits 31–22 (10 bits): L\* mapped linearly from the 0–100 range,
+    /// - bits 21–11 (11 bits): u\* mapped linearly from the -134–224 range,
+    /// - bits 10–0 (11 bits): v\* mapped linearly from the -140–122 range.
+    ///
+    /// The ranges cover all colours visible to a standard observer (and thus
+    /// the whole sRGB gamut).  Coordinates outside of them are clamped.
+    /// Each coordinate is rounded to the nearest representable value so the
+    /// error introduced is at most half of the quantisation step: about
+    /// 0.049 for L\*, 0.088 for u\* and 0.064 for v\*.  That’s well below
+    /// the just-noticeable difference.
+    ///
+    /// The inverse is [`Luv::from_packed_u32`].
     ///
     /// # Examples
     ///
     /// ```
-    /// let mut lch = luv::LCh { l: 52.334686, c: 139.20773, h: 0.05640377 };
-    /// assert_eq!([240, 33, 95], lch.to_rgb());
+    /// assert_eq!(0x0017_f446, luv::Luv::BLACK.to_packed_u32());
+    /// assert_eq!(0xffd7_f446, luv::Luv::WHITE.to_packed_u32());
     ///
-    /// lch.h += std::f32::consts::TAU;
-    /// assert_eq!([240, 33, 95], lch.to_rgb());
+    /// let luv = luv::Luv::from_rgb(&[40, 90, 200]);
+    /// let got = luv::Luv::from_packed_u32(luv.to_packed_u32());
+    /// assert!((got.l - luv.l).abs() < 0.05);
+    /// assert!((got.u - luv.u).abs() < 0.09);
+    /// assert!((got.v - luv.v).abs() < 0.07);
     /// ```
-    pub fn to_rgb(&self) -> [u8; 3] { self.to_luv().to_rgb() }
+    pub fn to_packed_u32(&self) -> u32 {
+        let [l, u, v] = PACKED_RANGES;
+        (pack_component(self.l, l) << 22) |
+            (pack_component(self.u, u) << 11) |
+            pack_component(self.v, v)
+    }
 
-    /// Returns the `LCh`'s color in `Luv`
+    /// Unpacks colour from a 32-bit integer.
     ///
-    /// Note that due to imprecision of floating point arithmetic, conversions
-    /// between Luv and LCh are not stable.  A chain of Luv→LCh→Luv or
-    /// LCh→Luv→LCh operations isn’t guaranteed to give back the source colour.
+    /// This is an inverse of [`Luv::to_packed_u32`] which see for
+    /// description of the bit layout and precision.  Every `u32` value
+    /// decodes to a colour within the documented ranges.
     ///
     /// # Examples
     ///
     /// ```
-    /// let lch = luv::LCh { l: 52.33686, c: 78.15369, h: 0.25877 };
-    /// let luv = lch.to_luv();
-    /// assert_eq!(luv::Luv { l: 52.33686, u: 75.5516, v: 19.998878 }, luv);
-    ///
-    /// let lch = luv::LCh { l: 52.33686, c: 0.0, h: 0.25877 };
-    /// let luv = lch.to_luv();
-    /// assert_eq!(luv::Luv { l: 52.33686, u: 0.0, v: 0.0 }, luv);
-    ///
-    /// let inp = luv::Luv { l: 29.52658, u: 58.595745, v: -36.281406 };
-    /// let lch = luv::LCh { l: 29.52658, c: 68.91881,  h: -0.5544043 };
-    /// let out = luv::Luv { l: 29.52658, u: 58.59575,  v: -36.281406 };
-    /// assert_eq!(lch, luv::LCh::from_luv(inp));
-    /// assert_eq!(out, lch.to_luv());
+    /// let luv = luv::Luv::from_packed_u32(0xffd7_f446);
+    /// assert_eq!(100.0, luv.l);
+    /// assert!(luv.u.abs() < 0.09 && luv.v.abs() < 0.07);
     /// ```
-    pub fn to_luv(&self) -> Luv {
+    pub fn from_packed_u32(packed: u32) -> Self {
+        let [l, u, v] = PACKED_RANGES;
+        Luv {
+            l: unpack_component(packed >> 22, l),
+            u: unpack_component(packed >> 11, u),
+            v: unpack_component(packed, v),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn from_rgba_normalized(rgba: &[f32; 4]) -> Self {
+        Luv::from_rgb_normalized(subarray(rgba))
+    }
+
+    /// Returns the `Luv`'s color in RGB, in a 3-element array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 52.334686, u: 138.98636, v: 7.8476787 };
+    /// assert_eq!([240, 33, 95], luv.to_rgb());
+    /// ```
+    pub fn to_rgb(&self) -> [u8; 3] { srgb::u8_from_xyz(xyz_from_luv(self)) }
+
+    /// Returns the `Luv`'s color in XYZ colour space.
+    ///
+    /// The XYZ space is one where the D65 reference white has Y coordinate
+    /// equal one.  See [`Luv::to_xyz_scaled`] for other scales.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 53.238235, u: 175.01141, v: 37.758636 };
+    /// assert_eq!([0.4124108, 0.21264932, 0.019331753], luv.to_xyz());
+    /// ```
+    pub fn to_xyz(&self) -> [f32; 3] { xyz_from_luv(self) }
+
+    /// Returns the `Luv`'s color in scaled XYZ colour space.
+    ///
+    /// This is an inverse of [`Luv::from_xyz_scaled`].  The coordinates
+    /// returned by [`Luv::to_xyz`] are multiplied by `y_max`, i.e. the Y
+    /// coordinate of the reference white in the resulting space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 53.238235, u: 175.01141, v: 37.758636 };
+    /// assert_eq!([41.24108, 21.26493, 1.9331753], luv.to_xyz_scaled(100.0));
+    /// ```
+    pub fn to_xyz_scaled(&self, y_max: f32) -> [f32; 3] {
+        let [x, y, z] = xyz_from_luv(self);
+        [x * y_max, y * y_max, z * y_max]
+    }
+
+    /// Returns the colour’s chromaticity coordinates in the CIE 1976 UCS
+    /// diagram as a `(u′, v′)` pair.
+    ///
+    /// L\*u\*v\* colour space is built on top of those coordinates: u\* and
+    /// v\* are differences between colour’s and reference white’s u′ and v′
+    /// scaled by 13·L\*.  This function inverts that scaling, i.e. computes
+    /// u′ = u\* / (13·L\*) + u′ₙ and v′ = v\* / (13·L\*) + v′ₙ where u′ₙ and
+    /// v′ₙ are [`D65_U_PRIME`] and [`D65_V_PRIME`] respectively.  The result
+    /// is independent of lightness, e.g. all shades of grey map to the white
+    /// point.  Since chromaticity of black is undefined, for non-positive
+    /// lightness the white point’s coordinates are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let grey = luv::Luv { l: 50.0, u: 0.0, v: 0.0 };
+    /// assert_eq!((luv::D65_U_PRIME, luv::D65_V_PRIME), grey.uv_prime());
+    ///
+    /// let red = luv::Luv::from_rgb(&[255, 0, 0]);
+    /// let (u_prime, v_prime) = red.uv_prime();
+    /// assert!((u_prime - 0.4507).abs() < 1e-4);
+    /// assert!((v_prime - 0.5229).abs() < 1e-4);
+    /// ```
+    pub fn uv_prime(&self) -> (f32, f32) {
+        if self.l <= 0.0 {
+            return (D65_U_PRIME, D65_V_PRIME);
+        }
+        let ll = 13.0 * self.l;
+        (self.u / ll + D65_U_PRIME, self.v / ll + D65_V_PRIME)
+    }
+
+    #[doc(hidden)]
+    pub fn to_rgb_normalized(&self) -> [f32; 3] {
+        srgb::normalised_from_xyz(xyz_from_luv(self))
+    }
+
+    /// Returns the `Luv`'s color in RGB using specified rounding mode.
+    ///
+    /// With [`Rounding::Nearest`] (which is the default rounding mode) the
+    /// result is exactly the same as the one returned by [`Luv::to_rgb`].
+    /// With other modes, gamma-compressed sRGB components are clamped to the
+    /// 0–1 range, scaled to 0–255 range and rounded as specified.  This allows
+    /// matching output of other tools which quantise colours differently.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luv::Rounding;
+    ///
+    /// let luv = luv::Luv { l: 50.0, u: 20.0, v: 10.0 };
+    /// assert_eq!([143, 112, 104], luv.to_rgb_with_rounding(Rounding::Nearest));
+    /// assert_eq!([142, 112, 103], luv.to_rgb_with_rounding(Rounding::Floor));
+    /// assert_eq!([143, 113, 104], luv.to_rgb_with_rounding(Rounding::Ceil));
+    /// ```
+    pub fn to_rgb_with_rounding(&self, mode: Rounding) -> [u8; 3] {
+        let round: fn(f32) -> f32 = match mode {
+            Rounding::Nearest => return self.to_rgb(),
+            Rounding::Floor => f32::floor,
+            Rounding::Ceil => f32::ceil,
+            Rounding::TruncateTowardZero => f32::trunc,
+        };
+        let rgb = self.to_rgb_normalized();
+        let quantise = |c: f32| round(c.clamp(0.0, 1.0) * 255.0) as u8;
+        [quantise(rgb[0]), quantise(rgb[1]), quantise(rgb[2])]
+    }
+
+    /// Returns the `Luv`'s color in RGB together with the amount each channel
+    /// had to be clamped by.
+    ///
+    /// The first element of the returned tuple is the same as result of
+    /// [`Luv::to_rgb`].  The second holds, for each channel, signed overshoot
+    /// of the gamma-compressed component (in normalised 0–1 units) before it
+    /// was clamped: a positive value means the component was above one, a
+    /// negative value means it was below zero and zero means the channel was
+    /// in gamut.  Quantisation error is not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv::from_rgb(&[240, 33, 95]);
+    /// assert_eq!(([240, 33, 95], [0.0, 0.0, 0.0]), luv.to_rgb_with_error());
+    ///
+    /// let luv = luv::Luv { l: 50.0, u: -9.6, v: -133.0 };
+    /// let (rgb, error) = luv.to_rgb_with_error();
+    /// assert_eq!(255, rgb[2]);
+    /// assert!(error[2] > 0.05);
+    /// ```
+    pub fn to_rgb_with_error(&self) -> ([u8; 3], [f32; 3]) {
+        let rgb = self.to_rgb_normalized();
+        let error = |c: f32| {
+            if c < 0.0 {
+                c
+            } else if c > 1.0 {
+                c - 1.0
+            } else {
+                0.0
+            }
+        };
+        (self.to_rgb(), [error(rgb[0]), error(rgb[1]), error(rgb[2])])
+    }
+
+    /// Measures the perceptual distance between the colors of one `Luv`
+    /// and an `other`.
+    ///
+    /// The result is the square of Euclidean distance in L\*u\*v\* space.
+    /// It’s cheaper to compute than [`Luv::distance`] and orders pairs of
+    /// colours the same way but note that it isn’t a metric: it violates the
+    /// triangle inequality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let pink = luv::Luv { l: 52.334686, u: 138.98636, v: 7.8476787 };
+    /// let websafe_pink = luv::Luv { l: 56.675262, u: 142.3089, v: 10.548637 };
+    /// assert_eq!(37.175053, pink.squared_distance(&websafe_pink));
+    /// ```
+    pub fn squared_distance(&self, other: &Luv) -> f32 {
+        (self.l - other.l).powi(2) +
+            (self.u - other.u).powi(2) +
+            (self.v - other.v).powi(2)
+    }
+
+    /// Returns Euclidean distance in L\*u\*v\* space between the colour and
+    /// an `other`, i.e. CIE 1976 ΔE\*uv colour difference.
+    ///
+    /// This is the square root of [`Luv::squared_distance`].  Unlike the
+    /// latter, it’s a proper metric (in particular, it satisfies the triangle
+    /// inequality) which makes it suitable for spatial data structures which
+    /// rely on that property; see also [`LuvMetricSpace`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let a = luv::Luv { l: 50.0, u: 3.0, v: 0.0 };
+    /// let b = luv::Luv { l: 50.0, u: 0.0, v: 4.0 };
+    /// assert_eq!(5.0, a.distance(&b));
+    /// ```
+    pub fn distance(&self, other: &Luv) -> f32 {
+        self.squared_distance(other).sqrt()
+    }
+
+    /// Returns whether the colour lies within the sRGB gamut.
+    ///
+    /// The colour is considered displayable if all of its gamma-encoded sRGB
+    /// components are within the 0–1 range.  To allow for floating point
+    /// imprecision and to treat colours converted from 8-bit sRGB as
+    /// displayable, components are allowed to overshoot the range by half of
+    /// the 8-bit quantisation step.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let red = luv::Luv::from_rgb(&[255, 0, 0]);
+    /// assert!(red.is_displayable());
+    ///
+    /// let too_red = luv::Luv { l: 53.24, u: 200.0, v: 37.76 };
+    /// assert!(!too_red.is_displayable());
+    /// ```
+    pub fn is_displayable(&self) -> bool {
+        const TOLERANCE: f32 = 0.5 / 255.0;
+        srgb::normalised_from_xyz(xyz_from_luv(self))
+            .iter()
+            .all(|&c| (-TOLERANCE..=1.0 + TOLERANCE).contains(&c))
+    }
+
+    /// Returns how far outside of the sRGB gamut the colour is.
+    ///
+    /// For displayable colours (see [`Luv::is_displayable`]) returns zero.
+    /// Otherwise, returns Euclidean distance in L\*u\*v\* space (i.e. ΔE\*uv)
+    /// to a displayable colour found by clamping lightness to the 0–100 range
+    /// and then reducing chroma to [`LCh::max_chroma`] while keeping the hue.
+    /// The nearest displayable colour may have different lightness or hue so
+    /// the result is an approximation (more precisely, an upper bound) of the
+    /// true distance to the gamut.  Nonetheless, it grows continuously as the
+    /// colour moves away from the gamut which makes it usable as a penalty
+    /// term in optimisation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(0.0, luv::Luv::from_rgb(&[255, 0, 0]).distance_to_gamut());
+    ///
+    /// let too_bright = luv::Luv { l: 110.0, u: 0.0, v: 0.0 };
+    /// assert_eq!(10.0, too_bright.distance_to_gamut());
+    /// ```
+    pub fn distance_to_gamut(&self) -> f32 {
+        if self.is_displayable() {
+            return 0.0;
+        }
+        let lch = LCh::from_luv(*self);
+        let l = self.l.clamp(0.0, 100.0);
+        let c = lch.c.min(LCh::max_chroma(l, lch.h));
+        let nearest = LCh { l, c, h: lch.h }.to_luv();
+        self.squared_distance(&nearest).sqrt()
+    }
+
+    /// Returns whether converting the colour to sRGB and back gives a colour
+    /// within `tolerance` distance from the original.
+    ///
+    /// This is a practical test of gamut membership based on the behaviour of
+    /// [`Luv::to_rgb`].  Colours outside of the sRGB gamut are clamped during
+    /// the conversion and the further they are from the gamut, the further
+    /// the colour moves.  Unlike [`Luv::is_displayable`], which checks the
+    /// exact sRGB coordinates, this method also accounts for quantisation to
+    /// 8-bit values.  `tolerance` is expressed as Euclidean distance in
+    /// L\*u\*v\* space (i.e. square root of [`Luv::squared_distance`]) and
+    /// should be large enough to absorb the quantisation error; values around
+    /// one are typically sufficient for displayable colours.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let pink = luv::Luv { l: 66.6377, u: 93.02939, v: 9.430343 };
+    /// assert!(pink.round_trips_to_self(1.0));
+    ///
+    /// let too_red = luv::Luv { l: 53.24, u: 200.0, v: 37.76 };
+    /// assert!(!too_red.round_trips_to_self(1.0));
+    /// ```
+    pub fn round_trips_to_self(&self, tolerance: f32) -> bool {
+        let back = Luv::from_rgb(&self.to_rgb());
+        self.squared_distance(&back) <= tolerance * tolerance
+    }
+
+    /// Returns colour with inverted lightness and the same chromaticity.
+    ///
+    /// Lightness is mapped from L\* to 100 − L\*, which turns light colours
+    /// into dark ones and vice versa, for example when deriving a dark theme
+    /// from a light one.  The u\* and v\* coordinates are scaled so that the
+    /// colour keeps its CIE 1976 u′v′ chromaticity.  Since u\* = 13·L\*·(u′ −
+    /// u′ₙ) (and analogously for v\*), this means scaling them by
+    /// (100 − L\*) / L\*.  Hue is therefore preserved while chroma changes
+    /// proportionally to lightness.  Use [`LCh::invert_lightness`] to keep
+    /// the chroma instead.
+    ///
+    /// Black (or any colour with non-positive lightness) maps to [`D65_LUV`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 80.0, u: 20.0, v: -8.0 };
+    /// let dark = luv.invert_lightness();
+    /// assert_eq!(luv::Luv { l: 20.0, u: 5.0, v: -2.0 }, dark);
+    /// ```
+    pub fn invert_lightness(&self) -> Luv {
+        if self.l <= 0.0 {
+            return D65_LUV;
+        }
+        let l = 100.0 - self.l;
+        let scale = l / self.l;
+        Luv {
+            l,
+            u: self.u * scale,
+            v: self.v * scale,
+        }
+    }
+
+    /// Returns saturation of the colour, s_uv, as defined by CIE.
+    ///
+    /// Saturation is colourfulness of the colour relative to its lightness.
+    /// Unlike chroma, it stays the same when a colour is made darker or
+    /// lighter without changing its chromaticity, which makes it comparable
+    /// across lightness levels.  It is defined as `s_uv = 13·√((u′ − u′ₙ)² +
+    /// (v′ − v′ₙ)²)` where (u′, v′) is chromaticity of the colour and (u′ₙ,
+    /// v′ₙ) of the reference white.  Since u\* = 13·L\*·(u′ − u′ₙ) (and
+    /// analogously for v\*), this equals `√(u² + v²) / L`, i.e. chroma divided
+    /// by lightness.  For colours with non-positive lightness returns zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 50.0, u: 30.0, v: -40.0 };
+    /// assert_eq!(1.0, luv.saturation());
+    /// assert_eq!(0.0, luv::Luv::WHITE.saturation());
+    /// ```
+    pub fn saturation(&self) -> f32 {
+        if self.l > 0.0 {
+            self.u.hypot(self.v) / self.l
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns apparent brightness of the colour corrected for the
+    /// Helmholtz–Kohlrausch effect.
+    ///
+    /// Saturated colours look brighter than greys of the same L\*.  This
+    /// method estimates that effect using Nayatani’s (1997)
+    /// variable-achromatic-colour (VAC) model which is defined in terms of
+    /// CIELUV saturation and hue:
+    ///
+    /// `L*_HK = L* + (−0.1340·q(θ) + 0.0872·K_Br)·s_uv·L*`
+    ///
+    /// where s_uv·L\* is simply the chroma `√(u² + v²)` (see
+    /// [`Luv::saturation`]), θ is the hue angle `atan2(v, u)` and q(θ) is
+    /// a hue-dependent Fourier series given by Nayatani.  K_Br depends on
+    /// adapting luminance L_a; this method assumes L_a = 20 cd/m² (typical
+    /// for a display in an office) which gives K_Br ≈ 0.8147.  With those
+    /// parameters the chroma coefficient ranges from about 0.045 for yellows
+    /// to about 0.092 for blues and purples.
+    ///
+    /// The result is in units of L\* and equals L\* for neutral colours.  It’s
+    /// meant for ordering colours by how bright they look, e.g. when sorting
+    /// vivid palettes.  The model was fitted to a limited set of observations
+    /// and is only a rough approximation; in particular it doesn’t account
+    /// for the viewing surround, and the correction grows with chroma without
+    /// bound so values for colours outside of the sRGB gamut are not
+    /// meaningful.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let yellow = luv::Luv::from_rgb(&[255, 255, 0]);
+    /// let grey = luv::Luv { l: yellow.l, u: 0.0, v: 0.0 };
+    /// assert_eq!(grey.l, grey.apparent_brightness());
+    /// assert!(yellow.apparent_brightness() > grey.apparent_brightness());
+    /// ```
+    pub fn apparent_brightness(&self) -> f32 {
+        // K_Br = 0.2717 · (6.469 + 6.362·L_a^0.4495) / (6.469 + L_a^0.4495)
+        // for L_a = 20 cd/m².
+        const K_BR: f32 = 0.8147419;
+        let chroma = self.u.hypot(self.v);
+        if chroma == 0.0 {
+            return self.l;
+        }
+        // Coefficients of cos(kθ) and sin(kθ) for k = 1…4 in q(θ).
+        const Q_COS: [f32; 4] = [-0.03017, -0.04556, -0.02667, -0.00295];
+        const Q_SIN: [f32; 4] = [0.14592, 0.05084, -0.01900, -0.00764];
+        let theta = self.v.atan2(self.u);
+        let q = (0..4).fold(-0.01585, |q, k| {
+            let (sin, cos) = ((k + 1) as f32 * theta).sin_cos();
+            mul_add(Q_COS[k], cos, mul_add(Q_SIN[k], sin, q))
+        });
+        mul_add(-0.1340 * q + 0.0872 * K_BR, chroma, self.l)
+    }
+
+    /// Returns colour with lightness snapped to one of `levels` bands.
+    ///
+    /// The 0–100 lightness range is divided into `levels` evenly spaced
+    /// values (including both ends) and L\* is snapped to the nearest one.
+    /// For example, with four levels, lightness becomes one of 0, 33⅓, 66⅔
+    /// or 100.  Applied to every pixel of an image, this produces posterised
+    /// (or cel-shaded) look with flat bands of lightness while keeping the
+    /// colours.  As in [`Luv::invert_lightness`], u\* and v\* are scaled by
+    /// the ratio of the new and old lightness so that the colour keeps its
+    /// u′v′ chromaticity.  Note that this may push saturated colours outside
+    /// of the sRGB gamut.
+    ///
+    /// If `levels` is less than two, the colour is returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 60.0, u: 30.0, v: -12.0 };
+    /// let poster = luv.posterize_lightness(3);
+    /// assert_eq!(luv::Luv { l: 50.0, u: 25.0, v: -10.0 }, poster);
+    /// ```
+    pub fn posterize_lightness(&self, levels: u8) -> Luv {
+        if levels < 2 {
+            return *self;
+        }
+        let step = 100.0 / (levels - 1) as f32;
+        let l = (self.l.clamp(0.0, 100.0) / step).round() * step;
+        let scale = if self.l > 0.0 { l / self.l } else { 0.0 };
+        Luv {
+            l,
+            u: self.u * scale,
+            v: self.v * scale,
+        }
+    }
+
+    /// Returns shade of grey with the same lightness as the colour.
+    ///
+    /// This is the point on the achromatic axis closest to the colour, i.e.
+    /// the colour with u\* and v\* set to zero.  It’s a natural target when
+    /// reducing chroma to bring a colour into gamut.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 60.0, u: 30.0, v: -12.0 };
+    /// assert_eq!(luv::Luv { l: 60.0, u: 0.0, v: 0.0 }, luv.neutral());
+    /// ```
+    pub fn neutral(&self) -> Luv {
         Luv {
             l: self.l,
-            u: self.c * self.h.cos(),
-            v: self.c * self.h.sin(),
+            u: 0.0,
+            v: 0.0,
+        }
+    }
+
+    /// Snaps colours which are almost neutral to exact grey.
+    ///
+    /// If chroma of the colour, i.e. `√(u² + v²)`, is at most `epsilon`,
+    /// returns [`Luv::neutral`]; otherwise returns the colour unchanged.
+    ///
+    /// Due to limited precision, converting 8-bit sRGB greys results in
+    /// u\* and v\* which are tiny but not exactly zero (see
+    /// [`grey_chroma_residuals`]).  Hue of such colours is meaningless and
+    /// may vary wildly between similar greys.  The largest residual chroma is
+    /// below 4·10⁻⁵ so `epsilon` of 10⁻⁴ is a sensible default for colours
+    /// converted from 8-bit sRGB; it’s still far below any perceptible
+    /// difference.  Colours which went through further processing may need
+    /// a larger value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let grey = luv::Luv::from_rgb(&[196, 196, 196]);
+    /// assert_ne!(0.0, grey.v);
+    /// let got = grey.denoise_neutral(1e-4);
+    /// assert_eq!(luv::Luv { l: grey.l, u: 0.0, v: 0.0 }, got);
+    ///
+    /// let red = luv::Luv::from_rgb(&[196, 0, 0]);
+    /// assert_eq!(red, red.denoise_neutral(1e-4));
+    /// ```
+    pub fn denoise_neutral(&self, epsilon: f32) -> Luv {
+        if self.u.hypot(self.v) <= epsilon {
+            self.neutral()
+        } else {
+            *self
+        }
+    }
+
+    /// Returns colour with chroma multiplied by given factor.
+    ///
+    /// u\* and v\* are multiplied by `factor` while L\* is kept unchanged so
+    /// hue is preserved (for positive factors).  Factor below one desaturates
+    /// the colour with zero resulting in a shade of grey; factor above one
+    /// makes it more saturated and may move it outside of the sRGB gamut.
+    /// See [`scale_chroma_in_place`] for a bulk variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 50.0, u: 30.0, v: -40.0 };
+    /// let got = luv.scale_chroma(0.5);
+    /// assert_eq!(luv::Luv { l: 50.0, u: 15.0, v: -20.0 }, got);
+    /// assert_eq!(luv.neutral(), luv.scale_chroma(0.0));
+    /// ```
+    pub fn scale_chroma(&self, factor: f32) -> Luv {
+        Luv {
+            l: self.l,
+            u: self.u * factor,
+            v: self.v * factor,
+        }
+    }
+
+    /// Returns colour with chroma limited to given maximum.
+    ///
+    /// Chroma of a L\*u\*v\* colour is its distance from the neutral axis,
+    /// i.e. `√(u² + v²)`.  If it exceeds `max_chroma`, u\* and v\* are scaled
+    /// down proportionally so that the result has chroma equal `max_chroma`;
+    /// otherwise the colour is returned unchanged.  Lightness and hue are
+    /// preserved.  A non-positive `max_chroma` results in a shade of grey.
+    ///
+    /// Note that this operates on absolute chroma and doesn’t consider sRGB
+    /// gamut.  A colour outside of the gamut may remain so after the
+    /// operation.  See [`LCh::max_chroma`] for finding chroma limit of
+    /// displayable colours.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 50.0, u: 30.0, v: -40.0 };
+    /// let got = luv.clamp_chroma(10.0);
+    /// assert_eq!(luv::Luv { l: 50.0, u: 6.0, v: -8.0 }, got);
+    /// assert_eq!(luv, luv.clamp_chroma(60.0));
+    /// ```
+    pub fn clamp_chroma(&self, max_chroma: f32) -> Luv {
+        let max_chroma = max_chroma.max(0.0);
+        let chroma = self.u.hypot(self.v);
+        if chroma <= max_chroma {
+            return *self;
+        }
+        let scale = max_chroma / chroma;
+        Luv {
+            l: self.l,
+            u: self.u * scale,
+            v: self.v * scale,
+        }
+    }
+
+    /// Returns colour with each coordinate clamped to given closed interval.
+    ///
+    /// Each of the `l`, `u` and `v` arguments is a `(min, max)` pair bounding
+    /// the corresponding coordinate.  The clamping is done independently for
+    /// each channel and isn’t gamut-aware; in particular, clamping u\* and v\*
+    /// separately may change hue of the colour and the result may lie outside
+    /// of the sRGB gamut.
+    ///
+    /// # Panics
+    ///
+    /// Panics if for any of the intervals `min > max` or either bound is NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 120.0, u: -150.0, v: 30.0 };
+    /// let got = luv.clamp_ranges((0.0, 100.0), (-100.0, 100.0), (-50.0, 0.0));
+    /// assert_eq!(luv::Luv { l: 100.0, u: -100.0, v: 0.0 }, got);
+    /// ```
+    pub fn clamp_ranges(
+        &self,
+        l: (f32, f32),
+        u: (f32, f32),
+        v: (f32, f32),
+    ) -> Luv {
+        Luv {
+            l: self.l.clamp(l.0, l.1),
+            u: self.u.clamp(u.0, u.1),
+            v: self.v.clamp(v.0, v.1),
+        }
+    }
+
+    /// Fades the colour towards an `other` colour.
+    ///
+    /// Each of the coordinates is linearly interpolated with `t` equal zero
+    /// corresponding to `self` and one to `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 60.0, u: 40.0, v: -20.0 };
+    /// let other = luv::Luv { l: 80.0, u: 0.0, v: 20.0 };
+    /// let got = luv.fade_to(&other, 0.25);
+    /// assert_eq!(luv::Luv { l: 65.0, u: 30.0, v: -10.0 }, got);
+    /// ```
+    pub fn fade_to(&self, other: &Luv, t: f32) -> Luv {
+        Luv {
+            l: mul_add(other.l - self.l, t, self.l),
+            u: mul_add(other.u - self.u, t, self.u),
+            v: mul_add(other.v - self.v, t, self.v),
+        }
+    }
+
+    /// Fades the colour towards the grey of the same lightness.
+    ///
+    /// u\* and v\* are scaled by `1 − t` while L\* is kept unchanged.  In
+    /// other words, chroma is reduced by factor of `t` without changing
+    /// lightness or hue.  With `t` equal one, the result is neutral grey.  This
+    /// is useful for rendering disabled UI elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 60.0, u: 40.0, v: -20.0 };
+    /// let got = luv.fade_to_grey(0.75);
+    /// assert_eq!(luv::Luv { l: 60.0, u: 10.0, v: -5.0 }, got);
+    /// ```
+    pub fn fade_to_grey(&self, t: f32) -> Luv {
+        let grey = Luv {
+            l: self.l,
+            u: 0.0,
+            v: 0.0,
+        };
+        self.fade_to(&grey, t)
+    }
+
+    /// Returns a tint of the colour, i.e. the colour mixed with white.
+    ///
+    /// This is [`Luv::fade_to`] towards [`Luv::WHITE`] (the D65 reference
+    /// white with L\* of 100).  With `t` equal zero the colour is unchanged
+    /// and with `t` equal one the result is white.  Because the mixing is done
+    /// in L\*u\*v\* space, chroma decreases linearly while lightness grows
+    /// which avoids the washed-out look of tints made by mixing sRGB
+    /// components.  Hue is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 40.0, u: 60.0, v: -20.0 };
+    /// assert_eq!(luv::Luv { l: 70.0, u: 30.0, v: -10.0 }, luv.tint(0.5));
+    /// assert_eq!(luv::Luv::WHITE, luv.tint(1.0));
+    /// ```
+    pub fn tint(&self, t: f32) -> Luv { self.fade_to(&Luv::WHITE, t) }
+
+    /// Returns a shade of the colour, i.e. the colour mixed with black.
+    ///
+    /// This is [`Luv::fade_to`] towards [`Luv::BLACK`] (L\* of zero).  With
+    /// `t` equal zero the colour is unchanged and with `t` equal one the result
+    /// is black.  Since u\* and v\* are scaled together with L\*, the
+    /// colour keeps its u′v′ chromaticity and hue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 40.0, u: 60.0, v: -20.0 };
+    /// assert_eq!(luv::Luv { l: 30.0, u: 45.0, v: -15.0 }, luv.shade(0.25));
+    /// assert_eq!(luv::Luv::BLACK, luv.shade(1.0));
+    /// ```
+    pub fn shade(&self, t: f32) -> Luv { self.fade_to(&Luv::BLACK, t) }
+
+    /// Maps the colour onto a two-colour gradient for a duotone effect.
+    ///
+    /// Only lightness of the source colour is used; its chroma and hue are
+    /// discarded.  L\* divided by 100 (clamped to the 0–1 range) is used as
+    /// the interpolation factor for [`Luv::fade_to`] from `dark` to `light`.
+    /// Thus black maps to `dark`, white to `light` and greys in between to
+    /// colours along the line connecting the two.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let dark = luv::Luv { l: 20.0, u: -10.0, v: -40.0 };
+    /// let light = luv::Luv { l: 90.0, u: 30.0, v: 60.0 };
+    /// let luv = luv::Luv { l: 25.0, u: 80.0, v: 10.0 };
+    /// let got = luv.duotone(&dark, &light);
+    /// assert_eq!(luv::Luv { l: 37.5, u: 0.0, v: -15.0 }, got);
+    /// ```
+    pub fn duotone(&self, dark: &Luv, light: &Luv) -> Luv {
+        dark.fade_to(light, (self.l / 100.0).clamp(0.0, 1.0))
+    }
+
+    /// Returns relative luminance of the colour as defined by WCAG.
+    ///
+    /// This is the Y coordinate of the colour in XYZ colour space clamped to
+    /// the 0–1 range.  Note that it depends on L\* only.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(1.0, luv::Luv::WHITE.relative_luminance());
+    /// assert_eq!(0.0, luv::Luv::BLACK.relative_luminance());
+    /// ```
+    pub fn relative_luminance(&self) -> f32 {
+        luminance_from_l_star(self.l).clamp(0.0, 1.0)
+    }
+
+    /// Returns WCAG contrast ratio between the colour and an `other`.
+    ///
+    /// The ratio is `(L₁ + 0.05) / (L₂ + 0.05)` where L₁ and L₂ are relative
+    /// luminances (see [`Luv::relative_luminance`]) of the lighter and darker
+    /// colour respectively.  It ranges from 1 (no contrast) to 21 (black on
+    /// white).  WCAG 2 requires ratio of at least 4.5 for normal text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ratio = luv::Luv::BLACK.contrast_ratio(&luv::Luv::WHITE);
+    /// assert!((ratio - 21.0).abs() < 0.0001);
+    /// ```
+    pub fn contrast_ratio(&self, other: &Luv) -> f32 {
+        let a = self.relative_luminance() + 0.05;
+        let b = other.relative_luminance() + 0.05;
+        if a > b {
+            a / b
+        } else {
+            b / a
+        }
+    }
+
+    /// Returns colour with given relative luminance and the same
+    /// chromaticity.
+    ///
+    /// `y` is the desired Y coordinate in XYZ colour space (i.e. linear
+    /// luminance relative to the reference white) and is clamped to the 0–1
+    /// range.  It’s converted to lightness using [`l_star_from_luminance`].
+    /// As in [`Luv::invert_lightness`], u\* and v\* are scaled by the ratio
+    /// of the new and old lightness so that the colour keeps its u′v′
+    /// chromaticity.  If the colour has non-positive lightness, the result is
+    /// a shade of grey.  This is the luminance-domain
+    /// counterpart of changing `l` directly and is the inverse of
+    /// [`Luv::relative_luminance`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv::from_rgb(&[40, 90, 200]);
+    /// let got = luv.with_relative_luminance(0.5);
+    /// assert!((got.relative_luminance() - 0.5).abs() < 1e-6);
+    /// ```
+    pub fn with_relative_luminance(&self, y: f32) -> Luv {
+        let l = l_star_from_luminance(y.clamp(0.0, 1.0));
+        let scale = if self.l > 0.0 { l / self.l } else { 0.0 };
+        Luv {
+            l,
+            u: self.u * scale,
+            v: self.v * scale,
+        }
+    }
+
+    /// Adjusts lightness of the colour so that it has at least given contrast
+    /// ratio against a `background`.
+    ///
+    /// If the colour already meets the requirement, it’s returned unchanged.
+    /// Otherwise, the colour is darkened if the background has better
+    /// contrast against black than against white and lightened otherwise.  If
+    /// the target ratio is unreachable in that direction, the opposite one is
+    /// tried.  Since relative luminance depends on L\* only, rather than
+    /// searching, the L\* which gives the exact ratio is computed directly
+    /// (with a small margin to account for rounding errors).
+    ///
+    /// Hue and chroma are preserved except that chroma is reduced to
+    /// [`LCh::max_chroma`] at the new lightness if necessary to keep the
+    /// colour displayable.  If the ratio cannot be achieved at all, black or
+    /// white (whichever gives higher contrast) is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let text = luv::Luv::from_rgb(&[40, 90, 200]);
+    /// let background = luv::Luv::from_rgb(&[20, 30, 60]);
+    /// let adjusted = text.adjust_for_contrast(&background, 4.5);
+    /// assert!(adjusted.contrast_ratio(&background) >= 4.5);
+    /// assert!(adjusted.l > text.l);
+    /// ```
+    pub fn adjust_for_contrast(&self, background: &Luv, min_ratio: f32) -> Luv {
+        if self.contrast_ratio(background) >= min_ratio {
+            return *self;
+        }
+        let bg = background.relative_luminance() + 0.05;
+        let darker = bg / min_ratio - 0.05;
+        let lighter = bg * min_ratio - 0.05;
+        let prefer_darker = bg / 0.05 > 1.05 / bg;
+
+        // Convert relative luminance to L* adding margin in given direction.
+        let lightness = |y: f32, margin: f32| {
+            (l_star_from_luminance(y) + margin).clamp(0.0, 100.0)
+        };
+        let l = match (darker >= 0.0, lighter <= 1.0) {
+            (true, true) if prefer_darker => lightness(darker, -0.001),
+            (true, false) => lightness(darker, -0.001),
+            (_, true) => lightness(lighter, 0.001),
+            (false, false) if prefer_darker => 0.0,
+            (false, false) => 100.0,
+        };
+
+        let lch = LCh::from_luv(*self);
+        let c = lch.c.min(LCh::max_chroma(l, lch.h));
+        LCh { l, c, h: lch.h }.to_luv()
+    }
+
+    /// Converts the colour to `LCh` storing the result in `out`.
+    ///
+    /// This is equivalent to `*out = LCh::from_luv(*self)` and is meant for
+    /// tight loops which fill preallocated buffers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 52.33686, u: 75.5516, v: 19.998878 };
+    /// let mut lch = luv::LCh::default();
+    /// luv.to_lch_into(&mut lch);
+    /// assert_eq!(luv::LCh::from_luv(luv), lch);
+    /// ```
+    pub fn to_lch_into(&self, out: &mut LCh) {
+        out.l = self.l;
+        out.c = self.u.hypot(self.v);
+        out.h = self.v.atan2(self.u);
+    }
+}
+
+
+impl LCh {
+    /// Black, i.e. colour with all coordinates equal zero.
+    pub const BLACK: LCh = LCh {
+        l: 0.0,
+        c: 0.0,
+        h: 0.0,
+    };
+
+    /// White, i.e. the D65 reference white.
+    pub const WHITE: LCh = LCh {
+        l: 100.0,
+        c: 0.0,
+        h: 0.0,
+    };
+
+    /// Perceptual middle grey, i.e. achromatic colour with L\* equal 50.  See
+    /// [`Luv::MIDDLE_GREY`].
+    pub const MIDDLE_GREY: LCh = LCh {
+        l: 50.0,
+        c: 0.0,
+        h: 0.0,
+    };
+
+    /// Constructs a new `LCh` from a three-element array of `u8`s
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let rgb = [240, 33, 95];
+    /// let lch = luv::LCh::from_rgb(&rgb);
+    /// assert_eq!(luv::LCh {l: 52.334686, c: 139.20776, h: 0.056403756}, lch);
+    /// assert_eq!(lch, luv::LCh::from_luv(luv::Luv::from_rgb(&rgb)));
+    /// ```
+    pub fn from_rgb(rgb: &[u8; 3]) -> Self {
+        LCh::from_luv(Luv::from_rgb(rgb))
+    }
+
+    /// Constructs a new `LCh` from a four-element array of `u8`s
+    ///
+    /// The `LCh` struct does not store alpha channel information, so the last
+    /// `u8` representing alpha is discarded. This convenience method exists
+    /// in order to easily measure colors already stored in an RGBA array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let rgba = [240, 33, 95, 255];
+    /// let lch = luv::LCh::from_rgba(&rgba);
+    /// assert_eq!(luv::LCh {l: 52.334686, c: 139.20776, h: 0.056403756}, lch);
+    /// assert_eq!(lch, luv::LCh::from_luv(luv::Luv::from_rgba(&rgba)));
+    /// ```
+    pub fn from_rgba(rgba: &[u8; 4]) -> Self {
+        LCh::from_luv(Luv::from_rgba(rgba))
+    }
+
+    /// Constructs a new `LCh` from a `Luv`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let luv = luv::Luv { l: 52.33686, u: 75.5516, v: 19.998878 };
+    /// let lch = luv::LCh::from_luv(luv);
+    /// assert_eq!(luv::LCh { l: 52.33686, c: 78.15369, h: 0.25877 }, lch);
+    ///
+    /// let luv = luv::Luv { l: 52.33686, u: 0.0, v: 0.0 };
+    /// let lch = luv::LCh::from_luv(luv);
+    /// assert_eq!(luv::LCh { l: 52.33686, c: 0.0, h: 0.0 }, lch);
+    /// ```
+    pub fn from_luv(luv: Luv) -> Self {
+        LCh {
+            l: luv.l,
+            c: luv.u.hypot(luv.v),
+            h: luv.v.atan2(luv.u),
+        }
+    }
+
+    /// Constructs a new `LCh` from a `Luv` snapping near-neutral colours to
+    /// exact grey.
+    ///
+    /// Hue of colours with tiny chroma is computed by `atan2` from values
+    /// which are mostly rounding noise (e.g. sRGB greys converted with
+    /// [`Luv::from_rgb`] have chroma up to 4·10⁻⁵, see
+    /// [`grey_chroma_residuals`]).  Such hue is meaningless and the noise
+    /// keeps propagating through subsequent conversions.
+    ///
+    /// This function treats colours whose chroma is at most 10⁻⁴ as neutral
+    /// (see [`Luv::denoise_neutral`]) and for them returns zero chroma with
+    /// hue of zero used as a sentinel.  Converting such value back with
+    /// [`LCh::to_luv`] gives u\* and v\* of exactly zero and converting that
+    /// again gives back the very same `LCh`, so Luv→LCh→Luv→LCh chains are
+    /// stable for neutral colours.  The cost is that u\* and v\* of
+    /// near-neutral colours change by at most 10⁻⁴ which is far below any
+    /// perceptible difference.  Colours with larger chroma are converted
+    /// exactly like with [`LCh::from_luv`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let grey = luv::Luv::from_rgb(&[196, 196, 196]);
+    /// let lch = luv::LCh::from_luv_stable(grey);
+    /// assert_eq!(luv::LCh { l: grey.l, c: 0.0, h: 0.0 }, lch);
+    /// assert_eq!(luv::Luv { l: grey.l, u: 0.0, v: 0.0 }, lch.to_luv());
+    ///
+    /// let luv = luv::Luv { l: 52.33686, u: 75.5516, v: 19.998878 };
+    /// assert_eq!(luv::LCh::from_luv(luv), luv::LCh::from_luv_stable(luv));
+    /// ```
+    pub fn from_luv_stable(luv: Luv) -> Self {
+        LCh::from_luv(luv.denoise_neutral(1e-4))
+    }
+
+    /// Returns the `LCh`'s color in RGB, in a 3-element array
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut lch = luv::LCh { l: 52.334686, c: 139.20773, h: 0.05640377 };
+    /// assert_eq!([240, 33, 95], lch.to_rgb());
+    ///
+    /// lch.h += std::f32::consts::TAU;
+    /// assert_eq!([240, 33, 95], lch.to_rgb());
+    /// ```
+    pub fn to_rgb(&self) -> [u8; 3] { self.to_luv().to_rgb() }
+
+    /// Returns the `LCh`'s color in `Luv`
+    ///
+    /// Note that due to imprecision of floating point arithmetic, conversions
+    /// between Luv and LCh are not stable.  A chain of Luv→LCh→Luv or
+    /// LCh→Luv→LCh operations isn’t guaranteed to give back the source colour.
+    /// For near-neutral colours, [`LCh::from_luv_stable`] avoids the problem.
+    ///
+    /// Negative chroma is not treated specially.  Since u\* and v\* are
+    /// calculated as `c·cos(h)` and `c·sin(h)` respectively, negative chroma
+    /// results in a colour with hue opposite to `h`.  Converting such colour
+    /// back to `LCh` gives a non-negative chroma and a different hue.  Use
+    /// [`LCh::normalized`] to get that canonical representation directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lch = luv::LCh { l: 52.33686, c: 78.15369, h: 0.25877 };
+    /// let luv = lch.to_luv();
+    /// assert_eq!(luv::Luv { l: 52.33686, u: 75.5516, v: 19.998878 }, luv);
+    ///
+    /// let lch = luv::LCh { l: 52.33686, c: 0.0, h: 0.25877 };
+    /// let luv = lch.to_luv();
+    /// assert_eq!(luv::Luv { l: 52.33686, u: 0.0, v: 0.0 }, luv);
+    ///
+    /// let inp = luv::Luv { l: 29.52658, u: 58.595745, v: -36.281406 };
+    /// let lch = luv::LCh { l: 29.52658, c: 68.91881,  h: -0.5544043 };
+    /// let out = luv::Luv { l: 29.52658, u: 58.59575,  v: -36.281406 };
+    /// assert_eq!(lch, luv::LCh::from_luv(inp));
+    /// assert_eq!(out, lch.to_luv());
+    /// ```
+    pub fn to_luv(&self) -> Luv {
+        Luv {
+            l: self.l,
+            u: self.c * self.h.cos(),
+            v: self.c * self.h.sin(),
+        }
+    }
+
+    /// Converts the colour to `Luv` storing the result in `out`.
+    ///
+    /// This is equivalent to `*out = self.to_luv()` and is meant for tight
+    /// loops which fill preallocated buffers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lch = luv::LCh { l: 52.33686, c: 78.15369, h: 0.25877 };
+    /// let mut luv = luv::Luv::default();
+    /// lch.to_luv_into(&mut luv);
+    /// assert_eq!(lch.to_luv(), luv);
+    /// ```
+    pub fn to_luv_into(&self, out: &mut Luv) {
+        out.l = self.l;
+        out.u = self.c * self.h.cos();
+        out.v = self.c * self.h.sin();
+    }
+
+    /// Packs the colour into a 32-bit integer.
+    ///
+    /// The colour is converted to L\*u\*v\* and packed with
+    /// [`Luv::to_packed_u32`] which see for description of the bit layout
+    /// and precision.  Note that for colours with low chroma, hue may
+    /// change considerably after a round trip.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lch = luv::LCh { l: 50.0, c: 60.0, h: 1.0 };
+    /// let got = luv::LCh::from_packed_u32(lch.to_packed_u32());
+    /// assert!((got.l - lch.l).abs() < 0.05);
+    /// assert!((got.c - lch.c).abs() < 0.1);
+    /// assert!((got.h - lch.h).abs() < 0.01);
+    /// ```
+    pub fn to_packed_u32(&self) -> u32 { self.to_luv().to_packed_u32() }
+
+    /// Unpacks colour from a 32-bit integer.
+    ///
+    /// This is an inverse of [`LCh::to_packed_u32`].
+    pub fn from_packed_u32(packed: u32) -> Self {
+        LCh::from_luv(Luv::from_packed_u32(packed))
+    }
+
+    /// Blends the colour with an `other` keeping the result saturated.
+    ///
+    /// Lightness is linearly interpolated and hue is interpolated along the
+    /// shorter arc between the two hues (if the hues are exactly opposite, the
+    /// path goes counter-clockwise, i.e. with increasing hue).  If one of the
+    /// colours is achromatic, its hue is meaningless and the hue of the other
+    /// colour is used.  Rather than being interpolated, chroma of the result is
+    /// the larger of the two chromas.  This avoids muddy midpoints which
+    /// interpolation in L\*u\*v\* space would produce when blending colours of
+    /// distant hues.
+    ///
+    /// `t` is the interpolation factor with zero corresponding to `self` and
+    /// one to `other`.  Note that, because of the chroma rule, result for `t`
+    /// equal zero or one has chroma of the more saturated colour.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let red = luv::LCh { l: 50.0, c: 100.0, h: 0.25 };
+    /// let green = luv::LCh { l: 70.0, c: 60.0, h: 2.25 };
+    /// let blend = red.blend_preserving_chroma(&green, 0.5);
+    /// assert_eq!(luv::LCh { l: 60.0, c: 100.0, h: 1.25 }, blend);
+    /// ```
+    pub fn blend_preserving_chroma(&self, other: &LCh, t: f32) -> LCh {
+        let h = if self.c == 0.0 {
+            other.h
+        } else if other.c == 0.0 {
+            self.h
+        } else {
+            mul_add(hue_delta(self.h, other.h), t, self.h)
+        };
+        LCh {
+            l: mul_add(other.l - self.l, t, self.l),
+            c: self.c.max(other.c),
+            h,
+        }
+    }
+
+    /// Measures weighted perceptual distance between the colours of one `LCh`
+    /// and an `other`.
+    ///
+    /// The distance is calculated as `√((w_l·ΔL)² + (w_c·ΔC)² + (w_h·ΔH)²)`
+    /// where ΔL and ΔC are differences in lightness and chroma respectively.
+    /// The hue difference is normalised to the same units as the other
+    /// coordinates by taking ΔH = 2·√(C₁·C₂)·sin(Δh/2) where Δh is the
+    /// difference of hue angles along the shorter arc.  ΔH is thus zero if
+    /// either colour is achromatic and with all weights equal one the result
+    /// is the same as Euclidean distance in L\*u\*v\* space (i.e. square root
+    /// of [`Luv::squared_distance`] between the colours).
+    ///
+    /// Increasing one of the weights makes the corresponding component more
+    /// important when looking for the closest colour.  For example, with large
+    /// `w_h` colours of matching hue are preferred.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let a = luv::LCh { l: 50.0, c: 40.0, h: 0.0 };
+    /// let b = luv::LCh { l: 60.0, c: 40.0, h: std::f32::consts::PI };
+    /// assert_eq!(80.622574, a.cylindrical_distance(&b, 1.0, 1.0, 1.0));
+    /// assert_eq!(10.0, a.cylindrical_distance(&b, 1.0, 1.0, 0.0));
+    /// ```
+    pub fn cylindrical_distance(
+        &self,
+        other: &LCh,
+        w_l: f32,
+        w_c: f32,
+        w_h: f32,
+    ) -> f32 {
+        let dl = w_l * (self.l - other.l);
+        let dc = w_c * (self.c - other.c);
+        let dh = (self.c * other.c).max(0.0).sqrt() *
+            (hue_delta(self.h, other.h) * 0.5).sin() *
+            2.0 *
+            w_h;
+        (dl * dl + dc * dc + dh * dh).sqrt()
+    }
+
+    /// Returns whether the colour lies within the sRGB gamut.
+    ///
+    /// This is equivalent to converting the colour to `Luv` and calling
+    /// [`Luv::is_displayable`] on it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(luv::LCh { l: 50.0, c: 20.0, h: 1.0 }.is_displayable());
+    /// assert!(!luv::LCh { l: 50.0, c: 150.0, h: 1.0 }.is_displayable());
+    /// ```
+    pub fn is_displayable(&self) -> bool { self.to_luv().is_displayable() }
+
+    /// Returns the largest chroma of a displayable colour with given lightness
+    /// and hue.
+    ///
+    /// The value is found with a binary search over chroma using
+    /// [`LCh::is_displayable`] as the predicate.  The search stops once the
+    /// bracketing interval is narrower than 0.001 and returns its lower bound
+    /// thus colour with the returned chroma is displayable.  For lightness
+    /// outside of the (0, 100) range the function returns zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let c = luv::LCh::max_chroma(50.0, 0.2);
+    /// assert!(luv::LCh { l: 50.0, c, h: 0.2 }.is_displayable());
+    /// assert!(!luv::LCh { l: 50.0, c: c + 0.01, h: 0.2 }.is_displayable());
+    /// ```
+    pub fn max_chroma(l: f32, h: f32) -> f32 {
+        if !(l > 0.0 && l < 100.0) {
+            return 0.0;
+        }
+        // The most saturated sRGB colour, red, has chroma around 179.
+        let (mut lo, mut hi) = (0.0, 200.0);
+        while hi - lo > 0.001 {
+            let c = (lo + hi) * 0.5;
+            if (LCh { l, c, h }).is_displayable() {
+                lo = c;
+            } else {
+                hi = c;
+            }
+        }
+        lo
+    }
+
+    /// Returns the colour with hue snapped to the nearest of given anchors.
+    ///
+    /// `anchors` are hues in radians.  The one with the smallest angular
+    /// distance (see [`LCh::hue_arc_length`]) to the colour’s hue is chosen,
+    /// with ties resolved in favour of the earlier anchor, and replaces the
+    /// hue.  Lightness and chroma are unchanged.  This can be used to
+    /// quantise a palette in the hue dimension, e.g. to restrict it to warm
+    /// hues only.  If `anchors` is empty, the colour is returned unchanged.
+    ///
+    /// Note that changing the hue may move the colour outside of the sRGB
+    /// gamut.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let anchors = [0.0, 2.0, 4.0];
+    /// let lch = luv::LCh { l: 50.0, c: 20.0, h: 2.3 };
+    /// assert_eq!(luv::LCh { l: 50.0, c: 20.0, h: 2.0 }, lch.snap_hue(&anchors));
+    ///
+    /// let lch = luv::LCh { l: 50.0, c: 20.0, h: 6.0 };
+    /// assert_eq!(0.0, lch.snap_hue(&anchors).h);
+    /// assert_eq!(lch, lch.snap_hue(&[]));
+    /// ```
+    pub fn snap_hue(&self, anchors: &[f32]) -> LCh {
+        let dist = |h: f32| hue_delta(self.h, h).abs();
+        let h = anchors.iter().copied().fold(None, |best, h| match best {
+            Some(best) if dist(best) <= dist(h) => Some(best),
+            _ => Some(h),
+        });
+        LCh {
+            h: h.unwrap_or(self.h),
+            ..*self
+        }
+    }
+
+    /// Returns the colour with hue rotated by given angle.
+    ///
+    /// Lightness and chroma are unchanged which means that the resulting
+    /// colour may fall outside of the sRGB gamut even if `self` is within it.
+    /// Use [`LCh::rotate_hue_in_gamut`] to avoid that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lch = luv::LCh { l: 50.0, c: 20.0, h: 0.5 };
+    /// assert_eq!(luv::LCh { l: 50.0, c: 20.0, h: 1.5 }, lch.rotate_hue(1.0));
+    /// ```
+    pub fn rotate_hue(&self, radians: f32) -> LCh {
+        LCh {
+            l: self.l,
+            c: self.c,
+            h: self.h + radians,
+        }
+    }
+
+    /// Returns the colour with hue rotated by given angle keeping the result
+    /// displayable.
+    ///
+    /// The maximum chroma of displayable colours depends on hue so rotating
+    /// a saturated colour may move it outside of the sRGB gamut.  This method
+    /// rotates the hue and then, if necessary, reduces chroma to
+    /// [`LCh::max_chroma`] for the new hue.  Lightness is unchanged.  Note that
+    /// the chroma is never increased; if `self` is outside of the gamut due to
+    /// its lightness, so will be the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let yellow = luv::LCh::from_rgb(&[255, 255, 0]);
+    /// let rotated = yellow.rotate_hue_in_gamut(std::f32::consts::PI);
+    /// assert!(rotated.c < yellow.c);
+    /// assert!(rotated.is_displayable());
+    /// ```
+    pub fn rotate_hue_in_gamut(&self, radians: f32) -> LCh {
+        let mut lch = self.rotate_hue(radians);
+        lch.c = lch.c.min(LCh::max_chroma(lch.l, lch.h));
+        lch
+    }
+
+    /// Returns an equivalent colour with non-negative chroma and hue in the
+    /// (-π, π] range.
+    ///
+    /// If chroma is negative, it is negated and hue rotated by π.  Hue is
+    /// then wrapped into the (-π, π] range which matches (up to the boundary)
+    /// the range of hues returned by [`LCh::from_luv`].  Lightness is
+    /// unchanged.  The result describes the same colour as `self`, i.e. both
+    /// convert to the same [`Luv`] value (up to floating point rounding).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::f32::consts::PI;
+    ///
+    /// let lch = luv::LCh { l: 50.0, c: -10.0, h: 0.0 };
+    /// assert_eq!(luv::LCh { l: 50.0, c: 10.0, h: PI }, lch.normalized());
+    ///
+    /// let lch = luv::LCh { l: 50.0, c: 10.0, h: 2.5 * PI }.normalized();
+    /// assert!((lch.h - 0.5 * PI).abs() < 1e-6);
+    /// ```
+    pub fn normalized(&self) -> LCh {
+        let (c, h) = if self.c < 0.0 {
+            (-self.c, self.h + std::f32::consts::PI)
+        } else {
+            (self.c, self.h)
+        };
+        LCh {
+            l: self.l,
+            c,
+            h: hue_delta(0.0, h),
+        }
+    }
+
+    /// Returns shade of grey with the same lightness as the colour.
+    ///
+    /// The result has zero chroma and the same lightness and hue as `self`.
+    /// Note that hue of an achromatic colour carries no information; it is
+    /// kept only so that chroma can be restored.  See [`Luv::neutral`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lch = luv::LCh { l: 60.0, c: 30.0, h: 0.5 };
+    /// assert_eq!(luv::LCh { l: 60.0, c: 0.0, h: 0.5 }, lch.neutral());
+    /// ```
+    pub fn neutral(&self) -> LCh {
+        LCh {
+            l: self.l,
+            c: 0.0,
+            h: self.h,
+        }
+    }
+
+    /// Returns colour with inverted lightness and the same chroma and hue.
+    ///
+    /// Lightness is mapped from L\* to 100 − L\* while chroma and hue are
+    /// left unchanged.  Unlike [`Luv::invert_lightness`], which preserves
+    /// u′v′ chromaticity, this keeps the colour equally saturated which may
+    /// push the result outside of the sRGB gamut.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lch = luv::LCh { l: 80.0, c: 20.0, h: 0.5 };
+    /// let dark = lch.invert_lightness();
+    /// assert_eq!(luv::LCh { l: 20.0, c: 20.0, h: 0.5 }, dark);
+    /// ```
+    pub fn invert_lightness(&self) -> LCh {
+        LCh {
+            l: 100.0 - self.l,
+            c: self.c,
+            h: self.h,
+        }
+    }
+
+    /// Returns the most saturated displayable colour with the same lightness
+    /// and hue.
+    ///
+    /// Sets chroma to [`LCh::max_chroma`] for the colour’s lightness and hue
+    /// which pushes the colour to the edge of the sRGB gamut along its hue.
+    /// Note that chroma may both increase and decrease.  The result is
+    /// displayable but, due to the tolerance of the search, its chroma may be
+    /// up to 0.001 below the true boundary.  For lightness outside of the
+    /// (0, 100) range, the result is achromatic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lch = luv::LCh { l: 50.0, c: 10.0, h: 2.0 }.with_max_chroma();
+    /// assert!(lch.is_displayable());
+    /// assert!(!luv::LCh { c: lch.c + 0.01, ..lch }.is_displayable());
+    /// ```
+    pub fn with_max_chroma(&self) -> LCh {
+        LCh {
+            l: self.l,
+            c: LCh::max_chroma(self.l, self.h),
+            h: self.h,
+        }
+    }
+
+    /// Returns `n` shades of the colour with lightness going from zero to one
+    /// hundred.
+    ///
+    /// This is equivalent to `self.tonal_ramp_between(n, 0.0, 100.0)`; see
+    /// [`LCh::tonal_ramp_between`] for details.  The first colour of the ramp
+    /// is black and the last one white.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let ramp = luv::LCh::from_rgb(&[0, 128, 255]).tonal_ramp(5);
+    /// let lightness = ramp.iter().map(|lch| lch.l).collect::<Vec<_>>();
+    /// assert_eq!(vec![0.0, 25.0, 50.0, 75.0, 100.0], lightness);
+    /// assert!(ramp.iter().all(luv::LCh::is_displayable));
+    /// ```
+    pub fn tonal_ramp(&self, n: usize) -> Vec<LCh> {
+        self.tonal_ramp_between(n, 0.0, 100.0)
+    }
+
+    /// Returns `n` shades of the colour with lightness going from `from` to
+    /// `to`.
+    ///
+    /// Lightness of the shades is spaced evenly (in L\*) with the first shade
+    /// having lightness `from` and the last one lightness `to`.  If `n` is one,
+    /// the only shade has lightness `from`.  All shades have the colour’s hue
+    /// and its chroma clamped to [`LCh::max_chroma`] at the shade’s lightness
+    /// so that each of them is displayable (provided that the lightness is
+    /// within the 0–100 range).  Shades at the extremes of lightness thus end
+    /// up considerably less saturated than the colour.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let lch = luv::LCh::from_rgb(&[0, 128, 255]);
+    /// let ramp = lch.tonal_ramp_between(3, 20.0, 80.0);
+    /// let lightness = ramp.iter().map(|lch| lch.l).collect::<Vec<_>>();
+    /// assert_eq!(vec![20.0, 50.0, 80.0], lightness);
+    /// ```
+    pub fn tonal_ramp_between(&self, n: usize, from: f32, to: f32) -> Vec<LCh> {
+        let step = if n > 1 {
+            (to - from) / (n - 1) as f32
+        } else {
+            0.0
+        };
+        (0..n)
+            .map(|i| {
+                let l = mul_add(step, i as f32, from);
+                LCh {
+                    l,
+                    c: self.c.min(LCh::max_chroma(l, self.h)),
+                    h: self.h,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns signed difference between hues of the colour and an `other`
+    /// along the shorter arc.
+    ///
+    /// The result is in the (-π, π] range and is positive if going from
+    /// `self`’s hue to `other`’s hue along the shorter arc is counter-clockwise
+    /// (i.e. increases the hue).  Adding it to `self.h` gives a hue equivalent
+    /// to `other.h`.  Chroma isn’t considered so the result is meaningless if
+    /// either colour is achromatic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let a = luv::LCh { l: 50.0, c: 20.0, h: 3.0 };
+    /// let b = luv::LCh { l: 50.0, c: 20.0, h: -3.0 };
+    /// let delta = a.shortest_hue_delta(&b);
+    /// assert!((delta - (std::f32::consts::TAU - 6.0)).abs() < 1e-6);
+    /// ```
+    pub fn shortest_hue_delta(&self, other: &LCh) -> f32 {
+        hue_delta(self.h, other.h)
+    }
+
+    /// Returns signed difference between hues of the colour and an `other`
+    /// along the shorter arc in degrees.
+    ///
+    /// This is [`LCh::shortest_hue_delta`] converted to degrees and is thus
+    /// in the (-180, 180] range (up to floating point rounding).  It’s
+    /// convenient for animation code which computes hue steps in degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let a = luv::LCh { l: 50.0, c: 20.0, h: 350f32.to_radians() };
+    /// let b = luv::LCh { l: 50.0, c: 20.0, h: 10f32.to_radians() };
+    /// assert!((a.shortest_hue_delta_degrees(&b) - 20.0).abs() < 1e-4);
+    /// assert!((b.shortest_hue_delta_degrees(&a) + 20.0).abs() < 1e-4);
+    /// ```
+    pub fn shortest_hue_delta_degrees(&self, other: &LCh) -> f32 {
+        self.shortest_hue_delta(other).to_degrees()
+    }
+
+    /// Returns angular distance between hues of the colour and an `other`.
+    ///
+    /// This is the length of the shorter arc between the hues, i.e. absolute
+    /// value of [`LCh::shortest_hue_delta`], and is in the 0–π range.  It can
+    /// be used to detect gradient stops whose hues are so far apart that hue
+    /// interpolation between them would sweep through unrelated colours.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let a = luv::LCh { l: 50.0, c: 20.0, h: 0.5 };
+    /// let b = luv::LCh { l: 50.0, c: 20.0, h: -0.25 };
+    /// assert_eq!(0.75, a.hue_arc_length(&b));
+    /// ```
+    pub fn hue_arc_length(&self, other: &LCh) -> f32 {
+        self.shortest_hue_delta(other).abs()
+    }
+
+    /// Returns `n` colours evenly interpolated between the colour and an
+    /// `other`.
+    ///
+    /// The first colour of the gradient corresponds to `self` and the last to
+    /// `other`; if `n` is one, only the former is returned.  Lightness and
+    /// chroma are interpolated linearly while hue follows the shorter arc
+    /// between the two hues (see [`LCh::shortest_hue_delta`]) so the gradient
+    /// never sweeps through unrelated hues.  If one of the colours is
+    /// achromatic, i.e. has zero chroma, hue of the other colour is used
+    /// throughout.
+    ///
+    /// Chroma of each step is then clamped to [`LCh::max_chroma`] at the
+    /// step’s lightness and hue so that all the colours are displayable
+    /// (provided that lightness is within the 0–100 range).  Note that this
+    /// applies to the endpoints as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let red = luv::LCh::from_rgb(&[255, 0, 0]);
+    /// let blue = luv::LCh::from_rgb(&[0, 0, 255]);
+    /// let gradient = red.gradient(&blue, 5);
+    /// assert_eq!(5, gradient.len());
+    /// assert_eq!([255, 0, 0], gradient[0].to_rgb());
+    /// assert_eq!([0, 0, 255], gradient[4].to_rgb());
+    /// assert!(gradient.iter().all(luv::LCh::is_displayable));
+    /// ```
+    pub fn gradient(&self, other: &LCh, n: usize) -> Vec<LCh> {
+        let (from_h, to_h) = if self.c == 0.0 {
+            (other.h, other.h)
+        } else if other.c == 0.0 {
+            (self.h, self.h)
+        } else {
+            (self.h, other.h)
+        };
+        let delta = hue_delta(from_h, to_h);
+        let step = if n > 1 { 1.0 / (n - 1) as f32 } else { 0.0 };
+        (0..n)
+            .map(|i| {
+                let t = i as f32 * step;
+                let l = mul_add(other.l - self.l, t, self.l);
+                let c = mul_add(other.c - self.c, t, self.c);
+                let h = mul_add(delta, t, from_h);
+                let c = c.min(LCh::max_chroma(l, h));
+                LCh { l, c, h }
+            })
+            .collect()
+    }
+}
+
+
+/// Returns signed difference between two hues along the shorter arc.
+///
+/// The result is in (-π, π] range such that `from + hue_delta(from, to)` is
+/// equivalent to `to` modulo τ.
+fn hue_delta(from: f32, to: f32) -> f32 {
+    use std::f32::consts::{PI, TAU};
+    let delta = (to - from).rem_euclid(TAU);
+    if delta > PI {
+        delta - TAU
+    } else {
+        delta
+    }
+}
+
+
+/// Ranges and widths in bits of L\*, u\* and v\* in packed representation.
+/// See [`Luv::to_packed_u32`].
+const PACKED_RANGES: [(f32, f32, u32); 3] =
+    [(0.0, 100.0, 10), (-134.0, 224.0, 11), (-140.0, 122.0, 11)];
+
+/// Quantises value from given range to an unsigned integer of given width.
+fn pack_component(value: f32, (min, max, bits): (f32, f32, u32)) -> u32 {
+    let steps = ((1 << bits) - 1) as f32;
+    let value = (value.clamp(min, max) - min) / (max - min);
+    (value * steps).round() as u32
+}
+
+/// Converts the lowest `bits` of `packed` back into a value from given range.
+fn unpack_component(packed: u32, (min, max, bits): (f32, f32, u32)) -> f32 {
+    let steps = ((1 << bits) - 1) as f32;
+    let value = (packed & ((1 << bits) - 1)) as f32 / steps;
+    mul_add(value, max - min, min)
+}
+
+
+/// Trait abstracting over colour representations provided by this crate.
+///
+/// It allows writing code which works with either [`Luv`] or [`LCh`] colours,
+/// for example a function finding the colour in a palette closest to given
+/// sRGB value.
+///
+/// # Example
+///
+/// ```
+/// use luv::ColorSpace;
+///
+/// fn nearest<C: ColorSpace>(palette: &[[u8; 3]], rgb: &[u8; 3]) -> usize {
+///     let colour = C::from_rgb(rgb);
+///     let dist = |idx: &usize| {
+///         colour.squared_distance(&C::from_rgb(&palette[*idx]))
+///     };
+///     (0..palette.len())
+///         .min_by(|a, b| dist(a).partial_cmp(&dist(b)).unwrap())
+///         .unwrap()
+/// }
+///
+/// let palette = [[0, 0, 0], [255, 0, 0], [255, 255, 255]];
+/// assert_eq!(1, nearest::<luv::Luv>(&palette, &[200, 50, 40]));
+/// assert_eq!(1, nearest::<luv::LCh>(&palette, &[200, 50, 40]));
+/// ```
+pub trait ColorSpace: Sized + Copy {
+    /// Constructs a new colour from a three-element array of `u8`s.
+    fn from_rgb(rgb: &[u8; 3]) -> Self;
+
+    /// Constructs a new colour from a four-element array of `u8`s discarding
+    /// the alpha channel.
+    fn from_rgba(rgba: &[u8; 4]) -> Self { Self::from_rgb(subarray(rgba)) }
+
+    /// Returns the colour in RGB, in a 3-element array.
+    fn to_rgb(&self) -> [u8; 3];
+
+    /// Measures the perceptual distance between the colors of one `self` and
+    /// an `other`.
+    ///
+    /// The distance is squared Euclidean distance in L\*u\*v\* colour space
+    /// regardless of representation of the colour.
+    fn squared_distance(&self, other: &Self) -> f32;
+}
+
+impl ColorSpace for Luv {
+    fn from_rgb(rgb: &[u8; 3]) -> Self { Luv::from_rgb(rgb) }
+    fn to_rgb(&self) -> [u8; 3] { Luv::to_rgb(self) }
+    fn squared_distance(&self, other: &Self) -> f32 {
+        Luv::squared_distance(self, other)
+    }
+}
+
+impl ColorSpace for LCh {
+    fn from_rgb(rgb: &[u8; 3]) -> Self { LCh::from_rgb(rgb) }
+    fn to_rgb(&self) -> [u8; 3] { LCh::to_rgb(self) }
+    fn squared_distance(&self, other: &Self) -> f32 {
+        self.to_luv().squared_distance(&other.to_luv())
+    }
+}
+
+
+impl std::cmp::PartialEq<Luv> for Luv {
+    /// Compares two colours ignoring chromaticity if L\* is zero.
+    fn eq(&self, other: &Self) -> bool {
+        if self.l != other.l {
+            false
+        } else if self.l == 0.0 {
+            true
+        } else {
+            self.u == other.u && self.v == other.v
+        }
+    }
+}
+
+impl std::cmp::PartialEq<LCh> for LCh {
+    /// Compares two colours ignoring chromaticity if L\* is zero and hue if C\*
+    /// is zero.  Hues which are τ apart are compared equal.
+    fn eq(&self, other: &Self) -> bool {
+        if self.l != other.l {
+            false
+        } else if self.l == 0.0 {
+            true
+        } else if self.c != other.c {
+            false
+        } else if self.c == 0.0 {
+            true
+        } else {
+            use std::f32::consts::TAU;
+            self.h.rem_euclid(TAU) == other.h.rem_euclid(TAU)
+        }
+    }
+}
+
+impl std::cmp::PartialEq<LCh> for Luv {
+    /// Converts `self` to `LCh` and compares the two colours using rules of
+    /// `LCh` equality.
+    ///
+    /// Note that conversion between `Luv` and `LCh` isn’t exact.  Unless the
+    /// `LCh` has been obtained from the `Luv` (or the colour is grey), the two
+    /// are unlikely to compare equal even if they describe the same colour.
+    /// Convert and use comparison with a tolerance (e.g. the `approx` crate)
+    /// in such cases.
+    fn eq(&self, other: &LCh) -> bool { LCh::from_luv(*self) == *other }
+}
+
+impl std::cmp::PartialEq<Luv> for LCh {
+    /// Converts `other` to `LCh` and compares the two colours using rules of
+    /// `LCh` equality.  See `PartialEq<LCh> for Luv` for caveats.
+    fn eq(&self, other: &Luv) -> bool { *self == LCh::from_luv(*other) }
+}
+
+impl From<[u8; 3]> for Luv {
+    /// Constructs a new colour from sRGB components; see [`Luv::from_rgb`].
+    fn from(rgb: [u8; 3]) -> Self { Luv::from_rgb(&rgb) }
+}
+
+impl From<[u8; 4]> for Luv {
+    /// Constructs a new colour from sRGBA components.  The alpha channel is
+    /// ignored; see [`Luv::from_rgba`].  Use [`LuvA::from_rgba`] to preserve
+    /// it.
+    fn from(rgba: [u8; 4]) -> Self { Luv::from_rgba(&rgba) }
+}
+
+impl From<[u8; 3]> for LCh {
+    /// Constructs a new colour from sRGB components; see [`LCh::from_rgb`].
+    fn from(rgb: [u8; 3]) -> Self { LCh::from_rgb(&rgb) }
+}
+
+impl From<[u8; 4]> for LCh {
+    /// Constructs a new colour from sRGBA components.  The alpha channel is
+    /// ignored; see [`LCh::from_rgba`].
+    fn from(rgba: [u8; 4]) -> Self { LCh::from_rgba(&rgba) }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::{LCh, Luv};
+
+    struct Cases {
+        rgb: [[u8; 3]; 17],
+        xyz: [[f32; 3]; 17],
+        luv: [Luv; 17],
+        lch: [LCh; 17],
+    }
+
+    #[rustfmt::skip]
+    static CASES: Cases = Cases {
+        rgb: [
+            [253, 120, 138],
+            [127, 0, 0],
+            [0, 127, 0],
+            [0, 0, 127],
+            [0, 127, 127],
+            [127, 0, 127],
+            [255, 0, 0],
+            [0, 255, 0],
+            [0, 0, 255],
+            [0, 255, 255],
+            [255, 0, 255],
+            [255, 255, 0],
+            [0, 0, 0],
+            [64, 64, 64],
+            [127, 127, 127],
+            [196, 196, 196],
+            [255, 255, 255],
+        ],
+        xyz: [
+            [0.5181154, 0.3615437, 0.28291947],
+            [0.08752623, 0.045130707, 0.0041028075],
+            [0.075890444, 0.15178089, 0.025296815],
+            [0.038297843, 0.015319137, 0.201702],
+            [0.11418828, 0.16710001, 0.22699873],
+            [0.12582412, 0.06044987, 0.20580477],
+            [0.4124108, 0.21264932, 0.019331753],
+            [0.35758454, 0.71516913, 0.11919485],
+            [0.18045378, 0.072181515, 0.9503897],
+            [0.5380384, 0.7873506, 1.0695845],
+            [0.59286475, 0.28483093, 0.9697217],
+            [0.76999545, 0.92781854, 0.13852677],
+            [0.0, 0.0, 0.0],
+            [0.048729002, 0.051269446, 0.05582814],
+            [0.20171452, 0.21223073, 0.23110154],
+            [0.52465874, 0.5520114, 0.6010947],
+            [0.95044917, 1.0, 1.0889173]
+        ],
+        luv: [
+            Luv { l: 66.6377, u: 93.02939, v: 9.4303465 },
+            Luv { l: 25.299875, u: 83.16893, v: 17.94365 },
+            Luv { l: 45.87716, u: -43.437843, v: 56.162994 },
+            Luv { l: 12.809525, u: -3.7292066, v: -51.694946 },
+            Luv { l: 47.89254, u: -37.040905, v: -7.991562 },
+            Luv { l: 29.52568, u: 41.146084, v: -53.19984 },
+            Luv { l: 53.238235, u: 175.01141, v: 37.758636 },
+            Luv { l: 87.73554, u: -83.07059, v: 107.40619 },
+            Luv { l: 32.298466, u: -9.40297, v: -130.34576 },
+            Luv { l: 91.11428, u: -70.46933, v: -15.2037325 },
+            Luv { l: 60.32269, u: 84.06383, v: -108.690346 },
+            Luv { l: 97.139, u: 7.7040625, v: 106.79492 },
+            Luv { l: 0.0, u: 0.0, v: 0.0 },
+            Luv { l: 27.09341, u: 0.0000052484024, v: 0.000006560503 },
+            Luv { l: 53.192772, u: 0.000010304243, v: 0.000012880304 },
+            Luv { l: 79.15698, u: -0.000015333902, v: -0.000015333902 },
+            Luv { l: 100.0, u: 0.0, v: -0.00001937151 }
+        ],
+        lch: [
+            LCh { l: 66.6377, c: 93.50614, h: 0.10102446 },
+            LCh { l: 25.299875, c: 85.08258, h: 0.21249236 },
+            LCh { l: 45.87716, c: 71.0009, h: 2.2291214 },
+            LCh { l: 12.809525, c: 51.82928, h: -1.6428102 },
+            LCh { l: 47.89254, c: 37.89319, h: -2.9291 },
+            LCh { l: 29.52568, c: 67.25491, h: -0.91247106 },
+            LCh { l: 53.238235, c: 179.03828, h: 0.2124925 },
+            LCh { l: 87.73554, c: 135.78223, h: 2.2291214 },
+            LCh { l: 32.298466, c: 130.68448, h: -1.6428102 },
+            LCh { l: 91.11428, c: 72.090775, h: -2.9291 },
+            LCh { l: 60.32269, c: 137.40567, h: -0.91247106 },
+            LCh { l: 97.139, c: 107.07244, h: 1.4987823 },
+            LCh { l: 0.0, c: 0.0, h: 0.0 },
+            LCh { l: 27.09341, c: 0.000008401544, h: 0.8960554 },
+            LCh { l: 53.192772, c: 0.000016494838, h: 0.8960554 },
+            LCh { l: 79.15698, c: 0.000021685413, h: -2.3561945 },
+            LCh { l: 100.0, c: 0.00001937151, h: -1.5707964 }
+        ],
+    };
+
+    fn run_test<T, U>(want: &[T], f: impl Fn(&U) -> T, input: &[U])
+    where
+        T: PartialEq + std::fmt::Debug, {
+        let actual: Vec<_> = input.iter().map(f).collect();
+        assert_eq!(want, &actual[..]);
+    }
+
+    fn run_test_approx<T, U>(want: &[T], f: impl Fn(&U) -> T, input: &[U])
+    where
+        T: PartialEq + std::fmt::Debug + approx::RelativeEq<Epsilon = f32>,
+    {
+        let actual: Vec<_> = input.iter().map(f).collect();
+        approx::assert_abs_diff_eq!(want, &actual[..], epsilon = 0.0001);
+    }
+
+    #[test]
+    fn test_luv_from_xyz() {
+        run_test_approx(
+            &CASES.luv[..],
+            |xyz: &[f32; 3]| super::luv_from_xyz(*xyz),
+            &CASES.xyz[..],
+        );
+    }
+
+    #[test]
+    fn test_xyz_from_luv() {
+        run_test(
+            &CASES.xyz[..],
+            |luv: &Luv| super::xyz_from_luv(luv),
+            &CASES.luv[..],
+        );
+    }
+
+    #[test]
+    fn test_luv_from_rgb() {
+        run_test(&CASES.luv[..], Luv::from_rgb, &CASES.rgb[..]);
+    }
+
+    #[test]
+    fn test_rgb_from_luv() {
+        run_test(&CASES.rgb[..], Luv::to_rgb, &CASES.luv[..]);
+    }
+
+    #[test]
+    fn test_xyz_scaled() {
+        let luv = Luv::from_xyz_scaled(&[51.8, 36.2, 28.3], 100.0);
+        approx::assert_abs_diff_eq!(
+            Luv::from_xyz(&[0.518, 0.362, 0.283]),
+            luv,
+            epsilon = 0.0001
+        );
+
+        for (xyz, luv) in CASES.xyz.iter().zip(CASES.luv.iter()) {
+            let got = luv.to_xyz_scaled(100.0);
+            let want = xyz.map(|v| v * 100.0);
+            approx::assert_abs_diff_eq!(&want[..], &got[..], epsilon = 0.0001);
+            let got = Luv::from_xyz_scaled(&got, 100.0);
+            approx::assert_abs_diff_eq!(luv, &got, epsilon = 0.001);
+        }
+    }
+
+    #[test]
+    fn test_d65_constants() {
+        use srgb::xyz::D65_XYZ;
+
+        let white = Luv::from_xyz(&D65_XYZ);
+        approx::assert_abs_diff_eq!(super::D65_LUV, white, epsilon = 0.0001);
+        let white = Luv::from_rgb(&[255, 255, 255]);
+        approx::assert_abs_diff_eq!(super::D65_LUV, white, epsilon = 0.0001);
+
+        let sum = D65_XYZ[0] + 15.0 * D65_XYZ[1] + 3.0 * D65_XYZ[2];
+        assert_eq!(4.0 * D65_XYZ[0] / sum, super::D65_U_PRIME);
+        assert_eq!(9.0 * D65_XYZ[1] / sum, super::D65_V_PRIME);
+        approx::assert_abs_diff_eq!(0.1978, super::D65_U_PRIME, epsilon = 1e-4);
+        approx::assert_abs_diff_eq!(0.4683, super::D65_V_PRIME, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_lch_from_luv() {
+        run_test(
+            &CASES.lch[..],
+            |luv: &Luv| LCh::from_luv(*luv),
+            &CASES.luv[..],
+        );
+    }
+
+    #[test]
+    fn test_luv_from_lch() {
+        run_test_approx(&CASES.luv[..], LCh::to_luv, &CASES.lch[..]);
+    }
+
+    fn get_rgb_bytes() -> Vec<u8> {
+        CASES.rgb.iter().fold(
+            Vec::with_capacity(CASES.rgb.len() * 3),
+            |mut acc, rgb| {
+                acc.extend_from_slice(&rgb[..]);
+                acc
+            },
+        )
+    }
+
+    #[test]
+    fn test_rgbs_to_luvs() {
+        let got = super::rgbs_to_luvs(&CASES.rgb);
+        assert_eq!(&CASES.luv[..], &got[..]);
+    }
+
+    #[test]
+    fn test_rgb_bytes_to_luvs() {
+        let input = get_rgb_bytes();
+        let got = super::rgb_bytes_to_luvs(&input[..]);
+        assert_eq!(&CASES.luv[..], &got[..]);
+    }
+
+    #[test]
+    fn test_luvs_to_rgbs() {
+        let got = super::luvs_to_rgbs(&CASES.luv);
+        assert_eq!(&CASES.rgb[..], &got[..]);
+    }
+
+    #[test]
+    fn test_luvs_to_rgb_bytes() {
+        let want = get_rgb_bytes();
+        let got = super::luvs_to_rgb_bytes(&CASES.luv);
+        assert_eq!(&want[..], &got[..]);
+    }
+
+    #[test]
+    fn test_luvs_to_rgb_bytes_ordered() {
+        // A shallow grey gradient spanning only a few 8-bit levels.  Plain
+        // quantisation collapses it into a handful of flat bands while
+        // dithering makes average of each column follow the gradient.
+        const WIDTH: usize = 64;
+        let luvs: Vec<Luv> = (0..4 * WIDTH)
+            .map(|idx| {
+                let x = (idx % WIDTH) as f32 / WIDTH as f32;
+                let grey = 0.2 + x * 0.01;
+                super::luv_from_xyz(srgb::xyz::xyz_from_linear([grey; 3]))
+            })
+            .collect();
+
+        let column_sums = |bytes: Vec<u8>| {
+            let mut sums = [0u32; WIDTH];
+            for (idx, rgb) in bytes.chunks_exact(3).enumerate() {
+                sums[idx % WIDTH] += u32::from(rgb[1]);
+            }
+            let mut sums = sums.to_vec();
+            sums.dedup();
+            sums.len()
+        };
+
+        let plain = column_sums(super::luvs_to_rgb_bytes(&luvs));
+        let dithered =
+            column_sums(super::luvs_to_rgb_bytes_ordered(&luvs, WIDTH));
+        assert!(plain < dithered, "plain: {}, dithered: {}", plain, dithered);
+    }
+
+    fn check_color_space<C: super::ColorSpace + std::fmt::Debug>() {
+        for rgb in CASES.rgb.iter() {
+            let colour = C::from_rgb(rgb);
+            assert_eq!(*rgb, colour.to_rgb());
+            assert_eq!(0.0, colour.squared_distance(&colour));
+
+            let rgba = [rgb[0], rgb[1], rgb[2], 42];
+            assert_eq!(*rgb, C::from_rgba(&rgba).to_rgb());
+        }
+
+        let red = C::from_rgb(&[255, 0, 0]);
+        let pink = C::from_rgb(&[255, 0, 128]);
+        let cyan = C::from_rgb(&[0, 255, 255]);
+        assert!(red.squared_distance(&pink) < red.squared_distance(&cyan));
+    }
+
+    #[test]
+    fn test_color_space() {
+        check_color_space::<Luv>();
+        check_color_space::<LCh>();
+
+        let (a, b) = (CASES.lch[0], CASES.lch[6]);
+        approx::assert_abs_diff_eq!(
+            a.to_luv().squared_distance(&b.to_luv()),
+            super::ColorSpace::squared_distance(&a, &b)
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_blend_preserving_chroma() {
+        use std::f32::consts::PI;
+
+        let a = LCh { l: 40.0, c: 80.0, h: 0.5 };
+        let b = LCh { l: 60.0, c: 40.0, h: 1.5 };
+        let got = a.blend_preserving_chroma(&b, 0.5);
+        assert_eq!(LCh { l: 50.0, c: 80.0, h: 1.0 }, got);
+        assert_ne!((a.c + b.c) / 2.0, got.c);
+
+        // Hue goes along the shorter arc through ±π.
+        let a = LCh { l: 50.0, c: 50.0, h: PI - 0.25 };
+        let b = LCh { l: 50.0, c: 70.0, h: -PI + 0.25 };
+        let got = a.blend_preserving_chroma(&b, 0.5);
+        approx::assert_abs_diff_eq!(
+            LCh { l: 50.0, c: 70.0, h: PI },
+            got,
+            epsilon = 1e-6
+        );
+
+        // Hue of achromatic colour is ignored.
+        let grey = LCh { l: 50.0, c: 0.0, h: 2.0 };
+        let got = grey.blend_preserving_chroma(&b, 0.5);
+        assert_eq!(LCh { l: 50.0, c: 70.0, h: b.h }, got);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_cylindrical_distance() {
+        let nearest = |target: &LCh, colours: &[LCh], w_h: f32| {
+            let dist = |c: &&LCh| target.cylindrical_distance(c, 1.0, 1.0, w_h);
+            let best = colours
+                .iter()
+                .min_by(|a, b| dist(a).partial_cmp(&dist(b)).unwrap());
+            best.copied().unwrap()
+        };
+
+        let target = LCh { l: 50.0, c: 50.0, h: 0.0 };
+        let other_hue = LCh { l: 50.0, c: 50.0, h: 0.6 };
+        let other_lightness = LCh { l: 85.0, c: 50.0, h: 0.0 };
+        let colours = [other_hue, other_lightness];
+
+        // With unit weights the distance is the same as in L*u*v* space.
+        for colour in colours.iter() {
+            approx::assert_abs_diff_eq!(
+                target.to_luv().squared_distance(&colour.to_luv()).sqrt(),
+                target.cylindrical_distance(colour, 1.0, 1.0, 1.0),
+                epsilon = 0.0001
+            );
+        }
+        assert_eq!(other_hue, nearest(&target, &colours, 1.0));
+        // Emphasising hue prefers colour with matching hue.
+        assert_eq!(other_lightness, nearest(&target, &colours, 2.0));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_is_displayable() {
+        assert!(CASES.luv.iter().all(Luv::is_displayable));
+        assert!(CASES.luv.iter().all(|luv| luv.round_trips_to_self(0.5)));
+
+        for luv in [
+            Luv { l: 53.24, u: 200.0, v: 37.76 },
+            Luv { l: 90.0, u: -100.0, v: -100.0 },
+            Luv { l: 20.0, u: 0.0, v: 80.0 },
+            Luv { l: 110.0, u: 0.0, v: 0.0 },
+        ]
+        .iter()
+        {
+            assert!(!luv.is_displayable(), "{:?}", luv);
+            assert!(!luv.round_trips_to_self(1.0), "{:?}", luv);
+        }
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_max_chroma() {
+        assert_eq!(0.0, LCh::max_chroma(0.0, 1.0));
+        assert_eq!(0.0, LCh::max_chroma(100.0, 1.0));
+        for lch in CASES.lch.iter().filter(|lch| lch.l > 0.0 && lch.l < 100.0) {
+            let c = LCh::max_chroma(lch.l, lch.h);
+            assert!(lch.c <= c + 0.001, "{:?} {}", lch, c);
+            assert!(LCh { c, ..*lch }.is_displayable());
+            assert!(!LCh { c: c + 0.002, ..*lch }.is_displayable());
+        }
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_rotate_hue_in_gamut() {
+        let yellow = LCh::from_rgb(&[255, 255, 0]);
+        let max_chroma = LCh::max_chroma(yellow.l, yellow.h);
+        let yellow = LCh { c: max_chroma, ..yellow };
+        assert!(yellow.is_displayable());
+
+        for &radians in [1.0, 2.0, 3.0, -1.0, -2.0].iter() {
+            assert!(!yellow.rotate_hue(radians).is_displayable());
+            let rotated = yellow.rotate_hue_in_gamut(radians);
+            assert!(rotated.is_displayable(), "{:?}", rotated);
+            assert!(rotated.c < yellow.c);
+            assert_eq!(yellow.l, rotated.l);
+            assert_eq!(yellow.h + radians, rotated.h);
+        }
+
+        // Colours which stay in gamut are unchanged.
+        let grey = LCh { l: 50.0, c: 10.0, h: 0.0 };
+        assert_eq!(grey.rotate_hue(2.0), grey.rotate_hue_in_gamut(2.0));
+    }
+
+    #[test]
+    fn test_extremes() {
+        let (dark, light) = super::lightness_extremes(&CASES.luv).unwrap();
+        assert_eq!([0, 0, 0], dark.to_rgb());
+        assert_eq!([255, 255, 255], light.to_rgb());
+
+        let (grey, vivid) = super::chroma_extremes(&CASES.luv).unwrap();
+        assert_eq!([0, 0, 0], grey.to_rgb());
+        assert_eq!([255, 0, 0], vivid.to_rgb());
+
+        assert_eq!(None, super::lightness_extremes(&[]));
+        assert_eq!(None, super::chroma_extremes(&[]));
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_normalized() {
+        use std::f32::consts::PI;
+
+        let lch = LCh { l: 50.0, c: -10.0, h: 0.0 };
+        assert_eq!(LCh { l: 50.0, c: 10.0, h: PI }, lch.normalized());
+        let lch = LCh { l: 50.0, c: -10.0, h: 0.5 };
+        assert_eq!(LCh { l: 50.0, c: 10.0, h: 0.5 - PI }, lch.normalized());
+        let lch = LCh { l: 50.0, c: 10.0, h: -PI };
+        assert_eq!(LCh { l: 50.0, c: 10.0, h: PI }, lch.normalized());
+
+        for lch in CASES.lch.iter() {
+            assert_eq!(*lch, lch.normalized());
+            let flipped = LCh { l: lch.l, c: -lch.c, h: lch.h + PI };
+            approx::assert_abs_diff_eq!(
+                lch.to_luv(), flipped.normalized().to_luv(), epsilon = 0.001);
+        }
+    }
+
+    #[test]
+    fn test_invert_lightness() {
+        let accent = Luv::from_rgb(&[250, 180, 200]);
+        let dark = accent.invert_lightness();
+        approx::assert_abs_diff_eq!(100.0 - accent.l, dark.l);
+        let (accent, dark) = (LCh::from_luv(accent), LCh::from_luv(dark));
+        approx::assert_abs_diff_eq!(accent.h, dark.h, epsilon = 0.0001);
+        assert!(dark.c < accent.c);
+        assert!(dark.to_luv().is_displayable());
+
+        let dark = accent.invert_lightness();
+        approx::assert_abs_diff_eq!(100.0 - accent.l, dark.l);
+        assert_eq!((accent.c, accent.h), (dark.c, dark.h));
+
+        assert_eq!(super::D65_LUV, Luv::default().invert_lightness());
+        let luv = Luv::from_rgb(&[0, 128, 64]);
+        approx::assert_abs_diff_eq!(
+            luv,
+            luv.invert_lightness().invert_lightness(),
+            epsilon = 0.001
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_luv_lch_eq() {
+        for luv in CASES.luv.iter() {
+            let lch = LCh::from_luv(*luv);
+            assert_eq!(*luv, lch);
+            assert_eq!(lch, *luv);
+        }
+
+        let grey = Luv { l: 50.0, u: 0.0, v: 0.0 };
+        assert_eq!(grey, LCh { l: 50.0, c: 0.0, h: 1.0 });
+        assert_eq!(LCh { l: 50.0, c: 0.0, h: 1.0 }, grey);
+
+        let luv = Luv::from_rgb(&[240, 33, 95]);
+        let lch = LCh::from_rgb(&[240, 33, 96]);
+        assert_ne!(luv, lch);
+        assert_ne!(lch, luv);
+    }
+
+    #[test]
+    fn test_lchs_from_rgb_bytes() {
+        let bytes = CASES.rgb.iter().flatten().copied().collect::<Vec<u8>>();
+        let want = super::rgb_bytes_to_lchs(&bytes);
+        assert_eq!(&CASES.lch[..], &want[..]);
+        let got = super::lchs_from_rgb_bytes(&bytes).collect::<Vec<_>>();
+        assert_eq!(want, got);
+
+        let got = super::lchs_from_rgb_bytes(&bytes[..bytes.len() - 1]);
+        assert_eq!(&want[..want.len() - 1], &got.collect::<Vec<_>>()[..]);
+    }
+
+    #[test]
+    fn test_luvs_from_rgba_bytes() {
+        let mut bytes = Vec::with_capacity(CASES.rgb.len() * 4);
+        for (i, rgb) in CASES.rgb.iter().enumerate() {
+            bytes.extend_from_slice(rgb);
+            bytes.push((i * 15) as u8);
+        }
+        let got = super::luvs_from_rgba_bytes(&bytes).collect::<Vec<_>>();
+        assert_eq!(&CASES.luv[..], &got[..]);
+        assert_eq!(super::rgbs_to_luvs(&CASES.rgb), got);
+
+        for trailing in 1..4 {
+            let bytes = &bytes[..bytes.len() - trailing];
+            let got = super::luvs_from_rgba_bytes(bytes).collect::<Vec<_>>();
+            assert_eq!(&CASES.luv[..CASES.luv.len() - 1], &got[..]);
+        }
+        assert_eq!(0, super::luvs_from_rgba_bytes(&[1, 2, 3]).count());
+    }
+
+    #[test]
+    fn test_clamp_chroma() {
+        let red = Luv::from_rgb(&[255, 0, 0]);
+        let clamped = red.clamp_chroma(50.0);
+        let (red, clamped) = (LCh::from_luv(red), LCh::from_luv(clamped));
+        approx::assert_abs_diff_eq!(50.0, clamped.c, epsilon = 0.0001);
+        approx::assert_abs_diff_eq!(red.h, clamped.h, epsilon = 0.0001);
+        assert_eq!(red.l, clamped.l);
+
+        let grey = red.to_luv().clamp_chroma(-1.0);
+        assert_eq!((red.l, 0.0, 0.0), (grey.l, grey.u, grey.v));
+
+        let grey = Luv::from_array_ref(&[50.0, 0.0, 0.0]);
+        for max_chroma in [-1.0, 0.0] {
+            let got = grey.clamp_chroma(max_chroma);
+            assert_eq!((50.0, 0.0, 0.0), (got.l, got.u, got.v));
+        }
+    }
+
+    #[test]
+    fn test_to_rgb_with_rounding() {
+        use super::Rounding;
+
+        let rgb = [100.6 / 255.0, 50.25 / 255.0, 200.25 / 255.0];
+        let luv = Luv::from_rgb_normalized(&rgb);
+        assert_eq!([101, 50, 200], luv.to_rgb());
+        let got = |mode| luv.to_rgb_with_rounding(mode);
+        assert_eq!([101, 50, 200], got(Rounding::Nearest));
+        assert_eq!([100, 50, 200], got(Rounding::Floor));
+        assert_eq!([101, 51, 201], got(Rounding::Ceil));
+        assert_eq!([100, 50, 200], got(Rounding::TruncateTowardZero));
+
+        for luv in CASES.luv.iter() {
+            let want = luv.to_rgb();
+            assert_eq!(want, luv.to_rgb_with_rounding(Rounding::default()));
+        }
+    }
+
+    #[test]
+    fn test_with_max_chroma() {
+        for lch in CASES.lch.iter().filter(|lch| lch.l > 0.0 && lch.l < 100.0) {
+            let got = lch.with_max_chroma();
+            assert_eq!((lch.l, lch.h), (got.l, got.h));
+            assert!(got.is_displayable(), "{:?}", got);
+            let mut bumped = got;
+            bumped.c += 0.01;
+            assert!(!bumped.is_displayable(), "{:?}", bumped);
+        }
+    }
+
+    #[test]
+    fn test_into_conversions() {
+        let mut luv = Luv::default();
+        let mut lch = LCh::default();
+        for (want_luv, want_lch) in CASES.luv.iter().zip(CASES.lch.iter()) {
+            want_luv.to_lch_into(&mut lch);
+            assert_eq!(LCh::from_luv(*want_luv), lch);
+            want_lch.to_luv_into(&mut luv);
+            assert_eq!(want_lch.to_luv(), luv);
+        }
+    }
+
+    #[test]
+    fn test_tonal_ramp() {
+        assert_eq!(Vec::<LCh>::new(), CASES.lch[0].tonal_ramp(0));
+        for lch in CASES.lch.iter() {
+            let ramp = lch.tonal_ramp(11);
+            assert_eq!(11, ramp.len());
+            assert_eq!((0.0, 100.0), (ramp[0].l, ramp[10].l));
+            for pair in ramp.windows(2) {
+                assert!(pair[0].l < pair[1].l, "{:?}", pair);
+            }
+            for shade in ramp.iter() {
+                assert!(shade.is_displayable(), "{:?}", shade);
+                assert!(shade.c <= lch.c);
+            }
+        }
+
+        let ramp = CASES.lch[0].tonal_ramp_between(1, 30.0, 70.0);
+        assert_eq!(1, ramp.len());
+        assert_eq!(30.0, ramp[0].l);
+    }
+
+    #[test]
+    fn test_rgb16_bytes_to_luvs() {
+        use super::Endianness;
+
+        let rgb16 = [[0x1234u16, 0xABCD, 0x0F0F], [0, 0xFFFF, 0x8000]];
+        let mut be = Vec::new();
+        let mut le = Vec::new();
+        for &c in rgb16.iter().flatten() {
+            be.extend_from_slice(&c.to_be_bytes());
+            le.extend_from_slice(&c.to_le_bytes());
+        }
+        let want = rgb16.iter().map(Luv::from_rgb16).collect::<Vec<_>>();
+        assert_eq!(want, super::rgb16_bytes_to_luvs(&be, Endianness::Big));
+        assert_eq!(want, super::rgb16_bytes_to_luvs(&le, Endianness::Little));
+        let got = super::rgb16_bytes_to_luvs(&be[..11], Endianness::Big);
+        assert_eq!(&want[..1], &got[..]);
+
+        for rgb in CASES.rgb.iter() {
+            let rgb16 = [
+                rgb[0] as u16 * 257,
+                rgb[1] as u16 * 257,
+                rgb[2] as u16 * 257,
+            ];
+            let got = Luv::from_rgb16(&rgb16);
+            approx::assert_abs_diff_eq!(
+                Luv::from_rgb(rgb),
+                got,
+                epsilon = 0.001
+            );
+        }
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_hue_arc_length() {
+        use std::f32::consts::{PI, TAU};
+
+        let a = LCh { l: 50.0, c: 20.0, h: 0.1 };
+        let b = LCh { l: 50.0, c: 20.0, h: 0.1 + PI - 0.01 };
+        approx::assert_abs_diff_eq!(PI - 0.01, a.hue_arc_length(&b), epsilon = 1e-5);
+        approx::assert_abs_diff_eq!(PI - 0.01, b.hue_arc_length(&a), epsilon = 1e-5);
+
+        let b = LCh { l: 50.0, c: 20.0, h: 0.1 + PI + 0.01 - TAU };
+        approx::assert_abs_diff_eq!(PI - 0.01, a.hue_arc_length(&b), epsilon = 1e-5);
+        approx::assert_abs_diff_eq!(-(PI - 0.01), a.shortest_hue_delta(&b), epsilon = 1e-5);
+
+        assert_eq!(0.0, a.hue_arc_length(&a));
+        for lch in CASES.lch.iter() {
+            let arc = CASES.lch[0].hue_arc_length(lch);
+            assert!((0.0..=PI).contains(&arc), "{}", arc);
+        }
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_clamp_ranges() {
+        let ranges = ((10.0, 90.0), (-20.0, 20.0), (0.0, 5.0));
+        let clamp = |luv: Luv| luv.clamp_ranges(ranges.0, ranges.1, ranges.2);
+
+        let luv = Luv { l: 5.0, u: 30.0, v: -1.0 };
+        assert_eq!(Luv { l: 10.0, u: 20.0, v: 0.0 }, clamp(luv));
+        let luv = Luv { l: 95.0, u: -30.0, v: 6.0 };
+        assert_eq!(Luv { l: 90.0, u: -20.0, v: 5.0 }, clamp(luv));
+        let luv = Luv { l: 50.0, u: 1.0, v: 2.0 };
+        assert_eq!(luv, clamp(luv));
+    }
+
+    #[test]
+    fn test_reference_constants() {
+        assert_eq!([255, 255, 255], Luv::WHITE.to_rgb());
+        assert_eq!([0, 0, 0], Luv::BLACK.to_rgb());
+        assert_eq!([119, 119, 119], Luv::MIDDLE_GREY.to_rgb());
+        assert_eq!([255, 255, 255], LCh::WHITE.to_rgb());
+        assert_eq!([0, 0, 0], LCh::BLACK.to_rgb());
+        assert_eq!([119, 119, 119], LCh::MIDDLE_GREY.to_rgb());
+
+        assert_eq!(Luv::default(), Luv::BLACK);
+        assert_eq!(LCh::default(), LCh::BLACK);
+        assert_eq!(LCh::from_luv(Luv::WHITE), LCh::WHITE);
+        assert_eq!(LCh::from_luv(Luv::MIDDLE_GREY), LCh::MIDDLE_GREY);
+    }
+
+    #[test]
+    fn test_fade_to_grey() {
+        for luv in CASES.luv.iter() {
+            let grey = luv.fade_to_grey(1.0);
+            assert_eq!((luv.l, 0.0, 0.0), (grey.l, grey.u, grey.v));
+            assert_eq!(*luv, luv.fade_to_grey(0.0));
+
+            let half = LCh::from_luv(luv.fade_to_grey(0.5));
+            let lch = LCh::from_luv(*luv);
+            approx::assert_abs_diff_eq!(lch.c * 0.5, half.c, epsilon = 0.001);
+            if lch.c > 0.0 {
+                approx::assert_abs_diff_eq!(lch.h, half.h, epsilon = 0.001);
+            }
+        }
+    }
+
+    #[test]
+    fn test_adjust_for_contrast() {
+        let background = Luv::MIDDLE_GREY;
+        for luv in CASES.luv.iter() {
+            let got = luv.adjust_for_contrast(&background, 4.5);
+            let ratio = got.contrast_ratio(&background);
+            assert!(ratio >= 4.5, "{:?} → {:?}: {}", luv, got, ratio);
+            assert!(ratio < 4.6 || *luv == got, "{:?} → {:?}", luv, got);
+            assert!(got.is_displayable(), "{:?} → {:?}", luv, got);
+        }
+
+        // Mid-grey has slightly better contrast against black so text gets
+        // darkened.
+        let text = Luv::from_rgb(&[40, 90, 200]);
+        let got = text.adjust_for_contrast(&background, 4.5);
+        assert!(got.l < text.l);
+
+        // Unreachable ratio gives black or white.
+        let got = text.adjust_for_contrast(&background, 10.0);
+        assert_eq!([0, 0, 0], got.to_rgb());
+        let light = Luv::from_rgb(&[200, 200, 200]);
+        let got = text.adjust_for_contrast(&light, 30.0);
+        assert_eq!([0, 0, 0], got.to_rgb());
+        let dark = Luv::from_rgb(&[30, 30, 30]);
+        let got = text.adjust_for_contrast(&dark, 30.0);
+        assert_eq!([255, 255, 255], got.to_rgb());
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_to_rgb_with_error() {
+        for (rgb, luv) in CASES.rgb.iter().zip(CASES.luv.iter()) {
+            let (got, error) = luv.to_rgb_with_error();
+            assert_eq!(*rgb, got);
+            assert!(error.iter().all(|e| e.abs() < 1e-5), "{:?}", error);
+        }
+
+        let blue = Luv { l: 50.0, u: -9.6, v: -133.0 };
+        let (rgb, error) = blue.to_rgb_with_error();
+        assert_eq!(blue.to_rgb(), rgb);
+        assert_eq!([0.0, 0.0], [error[0], error[1]]);
+        assert!(error[2] > 0.05, "{:?}", error);
+
+        let (rgb, error) = Luv { l: 60.0, u: 250.0, v: 0.0 }.to_rgb_with_error();
+        assert_eq!([255, 0], [rgb[0], rgb[1]]);
+        assert!(error[0] > 0.0, "{:?}", error);
+        assert!(error[1] < 0.0, "{:?}", error);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_from_lch_degrees() {
+        for lch in CASES.lch.iter() {
+            let got = Luv::from_lch_degrees(lch.l, lch.c, lch.h.to_degrees());
+            approx::assert_abs_diff_eq!(lch.to_luv(), got, epsilon = 0.001);
+        }
+        let got = Luv::from_lch_degrees(50.0, 10.0, 180.0);
+        let want = Luv { l: 50.0, u: -10.0, v: 0.0 };
+        approx::assert_abs_diff_eq!(want, got, epsilon = 0.0001);
+        let got = Luv::from_lch_degrees(50.0, 10.0, -90.0);
+        let want = Luv { l: 50.0, u: 0.0, v: -10.0 };
+        approx::assert_abs_diff_eq!(want, got, epsilon = 0.0001);
+    }
+
+    #[test]
+    fn test_layout() {
+        use std::mem::{align_of, size_of};
+
+        fn offsets<T>(obj: &T, fields: [*const f32; 3]) -> [usize; 3] {
+            let base = obj as *const T as usize;
+            [0, 1, 2].map(|i| fields[i] as usize - base)
+        }
+
+        assert_eq!(12, size_of::<Luv>());
+        assert_eq!(align_of::<f32>(), align_of::<Luv>());
+        let luv = Luv::default();
+        assert_eq!([0, 4, 8], offsets(&luv, [&luv.l, &luv.u, &luv.v]));
+
+        assert_eq!(12, size_of::<LCh>());
+        assert_eq!(align_of::<f32>(), align_of::<LCh>());
+        let lch = LCh::default();
+        assert_eq!([0, 4, 8], offsets(&lch, [&lch.l, &lch.c, &lch.h]));
+
+        for luv in CASES.luv.iter() {
+            assert_eq!(&[luv.l, luv.u, luv.v], luv.as_array());
+            assert_eq!(luv, Luv::from_array_ref(luv.as_array()));
+        }
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_gradient() {
+        let a = LCh { l: 40.0, c: 20.0, h: 3.0 };
+        let b = LCh { l: 60.0, c: 30.0, h: -3.0 };
+        assert_eq!(Vec::<LCh>::new(), a.gradient(&b, 0));
+        assert_eq!(vec![a], a.gradient(&b, 1));
+
+        let gradient = a.gradient(&b, 9);
+        assert_eq!(9, gradient.len());
+        approx::assert_abs_diff_eq!(a, gradient[0]);
+        approx::assert_abs_diff_eq!(b, gradient[8], epsilon = 1e-5);
+        let arc = a.hue_arc_length(&b);
+        for (i, lch) in gradient.iter().enumerate() {
+            // All hues must lie on the short arc across ±π.
+            let sum = a.hue_arc_length(lch) + lch.hue_arc_length(&b);
+            assert!((sum - arc).abs() < 1e-5, "{}: {:?}", i, lch);
+            assert!(lch.is_displayable(), "{}: {:?}", i, lch);
+            let t = i as f32 / 8.0;
+            assert!((lch.l - (40.0 + 20.0 * t)).abs() < 1e-4, "{:?}", lch);
+            assert!((lch.c - (20.0 + 10.0 * t)).abs() < 1e-4, "{:?}", lch);
+        }
+
+        // Chroma is clamped to keep colours displayable.
+        let yellow = LCh::from_rgb(&[255, 255, 0]);
+        let blue = LCh::from_rgb(&[0, 0, 255]);
+        let gradient = yellow.gradient(&blue, 16);
+        assert!(gradient.iter().all(LCh::is_displayable), "{:?}", gradient);
+
+        // Achromatic endpoint takes hue of the other colour.
+        let grey = LCh { l: 50.0, c: 0.0, h: 0.0 };
+        let gradient = grey.gradient(&a, 5);
+        assert!(gradient.iter().all(|lch| lch.h == a.h), "{:?}", gradient);
+    }
+
+    #[test]
+    fn test_posterize_lightness() {
+        let bands = [0.0, 100.0 / 3.0, 200.0 / 3.0, 100.0];
+        for luv in CASES.luv.iter() {
+            assert_eq!(*luv, luv.posterize_lightness(0));
+            assert_eq!(*luv, luv.posterize_lightness(1));
+
+            let got = luv.posterize_lightness(4);
+            let want = bands
+                .iter()
+                .min_by(|a, b| {
+                    (*a - luv.l).abs().partial_cmp(&(*b - luv.l).abs()).unwrap()
+                })
+                .unwrap();
+            assert!((got.l - want).abs() < 1e-4, "{:?} → {:?}", luv, got);
+            if got.l > 0.0 {
+                let (a, b) = (LCh::from_luv(*luv), LCh::from_luv(got));
+                assert!(a.hue_arc_length(&b) < 1e-4, "{:?} → {:?}", a, b);
+                assert_eq!(got.l, got.posterize_lightness(4).l);
+            } else {
+                assert_eq!(Luv::BLACK, got);
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_arrays() {
+        for rgb in CASES.rgb.iter() {
+            let rgba = [rgb[0], rgb[1], rgb[2], 42];
+            assert_eq!(Luv::from_rgb(rgb), Luv::from(*rgb));
+            assert_eq!(Luv::from_rgba(&rgba), Luv::from(rgba));
+            assert_eq!(LCh::from_rgb(rgb), LCh::from(*rgb));
+            assert_eq!(LCh::from_rgba(&rgba), LCh::from(rgba));
+        }
+
+        let luvs: Vec<Luv> = CASES.rgb.iter().copied().map(Luv::from).collect();
+        assert_eq!(super::rgbs_to_luvs(&CASES.rgb), luvs);
+    }
+
+    #[test]
+    fn test_saturation() {
+        // Scaling linear sRGB keeps the chromaticity so dark and light
+        // versions of a primary have the same saturation even though their
+        // chroma differs a lot.
+        for (light, dark) in [
+            ([0, 0, 255], [0, 0, 100]),
+            ([255, 0, 0], [60, 0, 0]),
+            ([255, 255, 0], [128, 128, 0]),
+        ] {
+            let (light, dark) = (Luv::from_rgb(&light), Luv::from_rgb(&dark));
+            let (a, b) = (light.saturation(), dark.saturation());
+            assert!((a - b).abs() < 0.01 * a, "{} vs {}", a, b);
+            let (a, b) = (LCh::from_luv(light).c, LCh::from_luv(dark).c);
+            assert!((a - b).abs() > 0.2 * a, "{} vs {}", a, b);
+        }
+
+        assert_eq!(0.0, Luv::BLACK.saturation());
+        assert_eq!(0.0, Luv::MIDDLE_GREY.saturation());
+        let blue = Luv::from_rgb(&[0, 0, 255]);
+        let green = Luv::from_rgb(&[0, 255, 0]);
+        assert!(blue.saturation() > green.saturation());
+    }
+
+    #[test]
+    fn test_map_rgb() {
+        let lighten = |luv: Luv| Luv {
+            l: (luv.l + 10.0).min(100.0),
+            ..luv
+        };
+        let sum = |rgb: &[u8; 3]| rgb.iter().map(|&c| c as u32).sum::<u32>();
+        for rgb in CASES.rgb.iter() {
+            assert_eq!(*rgb, super::map_rgb(rgb, |luv| luv));
+            let lighter = super::map_rgb(rgb, lighten);
+            assert!(
+                *rgb == [255, 255, 255] || sum(&lighter) > sum(rgb),
+                "{:?} → {:?}",
+                rgb,
+                lighter
+            );
         }
+
+        // Out of gamut result gets clamped.
+        let red = [255, 0, 0];
+        let saturate = |luv: Luv| Luv {
+            u: luv.u * 2.0,
+            ..luv
+        };
+        assert_eq!(red, super::map_rgb(&red, saturate));
     }
-}
 
+    #[test]
+    fn test_to_rgb_gamma_approximation() {
+        fn exact(c: f32) -> u8 {
+            let c = c.clamp(0.0, 1.0) as f64;
+            let s = if c <= 0.0031308 {
+                12.92 * c
+            } else {
+                1.055 * c.powf(1.0 / 2.4) - 0.055
+            };
+            (s * 255.0 + 0.5) as u8
+        }
 
-impl std::cmp::PartialEq<Luv> for Luv {
-    /// Compares two colours ignoring chromaticity if L\* is zero.
-    fn eq(&self, other: &Self) -> bool {
-        if self.l != other.l {
-            false
-        } else if self.l == 0.0 {
-            true
-        } else {
-            self.u == other.u && self.v == other.v
+        // Shift lightness a little so the colours don’t land exactly on
+        // 8-bit values.
+        let steps = || (0..=255).step_by(5);
+        let luvs: Vec<Luv> = steps()
+            .flat_map(|r| {
+                steps().flat_map(move |g| steps().map(move |b| [r, g, b]))
+            })
+            .map(|rgb| {
+                let mut luv = Luv::from_rgb(&rgb);
+                luv.l += 0.37;
+                luv
+            })
+            .collect();
+        let got = super::luvs_to_rgb_bytes(&luvs);
+        let want = luvs.iter().flat_map(|luv| {
+            srgb::xyz::linear_from_xyz(luv.to_xyz()).map(exact)
+        });
+
+        let (mut count, mut mismatches) = (0, 0);
+        for (got, want) in got.into_iter().zip(want) {
+            assert!((got as i32 - want as i32).abs() <= 1, "{} {}", got, want);
+            mismatches += (got != want) as u32;
+            count += 1;
         }
+        assert!(mismatches * 100 < count, "{} / {}", mismatches, count);
     }
-}
 
-impl std::cmp::PartialEq<LCh> for LCh {
-    /// Compares two colours ignoring chromaticity if L\* is zero and hue if C\*
-    /// is zero.  Hues which are τ apart are compared equal.
-    fn eq(&self, other: &Self) -> bool {
-        if self.l != other.l {
-            false
-        } else if self.l == 0.0 {
-            true
-        } else if self.c != other.c {
-            false
-        } else if self.c == 0.0 {
-            true
-        } else {
-            use std::f32::consts::TAU;
-            self.h.rem_euclid(TAU) == other.h.rem_euclid(TAU)
+    #[test]
+    fn test_grey_bytes_to_luvs() {
+        let bytes: Vec<u8> = (0..=255).chain((0..=255).rev()).collect();
+        for len in [0, 1, 3, 256, 257, bytes.len()] {
+            let bytes = &bytes[..len];
+            let tripled: Vec<u8> =
+                bytes.iter().flat_map(|&b| [b, b, b]).collect();
+            let want = super::rgb_bytes_to_luvs(&tripled);
+            assert_eq!(want, super::grey_bytes_to_luvs(bytes));
         }
     }
-}
 
+    #[test]
+    fn test_with_relative_luminance() {
+        for luv in CASES.luv.iter() {
+            for &y in &[0.0, 0.004, 0.01, 0.18, 0.5, 0.9, 1.0] {
+                let got = luv.with_relative_luminance(y);
+                let lum = got.relative_luminance();
+                assert!((lum - y).abs() < 1e-5, "{:?} {} {}", luv, y, lum);
+                if luv.l > 0.0 && y > 0.0 {
+                    let (a, b) = (LCh::from_luv(*luv), LCh::from_luv(got));
+                    assert!(a.c < 1e-3 || a.hue_arc_length(&b) < 1e-4);
+                    let want = a.c / a.l * b.l;
+                    assert!((b.c - want).abs() < 1e-3, "{:?} {:?}", a, b);
+                }
+            }
+        }
+        assert_eq!(Luv::WHITE, Luv::BLACK.with_relative_luminance(1.0));
+        assert_eq!(Luv::WHITE, Luv::MIDDLE_GREY.with_relative_luminance(2.0));
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::{LCh, Luv};
+    #[test]
+    fn test_neutral() {
+        for (luv, lch) in CASES.luv.iter().zip(CASES.lch.iter()) {
+            let grey = luv.neutral();
+            assert_eq!(luv.l, grey.l);
+            assert_eq!(0.0, LCh::from_luv(grey).c);
+            assert_eq!(grey, grey.neutral());
 
-    struct Cases {
-        rgb: [[u8; 3]; 17],
-        xyz: [[f32; 3]; 17],
-        luv: [Luv; 17],
-        lch: [LCh; 17],
+            let grey = lch.neutral();
+            assert_eq!(lch.l, grey.l);
+            assert_eq!(0.0, grey.c);
+            assert_eq!(luv.neutral(), grey.to_luv());
+        }
     }
 
-    #[rustfmt::skip]
-    static CASES: Cases = Cases {
-        rgb: [
-            [253, 120, 138],
-            [127, 0, 0],
-            [0, 127, 0],
-            [0, 0, 127],
-            [0, 127, 127],
-            [127, 0, 127],
-            [255, 0, 0],
-            [0, 255, 0],
-            [0, 0, 255],
-            [0, 255, 255],
-            [255, 0, 255],
-            [255, 255, 0],
-            [0, 0, 0],
-            [64, 64, 64],
-            [127, 127, 127],
-            [196, 196, 196],
-            [255, 255, 255],
-        ],
-        xyz: [
-            [0.5181154, 0.3615437, 0.28291947],
-            [0.08752623, 0.045130707, 0.0041028075],
-            [0.075890444, 0.15178089, 0.025296815],
-            [0.038297843, 0.015319137, 0.201702],
-            [0.11418828, 0.16710001, 0.22699873],
-            [0.12582412, 0.06044987, 0.20580477],
-            [0.4124108, 0.21264932, 0.019331753],
-            [0.35758454, 0.71516913, 0.11919485],
-            [0.18045378, 0.072181515, 0.9503897],
-            [0.5380384, 0.7873506, 1.0695845],
-            [0.59286475, 0.28483093, 0.9697217],
-            [0.76999545, 0.92781854, 0.13852677],
-            [0.0, 0.0, 0.0],
-            [0.048729002, 0.051269446, 0.05582814],
-            [0.20171452, 0.21223073, 0.23110154],
-            [0.52465874, 0.5520114, 0.6010947],
-            [0.95044917, 1.0, 1.0889173]
-        ],
-        luv: [
-            Luv { l: 66.6377, u: 93.02939, v: 9.4303465 },
-            Luv { l: 25.299875, u: 83.16893, v: 17.94365 },
-            Luv { l: 45.87716, u: -43.437843, v: 56.162994 },
-            Luv { l: 12.809525, u: -3.7292066, v: -51.694946 },
-            Luv { l: 47.89254, u: -37.040905, v: -7.991562 },
-            Luv { l: 29.52568, u: 41.146084, v: -53.19984 },
-            Luv { l: 53.238235, u: 175.01141, v: 37.758636 },
-            Luv { l: 87.73554, u: -83.07059, v: 107.40619 },
-            Luv { l: 32.298466, u: -9.40297, v: -130.34576 },
-            Luv { l: 91.11428, u: -70.46933, v: -15.2037325 },
-            Luv { l: 60.32269, u: 84.06383, v: -108.690346 },
-            Luv { l: 97.139, u: 7.7040625, v: 106.79492 },
-            Luv { l: 0.0, u: 0.0, v: 0.0 },
-            Luv { l: 27.09341, u: 0.0000052484024, v: 0.000006560503 },
-            Luv { l: 53.192772, u: 0.000010304243, v: 0.000012880304 },
-            Luv { l: 79.15698, u: -0.000015333902, v: -0.000015333902 },
-            Luv { l: 100.0, u: 0.0, v: -0.00001937151 }
-        ],
-        lch: [
-            LCh { l: 66.6377, c: 93.50614, h: 0.10102446 },
-            LCh { l: 25.299875, c: 85.08258, h: 0.21249236 },
-            LCh { l: 45.87716, c: 71.0009, h: 2.2291214 },
-            LCh { l: 12.809525, c: 51.82928, h: -1.6428102 },
-            LCh { l: 47.89254, c: 37.89319, h: -2.9291 },
-            LCh { l: 29.52568, c: 67.25491, h: -0.91247106 },
-            LCh { l: 53.238235, c: 179.03828, h: 0.2124925 },
-            LCh { l: 87.73554, c: 135.78223, h: 2.2291214 },
-            LCh { l: 32.298466, c: 130.68448, h: -1.6428102 },
-            LCh { l: 91.11428, c: 72.090775, h: -2.9291 },
-            LCh { l: 60.32269, c: 137.40567, h: -0.91247106 },
-            LCh { l: 97.139, c: 107.07244, h: 1.4987823 },
-            LCh { l: 0.0, c: 0.0, h: 0.0 },
-            LCh { l: 27.09341, c: 0.000008401544, h: 0.8960554 },
-            LCh { l: 53.192772, c: 0.000016494838, h: 0.8960554 },
-            LCh { l: 79.15698, c: 0.000021685413, h: -2.3561945 },
-            LCh { l: 100.0, c: 0.00001937151, h: -1.5707964 }
-        ],
-    };
+    #[test]
+    fn test_shortest_hue_delta_degrees() {
+        let lch = |deg: f32| LCh {
+            l: 50.0,
+            c: 20.0,
+            h: deg.to_radians(),
+        };
+        for &(from, to, want) in &[
+            (350.0, 10.0, 20.0),
+            (10.0, 350.0, -20.0),
+            (0.0, 180.0, 180.0),
+            (90.0, -90.0, 180.0),
+            (-170.0, 170.0, -20.0),
+            (45.0, 45.0, 0.0),
+            (720.0, 30.0, 30.0),
+        ] {
+            let got = lch(from).shortest_hue_delta_degrees(&lch(to));
+            assert!((got - want).abs() < 1e-4, "{} → {}: {}", from, to, got);
+        }
+    }
 
-    fn run_test<T, U>(want: &[T], f: impl Fn(&U) -> T, input: &[U])
-    where
-        T: PartialEq + std::fmt::Debug, {
-        let actual: Vec<_> = input.iter().map(f).collect();
-        assert_eq!(want, &actual[..]);
+    #[test]
+    fn test_luvs_to_rgba_bytes() {
+        assert_eq!(Vec::<u8>::new(), super::luvs_to_rgba_bytes(&[], 42));
+        for &alpha in &[0, 128, 255] {
+            let rgba = super::luvs_to_rgba_bytes(&CASES.luv, alpha);
+            let rgb = super::luvs_to_rgb_bytes(&CASES.luv);
+            assert_eq!(CASES.luv.len() * 4, rgba.len());
+            for (rgba, rgb) in rgba.chunks_exact(4).zip(rgb.chunks_exact(3)) {
+                assert_eq!(rgb, &rgba[..3]);
+                assert_eq!(alpha, rgba[3]);
+            }
+        }
     }
 
-    fn run_test_approx<T, U>(want: &[T], f: impl Fn(&U) -> T, input: &[U])
-    where
-        T: PartialEq + std::fmt::Debug + approx::RelativeEq<Epsilon = f32>,
-    {
-        let actual: Vec<_> = input.iter().map(f).collect();
-        approx::assert_abs_diff_eq!(want, &actual[..], epsilon = 0.0001);
+    #[test]
+    fn test_distance_to_gamut() {
+        for luv in CASES.luv.iter() {
+            assert_eq!(0.0, luv.distance_to_gamut());
+        }
+
+        // Just outside of the gamut the distance is the excess chroma.
+        for lch in CASES.lch.iter().filter(|lch| lch.c > 1.0) {
+            let max = LCh::max_chroma(lch.l, lch.h);
+            let mut prev = 0.0;
+            for &delta in &[0.5, 2.0, 10.0] {
+                let mut lch = *lch;
+                lch.c = max + delta;
+                let dist = lch.to_luv().distance_to_gamut();
+                assert!((dist - delta).abs() < 0.01, "{:?} {}", lch, dist);
+                assert!(dist > prev, "{:?} {} {}", lch, dist, prev);
+                prev = dist;
+            }
+        }
+
+        let too_bright = Luv {
+            l: 105.0,
+            u: 0.0,
+            v: 0.0,
+        };
+        assert_eq!(5.0, too_bright.distance_to_gamut());
     }
 
     #[test]
-    fn test_luv_from_xyz() {
-        run_test_approx(
-            &CASES.luv[..],
-            |xyz: &[f32; 3]| super::luv_from_xyz(*xyz),
-            &CASES.xyz[..],
-        );
+    fn test_snap_hue() {
+        use std::f32::consts::PI;
+
+        let anchors = [0.0, PI / 2.0, PI, -PI / 2.0];
+        for lch in CASES.lch.iter() {
+            assert_eq!(*lch, lch.snap_hue(&[]));
+            let got = lch.snap_hue(&anchors);
+            assert_eq!((lch.l, lch.c), (got.l, got.c));
+            assert!(anchors.contains(&got.h), "{:?}", got);
+            assert!(lch.hue_arc_length(&got) <= PI / 4.0 + 1e-6, "{:?}", got);
+        }
+
+        let lch = |h: f32| LCh {
+            l: 50.0,
+            c: 20.0,
+            h,
+        };
+        assert_eq!(PI / 2.0, lch(1.5).snap_hue(&anchors).h);
+        assert_eq!(-PI / 2.0, lch(4.8).snap_hue(&anchors).h);
+        assert_eq!(0.0, lch(-0.1).snap_hue(&anchors).h);
+        assert_eq!(PI, lch(-3.0).snap_hue(&anchors).h);
+        // Ties go to the first anchor.
+        assert_eq!(1.0, lch(2.0).snap_hue(&[1.0, 3.0]).h);
     }
 
     #[test]
-    fn test_xyz_from_luv() {
-        run_test(
-            &CASES.xyz[..],
-            |luv: &Luv| super::xyz_from_luv(luv),
-            &CASES.luv[..],
+    fn test_l_star_transfer() {
+        use super::{l_star_from_luminance, luminance_from_l_star};
+
+        assert_eq!(100.0, l_star_from_luminance(1.0));
+        assert_eq!(1.0, luminance_from_l_star(100.0));
+        assert_eq!(0.0, l_star_from_luminance(0.0));
+        assert_eq!(0.0, luminance_from_l_star(0.0));
+
+        // Both pieces meet at Y = ε where L* = κ·ε = 8.
+        approx::assert_abs_diff_eq!(
+            super::KAPPA_EPSILON,
+            super::KAPPA * super::EPSILON,
+            epsilon = 1e-5
         );
+        let below = l_star_from_luminance(super::EPSILON);
+        let above = l_star_from_luminance(super::EPSILON * (1.0 + 1e-6));
+        approx::assert_abs_diff_eq!(8.0, below, epsilon = 1e-5);
+        approx::assert_abs_diff_eq!(8.0, above, epsilon = 1e-3);
+        let below = luminance_from_l_star(8.0);
+        let above = luminance_from_l_star(8.0 + 1e-5);
+        approx::assert_abs_diff_eq!(super::EPSILON, below, epsilon = 1e-9);
+        approx::assert_abs_diff_eq!(super::EPSILON, above, epsilon = 1e-7);
+
+        // The functions are inverses of each other and agree with XYZ
+        // conversion.
+        for i in 0..=200 {
+            let y = i as f32 / 200.0;
+            let l = l_star_from_luminance(y);
+            let got = luminance_from_l_star(l);
+            approx::assert_abs_diff_eq!(y, got, epsilon = 1e-6);
+            let white = super::D65_XYZ;
+            let luv = Luv::from_xyz(&[y * white[0], y, y * white[2]]);
+            approx::assert_abs_diff_eq!(luv.l, l, epsilon = 1e-4);
+        }
+        for (xyz, luv) in CASES.xyz.iter().zip(CASES.luv.iter()) {
+            let l = l_star_from_luminance(xyz[1]);
+            approx::assert_abs_diff_eq!(luv.l, l, epsilon = 1e-4);
+        }
     }
 
     #[test]
-    fn test_luv_from_rgb() {
-        run_test(&CASES.luv[..], Luv::from_rgb, &CASES.rgb[..]);
+    fn test_packed_u32() {
+        // Maximum error for each coordinate: half of a quantisation step
+        // plus a bit of slack for floating point rounding.
+        let bounds = [100.0 / 2046.0, 358.0 / 4094.0, 262.0 / 4094.0];
+        let check = |luv: &Luv| {
+            let got = Luv::from_packed_u32(luv.to_packed_u32());
+            let (got, want) = (got.as_array(), luv.as_array());
+            for i in 0..3 {
+                let err = (got[i] - want[i]).abs();
+                assert!(err <= bounds[i] + 1e-4, "{:?} {:?}", want, got);
+            }
+        };
+        for luv in CASES.luv.iter() {
+            check(luv);
+        }
+        for i in 0..4096u32 {
+            let [_, r, g, b] = i.wrapping_mul(2_654_435_761).to_be_bytes();
+            check(&Luv::from_rgb(&[r, g, b]));
+        }
+
+        // Extremes of the ranges are represented exactly and values outside
+        // of them are clamped.
+        let lo = Luv::from_array_ref(&[0.0, -134.0, -140.0]);
+        let hi = Luv::from_array_ref(&[100.0, 224.0, 122.0]);
+        assert_eq!(0, lo.to_packed_u32());
+        assert_eq!(u32::MAX, hi.to_packed_u32());
+        assert_eq!(*lo, Luv::from_packed_u32(0));
+        assert_eq!(*hi, Luv::from_packed_u32(u32::MAX));
+        let luv = Luv::from_array_ref(&[-5.0, -500.0, 500.0]);
+        assert_eq!(0x0000_07ff, luv.to_packed_u32());
+
+        for lch in CASES.lch.iter() {
+            let got = LCh::from_packed_u32(lch.to_packed_u32());
+            assert!((got.l - lch.l).abs() <= bounds[0] + 1e-4);
+            let luv = got.to_luv();
+            check(&luv);
+        }
     }
 
     #[test]
-    fn test_rgb_from_luv() {
-        run_test(&CASES.rgb[..], Luv::to_rgb, &CASES.luv[..]);
+    fn test_contrast_ratio_rgb() {
+        let (black, white) = ([0, 0, 0], [255, 255, 255]);
+        let ratio = super::contrast_ratio_rgb(&black, &white);
+        approx::assert_abs_diff_eq!(21.0, ratio, epsilon = 0.0001);
+        assert_eq!(ratio, super::contrast_ratio_rgb(&white, &black));
+        assert_eq!(1.0, super::contrast_ratio_rgb(&white, &white));
+        for (a, b) in CASES.rgb.iter().zip(CASES.rgb.iter().rev()) {
+            let want = Luv::from_rgb(a).contrast_ratio(&Luv::from_rgb(b));
+            assert_eq!(want, super::contrast_ratio_rgb(a, b));
+        }
     }
 
     #[test]
-    fn test_lch_from_luv() {
-        run_test(
-            &CASES.lch[..],
-            |luv: &Luv| LCh::from_luv(*luv),
-            &CASES.luv[..],
-        );
+    fn test_tint_and_shade() {
+        for luv in CASES.luv.iter() {
+            assert_eq!(*luv, luv.tint(0.0));
+            assert_eq!(*luv, luv.shade(0.0));
+            assert_eq!(Luv::WHITE, luv.tint(1.0));
+            assert_eq!(Luv::BLACK, luv.shade(1.0));
+
+            // Hue is preserved along the way.
+            let lch = LCh::from_luv(*luv);
+            for t in [0.25, 0.5, 0.75] {
+                for got in [luv.tint(t), luv.shade(t)] {
+                    let got = LCh::from_luv(got);
+                    if lch.c > 0.01 {
+                        let delta = super::hue_delta(lch.h, got.h).abs();
+                        assert!(delta < 1e-4, "{:?} {:?}", lch, got);
+                    }
+                }
+                assert!(luv.tint(t).l >= luv.l);
+                assert!(luv.shade(t).l <= luv.l);
+            }
+        }
     }
 
     #[test]
-    fn test_luv_from_lch() {
-        run_test_approx(&CASES.luv[..], LCh::to_luv, &CASES.lch[..]);
+    fn test_denoise_neutral() {
+        let grey = Luv::from_rgb(&[196, 196, 196]);
+        assert!(grey.u != 0.0 || grey.v != 0.0);
+        let got = grey.denoise_neutral(1e-4);
+        assert_eq!((grey.l, 0.0, 0.0), (got.l, got.u, got.v));
+
+        for i in 0..=255 {
+            let grey = Luv::from_rgb(&[i, i, i]);
+            assert_eq!(grey.neutral(), grey.denoise_neutral(1e-4));
+        }
+        for luv in CASES.luv.iter() {
+            if luv.u.hypot(luv.v) > 1.0 {
+                assert_eq!(*luv, luv.denoise_neutral(1e-4));
+                assert_eq!(*luv, luv.denoise_neutral(1.0));
+            }
+        }
+
+        let luv = *Luv::from_array_ref(&[50.0, 3.0, 4.0]);
+        assert_eq!(luv, luv.denoise_neutral(4.99));
+        assert_eq!(luv.neutral(), luv.denoise_neutral(5.0));
     }
 
-    fn get_rgb_bytes() -> Vec<u8> {
-        CASES.rgb.iter().fold(
-            Vec::with_capacity(CASES.rgb.len() * 3),
-            |mut acc, rgb| {
-                acc.extend_from_slice(&rgb[..]);
-                acc
-            },
-        )
+    #[test]
+    fn test_from_luv_stable() {
+        // Error against the ideal neutral colour and number of greys whose
+        // LCh→Luv→LCh round trip isn’t exact.
+        let (mut plain_error, mut stable_error) = (0.0, 0.0);
+        let (mut plain_unstable, mut stable_unstable) = (0, 0);
+        for i in 0..=255 {
+            let luv = Luv::from_rgb(&[i, i, i]);
+            let plain = LCh::from_luv(luv);
+            let stable = LCh::from_luv_stable(luv);
+            assert_eq!((plain.l, 0.0, 0.0), (stable.l, stable.c, stable.h));
+            assert_eq!(luv.neutral(), stable.to_luv());
+
+            plain_error += plain.to_luv().squared_distance(&luv.neutral());
+            stable_error += stable.to_luv().squared_distance(&luv.neutral());
+            plain_unstable += (LCh::from_luv(plain.to_luv()) != plain) as u32;
+            let again = LCh::from_luv_stable(stable.to_luv());
+            stable_unstable += (again != stable) as u32;
+        }
+        assert_eq!((0.0, 0), (stable_error, stable_unstable));
+        assert!(plain_error > 0.0 && plain_unstable > 0);
+
+        // Chromatic colours are converted as usual.
+        for luv in CASES.luv.iter() {
+            if luv.u.hypot(luv.v) > 1e-3 {
+                assert_eq!(LCh::from_luv(*luv), LCh::from_luv_stable(*luv));
+            }
+        }
     }
 
     #[test]
-    fn test_rgbs_to_luvs() {
-        let got = super::rgbs_to_luvs(&CASES.rgb);
-        assert_eq!(&CASES.luv[..], &got[..]);
+    fn test_apparent_brightness() {
+        for i in [0, 50, 128, 255] {
+            let grey = Luv::from_rgb(&[i, i, i]).neutral();
+            assert_eq!(grey.l, grey.apparent_brightness());
+        }
+
+        // Saturated colours look brighter than greys of the same lightness.
+        for rgb in [[255, 255, 0], [255, 0, 0], [0, 0, 255], [0, 200, 0]] {
+            let luv = Luv::from_rgb(&rgb);
+            let got = luv.apparent_brightness();
+            assert!(got > luv.neutral().apparent_brightness(), "{:?}", rgb);
+            assert!(got < luv.l + 20.0, "{:?} {}", rgb, got);
+        }
+
+        // The effect is weakest for yellows and strong for blues.
+        let brightness =
+            |h: f32| Luv::from_lch_degrees(50.0, 50.0, h).apparent_brightness();
+        let (yellow, blue) = (brightness(85.0), brightness(265.0));
+        assert!(50.0 < yellow && yellow < blue, "{} {}", yellow, blue);
+        approx::assert_abs_diff_eq!(52.174, yellow, epsilon = 0.001);
+        approx::assert_abs_diff_eq!(54.425, blue, epsilon = 0.001);
     }
 
     #[test]
-    fn test_rgb_bytes_to_luvs() {
-        let input = get_rgb_bytes();
-        let got = super::rgb_bytes_to_luvs(&input[..]);
-        assert_eq!(&CASES.luv[..], &got[..]);
+    fn test_uv_prime() {
+        let white = (super::D65_U_PRIME, super::D65_V_PRIME);
+        assert_eq!(white, super::D65_LUV.uv_prime());
+        assert_eq!(white, Luv::default().uv_prime());
+        let (u_prime, v_prime) = Luv::from_rgb(&[255, 255, 255]).uv_prime();
+        approx::assert_abs_diff_eq!(white.0, u_prime, epsilon = 1e-6);
+        approx::assert_abs_diff_eq!(white.1, v_prime, epsilon = 1e-6);
+
+        for (luv, xyz) in CASES.luv.iter().zip(CASES.xyz.iter()) {
+            if luv.l <= 0.0 {
+                continue;
+            }
+            let (u_prime, v_prime) = luv.uv_prime();
+            let d = xyz[0] + 15.0 * xyz[1] + 3.0 * xyz[2];
+            approx::assert_abs_diff_eq!(
+                4.0 * xyz[0] / d,
+                u_prime,
+                epsilon = 1e-5
+            );
+            approx::assert_abs_diff_eq!(
+                9.0 * xyz[1] / d,
+                v_prime,
+                epsilon = 1e-5
+            );
+
+            let got = Luv::from_uv_prime(luv.l, u_prime, v_prime);
+            approx::assert_abs_diff_eq!(luv, &got, epsilon = 1e-3);
+        }
+
+        let black = Luv::from_uv_prime(0.0, 0.3, 0.4);
+        assert_eq!(Luv::default(), black);
     }
 
     #[test]
-    fn test_luvs_to_rgbs() {
-        let got = super::luvs_to_rgbs(&CASES.luv);
-        assert_eq!(&CASES.rgb[..], &got[..]);
+    fn test_duotone() {
+        let dark = Luv::from_rgb(&[20, 30, 90]);
+        let light = Luv::from_rgb(&[250, 220, 120]);
+        let grey = Luv::from_array_ref(&[50.0, 0.0, 0.0]);
+        let midpoint = dark.fade_to(&light, 0.5);
+        assert_eq!(midpoint, grey.duotone(&dark, &light));
+        let red = Luv::from_array_ref(&[50.0, 170.0, 30.0]);
+        assert_eq!(midpoint, red.duotone(&dark, &light));
+
+        assert_eq!(dark, Luv::BLACK.duotone(&dark, &light));
+        assert_eq!(light, Luv::WHITE.duotone(&dark, &light));
+        let too_bright = Luv::from_array_ref(&[120.0, 0.0, 0.0]);
+        assert_eq!(light, too_bright.duotone(&dark, &light));
     }
 
     #[test]
-    fn test_luvs_to_rgb_bytes() {
-        let want = get_rgb_bytes();
-        let got = super::luvs_to_rgb_bytes(&CASES.luv);
-        assert_eq!(&want[..], &got[..]);
+    #[rustfmt::skip]
+    fn test_approx_eq() {
+        use std::f32::consts::TAU;
+
+        // Chromaticity doesn’t matter if L* is zero.
+        assert!(Luv { l: 0.0, u: 0.0, v: 0.0 }
+                .approx_eq(&Luv { l: 1e-7, u: 1.0, v: 0.0 }, 1e-6));
+        assert!(LCh { l: 0.0, c: 0.0, h: 0.0 }
+                .approx_eq(&LCh { l: 1e-7, c: 1.0, h: 0.0 }, 1e-6));
+
+        // Hue doesn’t matter if C* is zero.
+        assert!(LCh { l: 100.0, c: 0.0, h: 0.0 }
+                .approx_eq(&LCh { l: 100.0, c: 1e-7, h: 1.0 }, 1e-6));
+
+        // Hues which are τ apart are equal.
+        assert!(LCh { l: 75.0, c: 50.0, h: 1.0 }
+                .approx_eq(&LCh { l: 75.0, c: 50.0, h: 1.0 - TAU }, 1e-5));
+        assert!(LCh { l: 75.0, c: 50.0, h: 1e-6 }
+                .approx_eq(&LCh { l: 75.0, c: 50.0, h: TAU - 1e-6 }, 1e-5));
+
+        // And a few non-equal test cases.
+        assert!(!Luv { l: 50.0, u: 100.0, v: 75.0 }
+                .approx_eq(&Luv { l: 50.0, u: 100.001, v: 75.0 }, 1e-4));
+        assert!(!Luv { l: 50.0, u: 100.0, v: 75.0 }
+                .approx_eq(&Luv { l: 25.0, u: 100.0, v: 75.0 }, 1e-4));
+        assert!(!LCh { l: 50.0, c: 100.0, h: 1.0 }
+                .approx_eq(&LCh { l: 50.0, c: 100.0, h: 1.001 }, 1e-4));
+        assert!(!LCh { l: 50.0, c: 100.0, h: 0.1 }
+                .approx_eq(&LCh { l: 50.0, c: 100.0, h: TAU - 0.1 }, 1e-4));
     }
 
     #[test]
@@ -721,26 +4043,14 @@ mod tests {
         // through all 8-bit greys and calculates squared error.  If it goes up,
         // a change might have worsen the precision of the calculations.  If it
         // goes down, calculations got better.
-        let mut error: f64 = 0.0;
-        let mut count: usize = 0;
-        for i in 0..=255_u32 {
-            let luv = Luv::from_rgb(&[i as u8, i as u8, i as u8]);
-            if luv.u != 0.0 || luv.v != 0.0 {
-                let u = (luv.u as f64).mul_add(luv.u as f64, error);
-                error = (luv.v as f64).mul_add(luv.v as f64, u);
-                count += 1;
-            }
-        }
+        let count = super::grey_chroma_residuals()
+            .iter()
+            .filter(|&&(_, chroma)| chroma != 0.0)
+            .count();
+        let error = super::region_roundtrip_error(super::GamutRegion::Greys);
         assert_eq!((255, 64.36984326401887), (count, error * 1e9));
     }
 
-    fn square_error(a: Luv, b: Luv, error: f64) -> f64 {
-        let a = (a.l as f64, a.u as f64, a.v as f64);
-        let b = (b.l as f64, b.u as f64, b.v as f64);
-        let (l, u, v) = (a.0 - b.0, a.1 - b.1, a.2 - b.2);
-        l.mul_add(l, u.mul_add(u, v.mul_add(v, error)))
-    }
-
     #[test]
     fn test_roundtrip_error() {
         let mut error: f64 = 0.0;
@@ -753,7 +4063,7 @@ mod tests {
                         v: v as f32 / 0.11,
                     };
                     let dst = super::luv_from_xyz(super::xyz_from_luv(&src));
-                    error = square_error(src, dst, error);
+                    error = super::precision::square_error(src, dst, error);
                 }
             }
         }
@@ -0,0 +1,349 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Colour difference metrics.
+
+use crate::Luv;
+
+/// Colour difference metric used by [`Luv::distance_with`] and
+/// [`nearest_with`].
+///
+/// CIE94 and CIEDE2000 formulæ are defined in CIE L\*a\*b\* colour space so
+/// using them involves converting the colours from L\*u\*v\* to L\*a\*b\*
+/// (through XYZ colour space using D65 reference white).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Metric {
+    /// Euclidean distance in L\*u\*v\* colour space, i.e. CIE 1976 ΔE\*uv.
+    ///
+    /// This is the cheapest metric taking just a few arithmetic operations.
+    /// It’s the square root of [`Luv::squared_distance`].
+    EuclideanLuv,
+    /// CIE 1994 ΔE\*₉₄ colour difference with graphic arts weighting factors.
+    ///
+    /// Computing it requires converting both colours to L\*a\*b\* (which
+    /// involves computing three cube roots for each colour) and two square
+    /// roots.  Note that the metric isn’t symmetric; the colour the method is
+    /// called on is treated as the reference.
+    CIE94,
+    /// CIEDE2000 ΔE₀₀ colour difference.
+    ///
+    /// The most perceptually uniform but also the most expensive of the
+    /// metrics.  In addition to conversion to L\*a\*b\*, it requires over
+    /// a dozen square root and trigonometric function evaluations.
+    CIEDE2000,
+}
+
+impl Luv {
+    /// Measures difference between the colour and an `other` using given
+    /// metric.
+    ///
+    /// See [`Metric`] for description of supported metrics and their
+    /// computational cost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luv::Metric;
+    ///
+    /// let a = luv::Luv::from_rgb(&[240, 33, 95]);
+    /// let b = luv::Luv::from_rgb(&[250, 40, 90]);
+    /// let de76 = a.distance_with(&b, Metric::EuclideanLuv);
+    /// assert_eq!(a.squared_distance(&b).sqrt(), de76);
+    /// let de00 = a.distance_with(&b, Metric::CIEDE2000);
+    /// assert!(de00 > 0.0 && de00 < de76);
+    /// ```
+    pub fn distance_with(&self, other: &Luv, metric: Metric) -> f32 {
+        match metric {
+            Metric::EuclideanLuv => self.squared_distance(other).sqrt(),
+            Metric::CIE94 => cie94(lab_from_luv(self), lab_from_luv(other)),
+            Metric::CIEDE2000 => {
+                ciede2000(lab_from_luv(self), lab_from_luv(other))
+            }
+        }
+    }
+}
+
+/// Returns index of the palette colour closest to `target` according to given
+/// metric.
+///
+/// If multiple colours are equally close, returns index of the first one.
+/// Returns `None` if the palette is empty.
+///
+/// # Example
+/// ```
+/// use luv::Metric;
+///
+/// let palette = luv::rgbs_to_luvs(&[[255, 0, 0], [0, 255, 0], [0, 0, 255]]);
+/// let target = luv::Luv::from_rgb(&[30, 40, 200]);
+/// let nearest = luv::nearest_with(&target, &palette, Metric::CIEDE2000);
+/// assert_eq!(Some(2), nearest);
+/// ```
+pub fn nearest_with(
+    target: &Luv,
+    palette: &[Luv],
+    metric: Metric,
+) -> Option<usize> {
+    let mut best = None;
+    let mut best_distance = f32::INFINITY;
+    for (idx, luv) in palette.iter().enumerate() {
+        let distance = target.distance_with(luv, metric);
+        if best.is_none() || distance < best_distance {
+            best = Some(idx);
+            best_distance = distance;
+        }
+    }
+    best
+}
+
+/// A `Luv` colour treated as a point in a metric space.
+///
+/// Spatial data structures such as k-d trees or vantage-point trees which
+/// allow fast nearest-colour queries on large palettes require distance
+/// function which satisfies the triangle inequality.  [`Luv::squared_distance`]
+/// doesn’t so this wrapper exposes Euclidean distance in L\*u\*v\* space
+/// (see [`Luv::distance`]) under `dist` and `distance` names used by common
+/// metric-space traits, and the coordinates as a `[f32; 3]` array (via
+/// [`AsRef`]) for structures which index points by their coordinates.
+/// Implementing a third-party trait for the wrapper is usually a matter of
+/// forwarding to one of those methods.
+///
+/// # Example
+/// ```
+/// use luv::LuvMetricSpace;
+///
+/// let a = LuvMetricSpace::from(luv::Luv { l: 50.0, u: 3.0, v: 0.0 });
+/// let b = LuvMetricSpace::from(luv::Luv { l: 50.0, u: 0.0, v: 4.0 });
+/// assert_eq!(5.0, a.dist(&b));
+/// assert_eq!(&[50.0, 3.0, 0.0], a.as_ref());
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[repr(transparent)]
+pub struct LuvMetricSpace(pub Luv);
+
+impl LuvMetricSpace {
+    /// Returns distance between two points; same as
+    /// [`LuvMetricSpace::distance`].
+    pub fn dist(&self, other: &Self) -> f32 { self.0.distance(&other.0) }
+
+    /// Returns Euclidean distance in L\*u\*v\* space between two points.
+    pub fn distance(&self, other: &Self) -> f32 { self.0.distance(&other.0) }
+}
+
+impl From<Luv> for LuvMetricSpace {
+    fn from(luv: Luv) -> Self { Self(luv) }
+}
+
+impl From<LuvMetricSpace> for Luv {
+    fn from(point: LuvMetricSpace) -> Self { point.0 }
+}
+
+impl AsRef<[f32; 3]> for LuvMetricSpace {
+    fn as_ref(&self) -> &[f32; 3] { self.0.as_array() }
+}
+
+
+/// Converts colour from L\*u\*v\* to L\*a\*b\* colour space.
+fn lab_from_luv(luv: &Luv) -> [f32; 3] {
+    let [x, y, z] = luv.to_xyz();
+    let white = srgb::xyz::D65_XYZ;
+    let f = |t: f32| {
+        if t > crate::EPSILON {
+            t.cbrt()
+        } else {
+            (crate::KAPPA * t + 16.0) / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x / white[0]), f(y / white[1]), f(z / white[2]));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Calculates CIE94 colour difference between two L\*a\*b\* colours using
+/// graphic arts weighting factors.
+fn cie94(reference: [f32; 3], sample: [f32; 3]) -> f32 {
+    let [l1, a1, b1] = reference;
+    let [l2, a2, b2] = sample;
+    let c1 = a1.hypot(b1);
+    let c2 = a2.hypot(b2);
+    let dl = l1 - l2;
+    let dc = c1 - c2;
+    let (da, db) = (a1 - a2, b1 - b2);
+    let dh2 = (da * da + db * db - dc * dc).max(0.0);
+    let sc = 1.0 + 0.045 * c1;
+    let sh = 1.0 + 0.015 * c1;
+    (dl * dl + (dc / sc).powi(2) + dh2 / (sh * sh)).sqrt()
+}
+
+/// Calculates CIEDE2000 colour difference between two L\*a\*b\* colours.
+fn ciede2000(lab1: [f32; 3], lab2: [f32; 3]) -> f32 {
+    use std::f32::consts::{PI, TAU};
+
+    let [l1, a1, b1] = lab1;
+    let [l2, a2, b2] = lab2;
+
+    let c_bar = (a1.hypot(b1) + a2.hypot(b2)) * 0.5;
+    let c_bar7 = c_bar.powi(7);
+    let g = 0.5 * (1.0 - (c_bar7 / (c_bar7 + 25.0f32.powi(7))).sqrt());
+    let (a1, a2) = (a1 * (1.0 + g), a2 * (1.0 + g));
+    let (c1, c2) = (a1.hypot(b1), a2.hypot(b2));
+    let hue = |a: f32, b: f32| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).rem_euclid(TAU)
+        }
+    };
+    let (h1, h2) = (hue(a1, b1), hue(a2, b2));
+
+    let dl = l2 - l1;
+    let dc = c2 - c1;
+    let dh = if c1 * c2 == 0.0 {
+        0.0
+    } else {
+        crate::hue_delta(h1, h2)
+    };
+    let dh = 2.0 * (c1 * c2).sqrt() * (dh * 0.5).sin();
+
+    let l_bar = (l1 + l2) * 0.5;
+    let c_bar = (c1 + c2) * 0.5;
+    let h_bar = if c1 * c2 == 0.0 {
+        h1 + h2
+    } else if (h1 - h2).abs() <= PI {
+        (h1 + h2) * 0.5
+    } else if h1 + h2 < TAU {
+        (h1 + h2 + TAU) * 0.5
+    } else {
+        (h1 + h2 - TAU) * 0.5
+    };
+
+    let t = 1.0 - 0.17 * (h_bar - PI / 6.0).cos() +
+        0.24 * (2.0 * h_bar).cos() +
+        0.32 * (3.0 * h_bar + PI / 30.0).cos() -
+        0.20 * (4.0 * h_bar - 0.35 * PI).cos();
+    let d_theta =
+        PI / 6.0 * (-((h_bar.to_degrees() - 275.0) / 25.0).powi(2)).exp();
+    let c_bar7 = c_bar.powi(7);
+    let rc = 2.0 * (c_bar7 / (c_bar7 + 25.0f32.powi(7))).sqrt();
+    let l50 = (l_bar - 50.0).powi(2);
+    let sl = 1.0 + 0.015 * l50 / (20.0 + l50).sqrt();
+    let sc = 1.0 + 0.045 * c_bar;
+    let sh = 1.0 + 0.015 * c_bar * t;
+    let rt = -(2.0 * d_theta).sin() * rc;
+
+    let (l, c, h) = (dl / sl, dc / sc, dh / sh);
+    (l * l + c * c + h * h + rt * c * h).sqrt()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::Metric;
+    use crate::Luv;
+
+    #[test]
+    fn test_ciede2000() {
+        // Test data from ‘The CIEDE2000 Color-Difference Formula:
+        // Implementation Notes, Supplementary Test Data, and Mathematical
+        // Observations’ by G. Sharma, W. Wu and E. N. Dalal.
+        #[rustfmt::skip]
+        let cases = [
+            ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+            ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+            ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0009], 7.1792),
+            ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+            ([50.0, 2.5, 0.0], [50.0, 3.1736, 0.5854], 1.0),
+            ([60.2574, -34.0099, 36.2677], [60.4626, -34.1751, 39.4387], 1.2644),
+            ([22.7233, 20.0904, -46.694], [23.0331, 14.973, -42.5619], 2.0373),
+            ([90.8027, -2.0831, 1.441], [91.1528, -1.6435, 0.0447], 1.4441),
+        ];
+        for (a, b, want) in cases.iter() {
+            let got = super::ciede2000(*a, *b);
+            approx::assert_abs_diff_eq!(*want, got, epsilon = 0.001);
+            let got = super::ciede2000(*b, *a);
+            approx::assert_abs_diff_eq!(*want, got, epsilon = 0.001);
+        }
+    }
+
+    #[test]
+    fn test_triangle_inequality() {
+        let a = Luv::from_rgb(&[0, 0, 0]);
+        let b = Luv::from_rgb(&[128, 128, 128]);
+        let c = Luv::from_rgb(&[255, 255, 255]);
+        let (ab, bc, ac) = (a.distance(&b), b.distance(&c), a.distance(&c));
+        assert!(ac <= ab + bc, "{} > {} + {}", ac, ab, bc);
+        // Squared distance is not a metric.
+        let (ab2, bc2, ac2) = (
+            a.squared_distance(&b),
+            b.squared_distance(&c),
+            a.squared_distance(&c),
+        );
+        assert!(ac2 > ab2 + bc2, "{} <= {} + {}", ac2, ab2, bc2);
+
+        let [a, b, c] = [a, b, c].map(super::LuvMetricSpace::from);
+        assert_eq!(ac, a.dist(&c));
+        assert!(a.distance(&c) <= a.distance(&b) + b.distance(&c));
+        assert_eq!(0.0, b.dist(&b));
+        assert_eq!(a.dist(&b), b.dist(&a));
+    }
+
+    #[test]
+    fn test_lab_from_luv() {
+        let white = super::lab_from_luv(&crate::D65_LUV);
+        approx::assert_abs_diff_eq!(
+            &[100.0, 0.0, 0.0][..],
+            &white[..],
+            epsilon = 0.001
+        );
+        let red = super::lab_from_luv(&Luv::from_rgb(&[255, 0, 0]));
+        approx::assert_abs_diff_eq!(
+            &[53.2408, 80.0925, 67.2032][..],
+            &red[..],
+            epsilon = 0.01
+        );
+    }
+
+    #[test]
+    fn test_ranking() {
+        let target = Luv::from_rgb(&[200, 60, 60]);
+        let near = Luv::from_rgb(&[210, 70, 50]);
+        let far = Luv::from_rgb(&[60, 60, 200]);
+        let palette = [far, near, target];
+        for &metric in
+            [Metric::EuclideanLuv, Metric::CIE94, Metric::CIEDE2000].iter()
+        {
+            let d_near = target.distance_with(&near, metric);
+            let d_far = target.distance_with(&far, metric);
+            assert!(0.0 < d_near && d_near < d_far, "{:?}", metric);
+            assert_eq!(
+                0.0,
+                target.distance_with(&target, metric),
+                "{:?}",
+                metric
+            );
+            assert_eq!(Some(2), super::nearest_with(&target, &palette, metric));
+            assert_eq!(
+                Some(1),
+                super::nearest_with(&target, &palette[..2], metric)
+            );
+            assert_eq!(None, super::nearest_with(&target, &[], metric));
+        }
+    }
+}
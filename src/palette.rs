@@ -0,0 +1,1191 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Functions analysing collections of colours, e.g. palettes or pixels of an
+//! image.
+
+use crate::{LCh, Luv};
+
+/// Returns the most frequent colour among given pixels.
+///
+/// The pixels are divided into bins by splitting bounding box of all the
+/// pixels in L\*u\*v\* space into `bins_per_axis` equal parts along each axis.
+/// The function then finds the bin containing the largest number of pixels
+/// and returns average of the pixels in that bin.  If multiple bins have the
+/// same number of pixels, the one with the lowest L\* (then u\* and then v\*)
+/// is chosen.
+///
+/// The resolution of the binning is a tradeoff.  With too few bins, pixels of
+/// noticeably different colours end up in the same bin and the result
+/// approaches the plain average of all pixels.  With too many bins, every
+/// pixel gets its own bin and the result becomes arbitrary.  Values from four
+/// to sixteen are typically reasonable.  Since the bins span bounding box of
+/// the pixels, a single outlier widens all the bins.
+///
+/// Returns `None` if `pixels` is empty or `bins_per_axis` is zero.
+///
+/// # Example
+/// ```
+/// let pixels = luv::rgbs_to_luvs(&[
+///     [200, 0, 0], [210, 10, 0], [205, 5, 5],
+///     [0, 0, 255], [0, 255, 0],
+/// ]);
+/// let dominant = luv::dominant_color(&pixels, 4).unwrap();
+/// assert_eq!([205, 5, 2], dominant.to_rgb());
+/// ```
+pub fn dominant_color(pixels: &[Luv], bins_per_axis: usize) -> Option<Luv> {
+    if pixels.is_empty() || bins_per_axis == 0 {
+        return None;
+    }
+
+    let (mut min, mut max) = ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]);
+    for luv in pixels {
+        for (i, &value) in [luv.l, luv.u, luv.v].iter().enumerate() {
+            min[i] = min[i].min(value);
+            max[i] = max[i].max(value);
+        }
+    }
+    let bin = |value: f32, axis: usize| {
+        let range = max[axis] - min[axis];
+        if range > 0.0 {
+            let idx = (value - min[axis]) / range * bins_per_axis as f32;
+            (idx as usize).min(bins_per_axis - 1)
+        } else {
+            0
+        }
+    };
+
+    let mut bins = std::collections::HashMap::new();
+    for luv in pixels {
+        let key = (bin(luv.l, 0), bin(luv.u, 1), bin(luv.v, 2));
+        let entry = bins.entry(key).or_insert((0usize, [0.0f32; 3]));
+        entry.0 += 1;
+        entry.1[0] += luv.l;
+        entry.1[1] += luv.u;
+        entry.1[2] += luv.v;
+    }
+
+    let (_, (count, sum)) = bins
+        .into_iter()
+        .max_by(|a, b| (a.1).0.cmp(&(b.1).0).then_with(|| b.0.cmp(&a.0)))?;
+    let count = count as f32;
+    Some(Luv {
+        l: sum[0] / count,
+        u: sum[1] / count,
+        v: sum[2] / count,
+    })
+}
+
+/// Returns the darkest and the lightest colour in a palette.
+///
+/// Finds, in a single pass, entries with the smallest and the largest L\*
+/// value and returns references to them as a `(darkest, lightest)` pair.  If
+/// several colours share the extreme lightness, the first one is returned as
+/// the darkest and the last one as the lightest colour (which matches
+/// behaviour of [`Iterator::min_by`] and [`Iterator::max_by`]).
+///
+/// Returns `None` if `colors` is empty.
+///
+/// # Example
+/// ```
+/// let colors = luv::rgbs_to_luvs(&[[255, 0, 0], [0, 0, 64], [255, 255, 0]]);
+/// let (dark, light) = luv::lightness_extremes(&colors).unwrap();
+/// assert_eq!([0, 0, 64], dark.to_rgb());
+/// assert_eq!([255, 255, 0], light.to_rgb());
+/// ```
+pub fn lightness_extremes(colors: &[Luv]) -> Option<(&Luv, &Luv)> {
+    extremes(colors, |luv| luv.l)
+}
+
+/// Returns the least and the most saturated colour in a palette.
+///
+/// Chroma of a L\*u\*v\* colour is its distance from the neutral axis, i.e.
+/// `√(u² + v²)`, which is the same as `c` of the corresponding [`crate::LCh`]
+/// colour.  Works like [`lightness_extremes`] but compares chroma rather than
+/// lightness and returns a `(least_chromatic, most_chromatic)` pair.
+///
+/// Returns `None` if `colors` is empty.
+///
+/// # Example
+/// ```
+/// let colors = luv::rgbs_to_luvs(&[[255, 0, 0], [128, 128, 128], [0, 0, 64]]);
+/// let (grey, vivid) = luv::chroma_extremes(&colors).unwrap();
+/// assert_eq!([128, 128, 128], grey.to_rgb());
+/// assert_eq!([255, 0, 0], vivid.to_rgb());
+/// ```
+pub fn chroma_extremes(colors: &[Luv]) -> Option<(&Luv, &Luv)> {
+    extremes(colors, |luv| luv.u.hypot(luv.v))
+}
+
+/// Returns entries with the smallest and the largest key.
+fn extremes(colors: &[Luv], key: impl Fn(&Luv) -> f32) -> Option<(&Luv, &Luv)> {
+    let (first, rest) = colors.split_first()?;
+    let first = (key(first), first);
+    let (min, max) = rest.iter().fold((first, first), |(min, max), luv| {
+        let value = (key(luv), luv);
+        (
+            if value.0 < min.0 { value } else { min },
+            if value.0 >= max.0 { value } else { max },
+        )
+    });
+    Some((min.1, max.1))
+}
+
+/// Counts colours falling into each of hue sectors of the hue wheel.
+///
+/// The hue circle is split into `sectors` equal sectors with sector `i`
+/// covering hues from `i·τ/sectors` (inclusive) to `(i+1)·τ/sectors`
+/// (exclusive) radians.  That is, the first sector starts at hue zero and the
+/// sectors go counterclockwise.  Hues outside of the 0–τ range (including
+/// negative hues typically produced by [`LCh::from_luv`]) are wrapped around
+/// before binning.
+///
+/// Colours whose chroma is below `min_chroma` are skipped since hue of
+/// near-grey colours is unstable and meaningless.  Returns a vector of
+/// `sectors` elements; if `sectors` is zero the vector is empty.
+///
+/// # Example
+/// ```
+/// let colors = [[255, 0, 0], [200, 20, 20], [0, 200, 0], [128, 128, 128]]
+///     .iter()
+///     .map(luv::LCh::from_rgb)
+///     .collect::<Vec<_>>();
+/// assert_eq!(vec![2, 0, 1, 0, 0, 0], luv::hue_histogram(&colors, 6, 1.0));
+/// ```
+pub fn hue_histogram(
+    colors: &[LCh],
+    sectors: usize,
+    min_chroma: f32,
+) -> Vec<u32> {
+    let mut histogram = vec![0; sectors];
+    if sectors == 0 {
+        return histogram;
+    }
+    let scale = sectors as f32 / std::f32::consts::TAU;
+    for lch in colors.iter().filter(|lch| lch.c >= min_chroma) {
+        let hue = lch.h.rem_euclid(std::f32::consts::TAU);
+        histogram[((hue * scale) as usize).min(sectors - 1)] += 1;
+    }
+    histogram
+}
+
+/// Returns squared distance of each pixel to the `target` colour.
+///
+/// This is equivalent to calling [`Luv::squared_distance`] for each of the
+/// pixels.  The result has the same length as `pixels`.
+///
+/// # Example
+/// ```
+/// let pixels = [
+///     luv::Luv { l: 50.0, u: 0.0, v: 0.0 },
+///     luv::Luv { l: 53.0, u: 4.0, v: 0.0 },
+/// ];
+/// let target = luv::Luv { l: 50.0, u: 0.0, v: 0.0 };
+/// assert_eq!(vec![0.0, 25.0], luv::distances_to(&pixels, &target));
+/// ```
+pub fn distances_to(pixels: &[Luv], target: &Luv) -> Vec<f32> {
+    pixels
+        .iter()
+        .map(|luv| luv.squared_distance(target))
+        .collect()
+}
+
+/// Returns mask of pixels which are close to the `target` colour.
+///
+/// A pixel is within the threshold if its Euclidean distance in L\*u\*v\*
+/// space to the `target` is at most `threshold`.  Note that, unlike
+/// [`distances_to`], the threshold is a plain (not squared) distance.  The
+/// result has the same length as `pixels`.
+///
+/// # Example
+/// ```
+/// let pixels = luv::rgbs_to_luvs(&[[0, 200, 0], [255, 0, 0], [10, 190, 20]]);
+/// let green = luv::Luv::from_rgb(&[0, 200, 0]);
+/// assert_eq!(vec![true, false, true], luv::within(&pixels, &green, 10.0));
+/// ```
+pub fn within(pixels: &[Luv], target: &Luv, threshold: f32) -> Vec<bool> {
+    let threshold = threshold * threshold;
+    pixels
+        .iter()
+        .map(|luv| luv.squared_distance(target) <= threshold)
+        .collect()
+}
+
+/// Returns indices of pixels which are close to the `reference` colour.
+///
+/// This is an index-returning counterpart of [`within`].  A pixel is selected
+/// if its Euclidean distance in L\*u\*v\* space to the `reference` is at
+/// most `max_delta_e`, i.e. the threshold is inclusive.  Indices are returned
+/// in increasing order.
+///
+/// # Example
+/// ```
+/// let pixels = luv::rgbs_to_luvs(&[[0, 200, 0], [255, 0, 0], [10, 190, 20]]);
+/// let green = luv::Luv::from_rgb(&[0, 200, 0]);
+/// assert_eq!(vec![0, 2], luv::select_similar(&pixels, &green, 10.0));
+/// ```
+pub fn select_similar(
+    pixels: &[Luv],
+    reference: &Luv,
+    max_delta_e: f32,
+) -> Vec<usize> {
+    let threshold = max_delta_e * max_delta_e;
+    pixels
+        .iter()
+        .enumerate()
+        .filter(|(_, luv)| luv.squared_distance(reference) <= threshold)
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Returns index of the palette colour closest to `target` and its squared
+/// distance to the target.
+///
+/// Colours are compared using Euclidean distance in L\*u\*v\* space (see
+/// [`Luv::squared_distance`]); for other metrics use [`crate::nearest_with`].
+/// If multiple colours are equally close, returns index of the first one.
+/// Returns `None` if the palette is empty.
+///
+/// # Example
+/// ```
+/// let palette = luv::rgbs_to_luvs(&[[255, 0, 0], [0, 255, 0], [0, 0, 255]]);
+/// let target = luv::Luv::from_rgb(&[30, 40, 200]);
+/// let (idx, distance) = luv::nearest(&target, &palette).unwrap();
+/// assert_eq!(2, idx);
+/// assert_eq!(palette[2].squared_distance(&target), distance);
+/// assert_eq!(None, luv::nearest(&target, &[]));
+/// ```
+pub fn nearest(target: &Luv, palette: &[Luv]) -> Option<(usize, f32)> {
+    let mut best = None;
+    for (idx, luv) in palette.iter().enumerate() {
+        let distance = luv.squared_distance(target);
+        match best {
+            Some((_, best_distance)) if best_distance <= distance => (),
+            _ => best = Some((idx, distance)),
+        }
+    }
+    best
+}
+
+/// Removes perceptually duplicate colours.
+///
+/// Goes through the colours in order and keeps a colour only if its
+/// Euclidean distance in L\*u\*v\* space to every colour kept so far is
+/// greater than `min_delta_e`.  The result is thus order-dependent: of two
+/// near-identical colours, the one which comes first is kept.  With
+/// `min_delta_e` of zero, only exact duplicates are removed.  As with
+/// [`within`], the threshold is a plain (not squared) distance.
+///
+/// This takes quadratic time in the number of colours which is fine for
+/// palettes but not for whole images.
+///
+/// # Example
+/// ```
+/// let colors = luv::rgbs_to_luvs(&[[255, 0, 0], [0, 0, 255], [254, 1, 0]]);
+/// let got = luv::dedup(&colors, 2.0);
+/// assert_eq!(vec![colors[0], colors[1]], got);
+/// ```
+pub fn dedup(colors: &[Luv], min_delta_e: f32) -> Vec<Luv> {
+    let threshold = min_delta_e * min_delta_e;
+    let mut kept: Vec<Luv> = Vec::new();
+    for luv in colors {
+        if kept.iter().all(|k| k.squared_distance(luv) > threshold) {
+            kept.push(*luv);
+        }
+    }
+    kept
+}
+
+/// Returns fraction of colours which lie within the sRGB gamut.
+///
+/// The result is a count-based ratio: the number of colours for which
+/// [`Luv::is_displayable`] returns `true` divided by the number of all
+/// colours.  It doesn’t consider how far outside of the gamut the other
+/// colours lie.  For an empty slice returns one.
+///
+/// # Example
+/// ```
+/// let colors = [
+///     luv::Luv::from_rgb(&[255, 0, 0]),
+///     luv::Luv::from_rgb(&[0, 128, 255]),
+///     luv::Luv { l: 50.0, u: 200.0, v: 0.0 },
+///     luv::Luv { l: 90.0, u: -100.0, v: -100.0 },
+/// ];
+/// assert_eq!(0.5, luv::gamut_coverage(&colors));
+/// ```
+pub fn gamut_coverage(colors: &[Luv]) -> f32 {
+    if colors.is_empty() {
+        return 1.0;
+    }
+    let count = colors.iter().filter(|luv| luv.is_displayable()).count();
+    count as f32 / colors.len() as f32
+}
+
+/// Returns volume of the convex hull of the colours in L\*u\*v\* space.
+///
+/// The value measures how much of the colour space the palette spans and
+/// can be used to compare richness of palettes: the larger the volume, the
+/// more diverse the colours.  For reference, hull of the eight corners of the
+/// sRGB cube (black, white, primaries and secondaries) has volume of about
+/// 1.36 million cubic ΔE\*uv units.  Note that the true sRGB gamut is
+/// larger since its surface isn’t flat in L\*u\*v\* space.
+///
+/// The hull is computed with an incremental algorithm which takes quadratic
+/// time in the worst case and is intended for palettes rather than whole
+/// images.  Computing a volume requires at least four points which don’t lie
+/// on a single plane; if there are fewer colours or all of them are coplanar
+/// the function returns zero.
+///
+/// # Example
+/// ```
+/// let corners = luv::rgbs_to_luvs(&[
+///     [0, 0, 0], [255, 0, 0], [0, 255, 0], [0, 0, 255],
+///     [255, 255, 0], [255, 0, 255], [0, 255, 255], [255, 255, 255],
+/// ]);
+/// let volume = luv::gamut_volume(&corners);
+/// assert!((volume - 1359113.0).abs() < 10.0);
+///
+/// let greys = luv::rgbs_to_luvs(&[[0, 0, 0], [128, 128, 128], [255; 3]]);
+/// assert_eq!(0.0, luv::gamut_volume(&greys));
+/// ```
+pub fn gamut_volume(colors: &[Luv]) -> f32 { hull::volume(colors) as f32 }
+
+/// Three-dimensional convex hull used by [`gamut_volume`].
+mod hull {
+    use crate::Luv;
+
+    type Point = [f64; 3];
+
+    fn sub(a: &Point, b: &Point) -> Point {
+        [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+    }
+
+    fn dot(a: &Point, b: &Point) -> f64 {
+        a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+    }
+
+    fn cross(a: &Point, b: &Point) -> Point {
+        [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]
+    }
+
+    /// Returns six times the signed volume of tetrahedron `abcd`; positive
+    /// if `d` lies on the side of triangle `abc` its normal points to.
+    fn orient(a: &Point, b: &Point, c: &Point, d: &Point) -> f64 {
+        dot(&cross(&sub(b, a), &sub(c, a)), &sub(d, a))
+    }
+
+    /// Returns index of the point maximising `key`.
+    fn farthest(points: &[Point], key: impl Fn(&Point) -> f64) -> usize {
+        let mut best = (0, f64::NEG_INFINITY);
+        for (idx, point) in points.iter().enumerate() {
+            let value = key(point);
+            if value > best.1 {
+                best = (idx, value);
+            }
+        }
+        best.0
+    }
+
+    /// Computes volume of the convex hull of the colours.
+    pub(super) fn volume(colors: &[Luv]) -> f64 {
+        if colors.len() < 4 {
+            return 0.0;
+        }
+        let points: Vec<Point> = colors
+            .iter()
+            .map(|luv| [luv.l as f64, luv.u as f64, luv.v as f64])
+            .collect();
+
+        // Initial tetrahedron made of extreme points.  Tolerances are
+        // relative to the extent of the point cloud.
+        let p = &points;
+        let a = 0;
+        let b = farthest(p, |x| dot(&sub(x, &p[a]), &sub(x, &p[a])));
+        let extent = dot(&sub(&p[b], &p[a]), &sub(&p[b], &p[a])).sqrt();
+        let eps = extent * extent * extent * 1e-10;
+        let ab = sub(&p[b], &p[a]);
+        let c = farthest(p, |x| {
+            let n = cross(&ab, &sub(x, &p[a]));
+            dot(&n, &n)
+        });
+        let d = farthest(p, |x| orient(&p[a], &p[b], &p[c], x).abs());
+        let side = orient(&p[a], &p[b], &p[c], &p[d]);
+        if side.abs() <= eps {
+            return 0.0;
+        }
+
+        // Faces are kept oriented so that their normals point outwards.
+        let (b, c) = if side > 0.0 { (c, b) } else { (b, c) };
+        let mut faces = vec![[a, b, c], [a, d, b], [b, d, c], [c, d, a]];
+        for (idx, point) in points.iter().enumerate() {
+            if [a, b, c, d].contains(&idx) {
+                continue;
+            }
+            let visible = |face: &[usize; 3]| {
+                let [x, y, z] = *face;
+                orient(&p[x], &p[y], &p[z], point) > eps
+            };
+            if !faces.iter().any(visible) {
+                continue;
+            }
+            // Horizon consists of edges of visible faces whose other side
+            // isn’t visible.  Each such edge forms a new face with the point.
+            let (lit, unlit): (Vec<[usize; 3]>, Vec<_>) =
+                faces.into_iter().partition(|face| visible(face));
+            let mut new_faces = unlit;
+            for face in lit.iter() {
+                for i in 0..3 {
+                    let (x, y) = (face[i], face[(i + 1) % 3]);
+                    let shared = lit.iter().any(|other| {
+                        (0..3).any(|j| other[j] == y && other[(j + 1) % 3] == x)
+                    });
+                    if !shared {
+                        new_faces.push([x, y, idx]);
+                    }
+                }
+            }
+            faces = new_faces;
+        }
+
+        // Sum volumes of tetrahedra formed by the faces and an interior
+        // point.
+        let centre =
+            [0, 1, 2].map(|i| (p[a][i] + p[b][i] + p[c][i] + p[d][i]) / 4.0);
+        let sum: f64 = faces
+            .iter()
+            .map(|[x, y, z]| orient(&p[*x], &p[*y], &p[*z], &centre))
+            .sum();
+        -sum / 6.0
+    }
+}
+
+/// Finds two palette entries whose lightness brackets given L\* value.
+///
+/// The palette must be sorted by lightness in ascending order; otherwise the
+/// result is unspecified (though the function won’t panic).  Returns indices
+/// of the lower and upper entries together with the position of `l` between
+/// their lightness as a fraction in the 0–1 range.  That is, for result
+/// `(lo, hi, t)`, `l` equals `palette[lo].l + t · (palette[hi].l −
+/// palette[lo].l)`.  The colour can then be interpolated between the two
+/// entries.
+///
+/// If `l` is outside of palette’s lightness range, indices of the first or
+/// last entry (respectively) are returned with `t` equal zero.  If the two
+/// bracketing entries have the same lightness, `t` is zero as well.  Returns
+/// `None` if the palette is empty.
+///
+/// # Example
+/// ```
+/// let palette = [
+///     luv::Luv { l: 20.0, u: 10.0, v: 5.0 },
+///     luv::Luv { l: 50.0, u: 20.0, v: -5.0 },
+///     luv::Luv { l: 90.0, u: 0.0, v: 0.0 },
+/// ];
+/// assert_eq!(Some((1, 2, 0.25)), luv::bracket_by_lightness(&palette, 60.0));
+/// assert_eq!(Some((0, 0, 0.0)), luv::bracket_by_lightness(&palette, 10.0));
+/// assert_eq!(Some((2, 2, 0.0)), luv::bracket_by_lightness(&palette, 95.0));
+/// ```
+pub fn bracket_by_lightness(
+    palette: &[Luv],
+    l: f32,
+) -> Option<(usize, usize, f32)> {
+    let last = palette.len().checked_sub(1)?;
+    let hi = palette.partition_point(|luv| luv.l < l);
+    if hi == 0 {
+        return Some((0, 0, 0.0));
+    } else if hi > last {
+        return Some((last, last, 0.0));
+    }
+    let (lo_l, hi_l) = (palette[hi - 1].l, palette[hi].l);
+    let t = if hi_l > lo_l {
+        (l - lo_l) / (hi_l - lo_l)
+    } else {
+        0.0
+    };
+    Some((hi - 1, hi, t))
+}
+
+/// Orders colours so that adjacent ones are perceptually close.
+///
+/// Returns a permutation of indices into `colors` which approximately
+/// minimises the length of the path going through all the colours in that
+/// order, where length is the sum of Euclidean distances in L\*u\*v\* space
+/// between adjacent colours.  This is useful for displaying a palette as
+/// a smooth strip.
+///
+/// Finding the optimal order is the travelling salesman problem, so the
+/// function uses a heuristic and the result is not guaranteed to be optimal.
+/// The path starts at the darkest colour and greedily continues to the
+/// nearest not yet visited colour.  The path is then improved with 2-opt,
+/// i.e. by reversing sections of the path as long as doing so shortens it.
+///
+/// # Example
+/// ```
+/// let colors = luv::rgbs_to_luvs(&[
+///     [200, 200, 200],
+///     [0, 0, 0],
+///     [255, 255, 255],
+///     [100, 100, 100],
+/// ]);
+/// assert_eq!(vec![1, 3, 0, 2], luv::order_for_smoothness(&colors));
+/// ```
+pub fn order_for_smoothness(colors: &[Luv]) -> Vec<usize> {
+    let start = match colors
+        .iter()
+        .enumerate()
+        .min_by(|a, b| a.1.l.total_cmp(&b.1.l))
+    {
+        Some((idx, _)) => idx,
+        None => return Vec::new(),
+    };
+    let dist =
+        |a: usize, b: usize| colors[a].squared_distance(&colors[b]).sqrt();
+
+    // Nearest neighbour.
+    let mut order = Vec::with_capacity(colors.len());
+    let mut rest: Vec<usize> =
+        (0..colors.len()).filter(|&i| i != start).collect();
+    order.push(start);
+    while !rest.is_empty() {
+        let last = order[order.len() - 1];
+        let (pos, _) = rest
+            .iter()
+            .enumerate()
+            .min_by(|a, b| dist(last, *a.1).total_cmp(&dist(last, *b.1)))
+            .unwrap();
+        order.push(rest.remove(pos));
+    }
+
+    // 2-opt.  Reversing order[i..=j] replaces edges (i-1, i) and (j, j+1)
+    // with (i-1, j) and (i, j+1).  Edges past the ends of the path don’t
+    // exist so they contribute nothing.
+    let n = order.len();
+    let edge = |order: &[usize], a: Option<usize>, b: usize| {
+        a.filter(|_| b < n)
+            .map_or(0.0, |a| dist(order[a], order[b]))
+    };
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in 0..n {
+            for j in i + 1..n {
+                let prev = i.checked_sub(1);
+                let before =
+                    edge(&order, prev, i) + edge(&order, Some(j), j + 1);
+                let after =
+                    edge(&order, prev, j) + edge(&order, Some(i), j + 1);
+                if after < before - 1e-4 {
+                    order[i..=j].reverse();
+                    improved = true;
+                }
+            }
+        }
+    }
+    order
+}
+
+/// Maps a palette so that source anchor colours move to target anchors.
+///
+/// The transform is a per-axis affine map: each of the L\*, u\* and v\*
+/// components is scaled and offset independently, i.e. `x′ = s·x + o`.
+/// Scale and offset for each axis are the least-squares fit of the target
+/// anchors to the source anchors.  If source anchors don’t vary along an axis
+/// (which is always the case with a single anchor), the scale for that axis is
+/// one and only the offset is fitted.  Consequently, a single anchor pair
+/// translates the whole palette and two anchors are enough to describe
+/// translation and scale along every axis.  Because the map is monotonic along
+/// each axis, lightness ordering and relative chroma of the palette are
+/// preserved.
+///
+/// With no anchors, returns the palette unchanged.  The result isn’t clamped
+/// so colours may end up outside of the sRGB gamut.
+///
+/// # Panics
+///
+/// Panics if `source_anchors` and `target_anchors` have different lengths.
+///
+/// # Example
+/// ```
+/// let palette = [
+///     luv::Luv { l: 20.0, u: 0.0, v: 0.0 },
+///     luv::Luv { l: 50.0, u: 20.0, v: 10.0 },
+///     luv::Luv { l: 80.0, u: 40.0, v: 20.0 },
+/// ];
+/// let target = [
+///     luv::Luv { l: 30.0, u: 10.0, v: 0.0 },
+///     luv::Luv { l: 90.0, u: 90.0, v: 40.0 },
+/// ];
+/// let source = [palette[0], palette[2]];
+/// let got = luv::retarget_palette(&palette, &source, &target);
+/// assert_eq!(&[60.0, 50.0, 20.0], got[1].as_array());
+/// ```
+pub fn retarget_palette(
+    source: &[Luv],
+    source_anchors: &[Luv],
+    target_anchors: &[Luv],
+) -> Vec<Luv> {
+    assert_eq!(
+        source_anchors.len(),
+        target_anchors.len(),
+        "anchor lists must have the same length"
+    );
+    if source_anchors.is_empty() {
+        return source.to_vec();
+    }
+
+    let n = source_anchors.len() as f32;
+    let mut transform = [(1.0, 0.0); 3];
+    for (axis, (scale, offset)) in transform.iter_mut().enumerate() {
+        let component = |luv: &Luv| luv.as_array()[axis];
+        let src_mean = source_anchors.iter().map(component).sum::<f32>() / n;
+        let dst_mean = target_anchors.iter().map(component).sum::<f32>() / n;
+        let (mut cov, mut var) = (0.0, 0.0);
+        for (src, dst) in source_anchors.iter().zip(target_anchors) {
+            let (x, y) = (component(src) - src_mean, component(dst) - dst_mean);
+            cov += x * y;
+            var += x * x;
+        }
+        if var > 1e-6 {
+            *scale = cov / var;
+        }
+        *offset = dst_mean - *scale * src_mean;
+    }
+
+    source
+        .iter()
+        .map(|luv| {
+            let arr = luv.as_array();
+            let mut out = [0.0; 3];
+            for ((out, x), (scale, offset)) in
+                out.iter_mut().zip(arr).zip(&transform)
+            {
+                *out = crate::mul_add(*x, *scale, *offset);
+            }
+            *Luv::from_array_ref(&out)
+        })
+        .collect()
+}
+
+impl Luv {
+    /// Returns average of the colours ignoring the ones farthest from it.
+    ///
+    /// The average is computed in two passes.  First, plain mean of all the
+    /// colours is calculated.  Then, `trim_fraction` of the colours (rounded
+    /// down) which are farthest (by [`Luv::squared_distance`]) from that mean
+    /// are discarded and the mean of the remaining colours is returned.  This
+    /// is more robust than the plain mean against outliers such as stray
+    /// highlights or shadows, e.g. when computing a representative colour of
+    /// an image crop.
+    ///
+    /// `trim_fraction` is clamped to the 0–1 range and at least one colour
+    /// is always kept.  With zero, the result is the plain mean.  Returns
+    /// `None` if `colors` is empty.
+    ///
+    /// # Example
+    /// ```
+    /// let colors = [
+    ///     luv::Luv { l: 50.0, u: 10.0, v: 10.0 },
+    ///     luv::Luv { l: 52.0, u: 10.0, v: 10.0 },
+    ///     luv::Luv { l: 48.0, u: 10.0, v: 10.0 },
+    ///     luv::Luv { l: 100.0, u: 0.0, v: 0.0 },
+    /// ];
+    /// let avg = luv::Luv::trimmed_average(&colors, 0.25).unwrap();
+    /// assert_eq!(luv::Luv { l: 50.0, u: 10.0, v: 10.0 }, avg);
+    /// ```
+    pub fn trimmed_average(colors: &[Luv], trim_fraction: f32) -> Option<Luv> {
+        let centre = mean(colors.iter())?;
+        let trim =
+            (colors.len() as f32 * trim_fraction.clamp(0.0, 1.0)) as usize;
+        let keep = (colors.len() - trim).max(1);
+        if keep == colors.len() {
+            return Some(centre);
+        }
+        let mut sorted: Vec<(f32, &Luv)> = colors
+            .iter()
+            .map(|luv| (luv.squared_distance(&centre), luv))
+            .collect();
+        sorted.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+        mean(sorted[..keep].iter().map(|entry| entry.1))
+    }
+
+    /// Returns average of the colours weighted by their salience.
+    ///
+    /// Each colour is weighted by `(C* · (1 − |L* − 50| / 50))²` where
+    /// C\* = √(u\*² + v\*²) is its chroma and L\* its lightness clamped to
+    /// the 0–100 range.  Squaring makes the weight grow faster than chroma so
+    /// that a handful of vivid pixels outweighs many slightly tinted ones.
+    /// Vivid colours of medium lightness thus dominate the
+    /// result while greys as well as very dark and very light colours
+    /// contribute little or nothing.  This gives a more intuitive ‘theme
+    /// colour’ of an image than the plain mean which tends towards a muddy
+    /// grey.
+    ///
+    /// If all weights are zero (e.g. all colours are greys), returns the
+    /// plain mean of the colours.  Returns `None` if `pixels` is empty.
+    ///
+    /// # Example
+    /// ```
+    /// let mut pixels = vec![luv::Luv::from_rgb(&[120, 115, 110]); 20];
+    /// pixels.push(luv::Luv::from_rgb(&[30, 90, 220]));
+    /// pixels.push(luv::Luv::from_rgb(&[40, 80, 230]));
+    /// let got = luv::Luv::salience_weighted_centroid(&pixels).unwrap();
+    /// assert_eq!([44, 86, 221], got.to_rgb());
+    /// ```
+    pub fn salience_weighted_centroid(pixels: &[Luv]) -> Option<Luv> {
+        let (mut l, mut u, mut v, mut total) = (0.0, 0.0, 0.0, 0.0);
+        for luv in pixels {
+            let lightness = 1.0 - (luv.l.clamp(0.0, 100.0) - 50.0).abs() / 50.0;
+            let weight =
+                (luv.u * luv.u + luv.v * luv.v) * lightness * lightness;
+            l += weight * luv.l;
+            u += weight * luv.u;
+            v += weight * luv.v;
+            total += weight;
+        }
+        if total > 0.0 {
+            Some(Luv {
+                l: l / total,
+                u: u / total,
+                v: v / total,
+            })
+        } else {
+            mean(pixels.iter())
+        }
+    }
+}
+
+/// Returns plain mean of given colours or `None` if there are none.
+fn mean<'a>(colors: impl Iterator<Item = &'a Luv>) -> Option<Luv> {
+    let (mut l, mut u, mut v, mut count) = (0.0, 0.0, 0.0, 0usize);
+    for luv in colors {
+        l += luv.l;
+        u += luv.u;
+        v += luv.v;
+        count += 1;
+    }
+    let count = count as f32;
+    (count > 0.0).then(|| Luv {
+        l: l / count,
+        u: u / count,
+        v: v / count,
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{LCh, Luv};
+
+    #[test]
+    fn test_order_for_smoothness() {
+        assert_eq!(Vec::<usize>::new(), super::order_for_smoothness(&[]));
+        assert_eq!(vec![0], super::order_for_smoothness(&[Luv::default()]));
+
+        // Greys in shuffled order should get sorted by lightness.
+        let values = [128, 0, 64, 255, 32, 192, 96, 160, 224];
+        let greys: Vec<Luv> =
+            values.iter().map(|&v| Luv::from_rgb(&[v, v, v])).collect();
+        let order = super::order_for_smoothness(&greys);
+        let got: Vec<u8> = order.iter().map(|&i| values[i]).collect();
+        assert_eq!(vec![0, 32, 64, 96, 128, 160, 192, 224, 255], got);
+
+        // Hues around a circle.  Starting from the darkest, the path should
+        // go around the circle rather than zigzag.
+        let colors: Vec<Luv> = [0, 5, 2, 7, 4, 1, 6, 3]
+            .iter()
+            .map(|&i| {
+                let h = i as f32 * std::f32::consts::TAU / 8.0;
+                let l = 50.0 + i as f32 * 0.01;
+                LCh { l, c: 50.0, h }.to_luv()
+            })
+            .collect();
+        let order = super::order_for_smoothness(&colors);
+        assert_eq!(vec![0, 5, 2, 7, 4, 1, 6, 3], order);
+    }
+
+    #[test]
+    fn test_trimmed_average() {
+        assert_eq!(None, Luv::trimmed_average(&[], 0.1));
+
+        let luv = Luv::from_rgb(&[200, 20, 30]);
+        assert_eq!(Some(luv), Luv::trimmed_average(&[luv], 0.0));
+        assert_eq!(Some(luv), Luv::trimmed_average(&[luv], 1.0));
+
+        // A cluster of reds with a single white outlier.
+        let mut colors = crate::rgbs_to_luvs(&[
+            [200, 20, 30],
+            [210, 25, 20],
+            [205, 15, 25],
+            [215, 20, 25],
+            [195, 22, 28],
+            [208, 18, 22],
+            [202, 24, 30],
+            [212, 16, 26],
+            [198, 20, 20],
+        ]);
+        let centroid = super::mean(colors.iter()).unwrap();
+        colors.push(Luv::WHITE);
+
+        let plain = Luv::trimmed_average(&colors, 0.0).unwrap();
+        assert_eq!(super::mean(colors.iter()).unwrap(), plain);
+        assert!(plain.squared_distance(&centroid) > 100.0, "{:?}", plain);
+
+        let trimmed = Luv::trimmed_average(&colors, 0.1).unwrap();
+        approx::assert_abs_diff_eq!(centroid, trimmed, epsilon = 0.001);
+    }
+
+    #[test]
+    fn test_salience_weighted_centroid() {
+        assert_eq!(None, Luv::salience_weighted_centroid(&[]));
+
+        // A few vivid pixels dominate many dull ones.
+        let vivid = LCh {
+            l: 55.0,
+            c: 90.0,
+            h: 2.0,
+        }
+        .to_luv();
+        let mut pixels = crate::rgbs_to_luvs(&[[120, 115, 110]; 50]);
+        pixels.extend(crate::rgbs_to_luvs(&[[250, 250, 250]; 30]));
+        pixels.extend(crate::rgbs_to_luvs(&[[10, 8, 12]; 30]));
+        pixels.extend([vivid; 5].iter());
+        let got = Luv::salience_weighted_centroid(&pixels).unwrap();
+        let plain = super::mean(pixels.iter()).unwrap();
+        assert!(
+            got.squared_distance(&vivid) < 100.0,
+            "{:?} {:?}",
+            got,
+            vivid
+        );
+        assert!(
+            got.squared_distance(&vivid) * 10.0 <
+                plain.squared_distance(&vivid)
+        );
+
+        // With no chroma, falls back to the plain mean.
+        let greys = crate::rgbs_to_luvs(&[[0, 0, 0], [255, 255, 255]]);
+        let want = super::mean(greys.iter());
+        assert_eq!(want, Luv::salience_weighted_centroid(&greys));
+        let greys = [Luv {
+            l: 30.0,
+            u: 0.0,
+            v: 0.0,
+        }];
+        assert_eq!(Some(greys[0]), Luv::salience_weighted_centroid(&greys));
+    }
+
+    #[test]
+    fn test_dedup() {
+        assert_eq!(Vec::<Luv>::new(), super::dedup(&[], 1.0));
+
+        let rgbs = [[255, 0, 0], [253, 2, 1], [0, 0, 255], [255, 0, 0]];
+        let colors = crate::rgbs_to_luvs(&rgbs);
+        let got = super::dedup(&colors, 3.0);
+        assert_eq!(vec![colors[0], colors[2]], got);
+
+        // First occurrence wins.
+        let got = super::dedup(&colors[1..], 3.0);
+        assert_eq!(vec![colors[1], colors[2]], got);
+
+        // Zero threshold removes only exact duplicates.
+        let got = super::dedup(&colors, 0.0);
+        assert_eq!(colors[..3].to_vec(), got);
+    }
+
+    #[test]
+    fn test_dominant_color() {
+        assert_eq!(None, super::dominant_color(&[], 4));
+        assert_eq!(None, super::dominant_color(&[Luv::default()], 0));
+
+        let luv = Luv {
+            l: 50.0,
+            u: 10.0,
+            v: -10.0,
+        };
+        assert_eq!(Some(luv), super::dominant_color(&[luv, luv], 8));
+
+        let pixels = crate::rgbs_to_luvs(&[
+            [200, 20, 30],
+            [0, 0, 255],
+            [210, 25, 20],
+            [255, 255, 0],
+            [205, 15, 25],
+            [0, 200, 200],
+            [215, 20, 25],
+            [40, 40, 40],
+        ]);
+        let reds = [pixels[0], pixels[2], pixels[4], pixels[6]];
+        let want = Luv {
+            l: reds.iter().map(|luv| luv.l).sum::<f32>() / 4.0,
+            u: reds.iter().map(|luv| luv.u).sum::<f32>() / 4.0,
+            v: reds.iter().map(|luv| luv.v).sum::<f32>() / 4.0,
+        };
+        let got = super::dominant_color(&pixels, 4).unwrap();
+        approx::assert_abs_diff_eq!(want, got, epsilon = 0.001);
+        assert_eq!([207, 20, 25], got.to_rgb());
+    }
+
+    #[test]
+    fn test_hue_histogram() {
+        let colors = [
+            [255, 0, 0],
+            [200, 30, 30],
+            [0, 255, 0],
+            [30, 200, 30],
+            [20, 150, 20],
+            [0, 0, 0],
+            [128, 128, 128],
+            [130, 128, 128],
+        ]
+        .iter()
+        .map(LCh::from_rgb)
+        .collect::<Vec<_>>();
+        let got = super::hue_histogram(&colors, 4, 5.0);
+        assert_eq!(vec![2, 3, 0, 0], got);
+        let got = super::hue_histogram(&colors, 12, 5.0);
+        assert_eq!(vec![2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0], got);
+
+        assert_eq!(Vec::<u32>::new(), super::hue_histogram(&colors, 0, 5.0));
+
+        let lch = LCh {
+            l: 50.0,
+            c: 50.0,
+            h: -0.1,
+        };
+        assert_eq!(vec![0, 0, 0, 1], super::hue_histogram(&[lch], 4, 5.0));
+    }
+
+    #[test]
+    fn test_within() {
+        let pixels = crate::rgbs_to_luvs(&[
+            [0, 177, 64],
+            [255, 255, 255],
+            [10, 180, 70],
+            [0, 0, 255],
+            [0, 170, 60],
+            [128, 128, 128],
+        ]);
+        let target = pixels[0];
+
+        let distances = super::distances_to(&pixels, &target);
+        assert_eq!(pixels.len(), distances.len());
+        for (luv, distance) in pixels.iter().zip(distances) {
+            assert_eq!(luv.squared_distance(&target), distance);
+        }
+
+        let want = vec![true, false, true, false, true, false];
+        assert_eq!(want, super::within(&pixels, &target, 5.0));
+        let want = vec![true, false, false, false, false, false];
+        assert_eq!(want, super::within(&pixels, &target, 0.0));
+        assert_eq!(Vec::<bool>::new(), super::within(&[], &target, 5.0));
+    }
+
+    #[test]
+    fn test_select_similar() {
+        let pixels = crate::rgbs_to_luvs(&[
+            [0, 177, 64],
+            [255, 255, 255],
+            [10, 180, 70],
+            [0, 0, 255],
+            [0, 170, 60],
+            [128, 128, 128],
+        ]);
+        let target = pixels[0];
+
+        let got = super::select_similar(&pixels, &target, 5.0);
+        assert_eq!(vec![0, 2, 4], got);
+        assert_eq!(vec![0], super::select_similar(&pixels, &target, 0.0));
+        let got = super::select_similar(&[], &target, 5.0);
+        assert_eq!(Vec::<usize>::new(), got);
+
+        // The threshold is inclusive.
+        let delta = pixels[2].squared_distance(&target).sqrt();
+        let got = super::select_similar(&pixels, &target, delta);
+        assert!(got.contains(&2));
+    }
+
+    #[test]
+    fn test_gamut_volume() {
+        let corners = crate::rgbs_to_luvs(&[
+            [0, 0, 0],
+            [0, 0, 255],
+            [0, 255, 0],
+            [0, 255, 255],
+            [255, 0, 0],
+            [255, 0, 255],
+            [255, 255, 0],
+            [255, 255, 255],
+        ]);
+        let want = 1359112.7;
+        let got = super::gamut_volume(&corners);
+        approx::assert_relative_eq!(want, got, max_relative = 1e-5);
+
+        // Interior points and duplicates don’t change the volume.
+        let mut colors = crate::rgbs_to_luvs(&[[128, 128, 128], [200, 50, 50]]);
+        colors.extend_from_slice(&corners);
+        colors.extend_from_slice(&corners);
+        colors.reverse();
+        let got = super::gamut_volume(&colors);
+        approx::assert_relative_eq!(want, got, max_relative = 1e-5);
+
+        // An axis-aligned cube with points on its faces.
+        let mut cube = Vec::new();
+        for l in [0.0, 5.0, 10.0] {
+            for u in [0.0, 5.0, 10.0] {
+                for v in [0.0, 5.0, 10.0] {
+                    cube.push(*Luv::from_array_ref(&[l, u, v]));
+                }
+            }
+        }
+        approx::assert_relative_eq!(1000.0, super::gamut_volume(&cube));
+
+        // Degenerate cases.
+        assert_eq!(0.0, super::gamut_volume(&[]));
+        assert_eq!(0.0, super::gamut_volume(&corners[..3]));
+        let plane =
+            crate::rgbs_to_luvs(&[[0, 0, 0], [64, 64, 64], [128, 128, 128], [
+                255, 255, 255,
+            ]]);
+        assert_eq!(0.0, super::gamut_volume(&plane));
+        let square = [0.0, 10.0]
+            .iter()
+            .flat_map(|&u| {
+                [0.0, 10.0].map(|v| *Luv::from_array_ref(&[50.0, u, v]))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(0.0, super::gamut_volume(&square));
+    }
+
+    #[test]
+    fn test_retarget_palette() {
+        let luv = |l, u, v| *Luv::from_array_ref(&[l, u, v]);
+        let assert_close = |want: &[Luv], got: &[Luv]| {
+            assert_eq!(want.len(), got.len());
+            for (want, got) in want.iter().zip(got) {
+                let distance = want.squared_distance(got);
+                assert!(distance < 1e-6, "{:?} != {:?}", want, got);
+            }
+        };
+
+        // Two anchors: translation and scale along each axis.
+        let palette = [
+            luv(20.0, 0.0, 0.0),
+            luv(50.0, 20.0, 10.0),
+            luv(80.0, 40.0, 20.0),
+        ];
+        let source = [palette[0], palette[2]];
+        let target = [luv(30.0, 10.0, 0.0), luv(90.0, 90.0, 40.0)];
+        let got = super::retarget_palette(&palette, &source, &target);
+        assert_close(&[target[0], luv(60.0, 50.0, 20.0), target[1]], &got);
+
+        // Single anchor: pure translation.
+        let (source, target) = ([palette[1]], [luv(40.0, 25.0, 5.0)]);
+        let got = super::retarget_palette(&palette, &source, &target);
+        assert_close(
+            &[
+                luv(10.0, 5.0, -5.0),
+                luv(40.0, 25.0, 5.0),
+                luv(70.0, 45.0, 15.0),
+            ],
+            &got,
+        );
+
+        // No anchors: identity.
+        assert_close(&palette, &super::retarget_palette(&palette, &[], &[]));
+    }
+
+    #[test]
+    fn test_nearest() {
+        let rgbs = [[255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 0, 0]];
+        let palette = crate::rgbs_to_luvs(&rgbs);
+        let target = Luv::from_rgb(&[200, 30, 40]);
+        let want = (0, palette[0].squared_distance(&target));
+        assert_eq!(Some(want), super::nearest(&target, &palette));
+        assert_eq!(Some((1, 0.0)), super::nearest(&palette[1], &palette));
+        assert_eq!(None, super::nearest(&target, &[]));
+
+        for luv in crate::rgbs_to_luvs(&[[10, 20, 30], [0, 200, 100]]) {
+            let metric = crate::Metric::EuclideanLuv;
+            let want = crate::nearest_with(&luv, &palette, metric);
+            let got = super::nearest(&luv, &palette).map(|(idx, _)| idx);
+            assert_eq!(want, got);
+        }
+    }
+
+    #[test]
+    fn test_gamut_coverage() {
+        assert_eq!(1.0, super::gamut_coverage(&[]));
+
+        let mut colors = crate::rgbs_to_luvs(&[
+            [255, 0, 0],
+            [0, 255, 0],
+            [0, 0, 255],
+            [128, 128, 128],
+            [0, 0, 0],
+            [255, 255, 255],
+        ]);
+        assert_eq!(1.0, super::gamut_coverage(&colors));
+
+        colors.push(Luv {
+            l: 50.0,
+            u: 200.0,
+            v: 0.0,
+        });
+        colors.push(Luv {
+            l: 101.0,
+            u: 0.0,
+            v: 0.0,
+        });
+        assert_eq!(0.75, super::gamut_coverage(&colors));
+    }
+
+    #[test]
+    fn test_bracket_by_lightness() {
+        let palette = crate::rgbs_to_luvs(&[[32, 16, 64], [128, 64, 160], [
+            240, 220, 255,
+        ]]);
+        assert!(palette[0].l < palette[1].l && palette[1].l < palette[2].l);
+
+        let l = (palette[0].l + 3.0 * palette[1].l) / 4.0;
+        let (lo, hi, t) = super::bracket_by_lightness(&palette, l).unwrap();
+        assert_eq!((0, 1), (lo, hi));
+        approx::assert_abs_diff_eq!(0.75, t, epsilon = 0.0001);
+
+        let l = palette[1].l;
+        let (lo, hi, t) = super::bracket_by_lightness(&palette, l).unwrap();
+        assert_eq!((0, 1, 1.0), (lo, hi, t));
+
+        let bracket = |l| super::bracket_by_lightness(&palette, l);
+        assert_eq!(Some((0, 0, 0.0)), bracket(0.0));
+        assert_eq!(Some((0, 0, 0.0)), bracket(palette[0].l));
+        assert_eq!(Some((2, 2, 0.0)), bracket(100.0));
+        assert_eq!(None, super::bracket_by_lightness(&[], 50.0));
+        assert_eq!(
+            Some((0, 0, 0.0)),
+            super::bracket_by_lightness(&palette[..1], 50.0)
+        );
+    }
+}
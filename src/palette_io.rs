@@ -0,0 +1,162 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Reading and writing GIMP palette (`.gpl`) files.
+//!
+//! Only the subset of the format needed to exchange colours is supported.
+//! A file must start with a `GIMP Palette` line.  Empty lines, comments (lines
+//! starting with `#`) and metadata lines (`Name:` and `Columns:` headers) are
+//! skipped.  Every other line describes a colour: its first three
+//! whitespace-separated fields are red, green and blue components in the
+//! 0–255 range.  Anything following them (usually the colour’s name) is
+//! ignored.
+
+use std::io;
+
+use crate::binary::invalid_data;
+use crate::Luv;
+
+/// Header identifying a GIMP palette file.
+const HEADER: &str = "GIMP Palette";
+
+/// Writes palette as a GIMP palette (`.gpl`) file.
+///
+/// Colours are converted to 8-bit sRGB with [`Luv::to_rgb`] which means that
+/// colours outside of the sRGB gamut are clamped and that the conversion is
+/// lossy.  Colours are written without names.  See [`read_gpl`] for the
+/// description of the format.
+///
+/// # Example
+/// ```
+/// let palette = luv::rgbs_to_luvs(&[[255, 0, 0], [0, 0, 255]]);
+/// let mut buf = Vec::new();
+/// luv::write_gpl(&palette, &mut buf).unwrap();
+/// assert_eq!("GIMP Palette\n#\n255   0   0\n  0   0 255\n",
+///            std::str::from_utf8(&buf).unwrap());
+/// ```
+pub fn write_gpl(luvs: &[Luv], w: &mut impl io::Write) -> io::Result<()> {
+    writeln!(w, "{}\n#", HEADER)?;
+    for luv in luvs {
+        let [r, g, b] = luv.to_rgb();
+        writeln!(w, "{:3} {:3} {:3}", r, g, b)?;
+    }
+    Ok(())
+}
+
+/// Reads palette from a GIMP palette (`.gpl`) file.
+///
+/// The file must start with a `GIMP Palette` line.  Empty lines, comments
+/// (lines starting with `#`) and `Name:` and `Columns:` metadata lines are
+/// skipped.  Every other line must start with red, green and blue components
+/// of a colour given as integers in the 0–255 range separated by whitespace.
+/// Rest of the line (usually the colour’s name) is ignored.  Colours are
+/// converted with [`Luv::from_rgb`].
+///
+/// # Errors
+///
+/// Returns an error with [`io::ErrorKind::InvalidData`] kind if the file
+/// doesn’t start with the header, isn’t valid UTF-8 or has a malformed colour
+/// line.  Other errors from the reader are passed through.
+///
+/// # Example
+/// ```
+/// let data = "GIMP Palette\nName: Primary\n# RGB\n255 0 0\tRed\n0 0 255\n";
+/// let palette = luv::read_gpl(&mut data.as_bytes()).unwrap();
+/// assert_eq!(luv::rgbs_to_luvs(&[[255, 0, 0], [0, 0, 255]]), palette);
+/// ```
+pub fn read_gpl(r: &mut impl io::Read) -> io::Result<Vec<Luv>> {
+    let mut data = String::new();
+    r.read_to_string(&mut data)?;
+    let mut lines = data.lines();
+    if lines.next().map(str::trim_end) != Some(HEADER) {
+        return Err(invalid_data("missing GIMP palette header"));
+    }
+
+    let mut luvs = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() ||
+            line.starts_with('#') ||
+            line.starts_with("Name:") ||
+            line.starts_with("Columns:")
+        {
+            continue;
+        }
+        let mut fields = line.split_whitespace().map(str::parse::<u8>);
+        let mut next = || match fields.next() {
+            Some(Ok(value)) => Ok(value),
+            _ => Err(invalid_data("invalid GIMP palette colour")),
+        };
+        let rgb = [next()?, next()?, next()?];
+        luvs.push(Luv::from_rgb(&rgb));
+    }
+    Ok(luvs)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    #[test]
+    fn test_round_trip() {
+        let data = "GIMP Palette\nName: Test\nColumns: 4\n#\n253 120 \
+                    138\tPink\n\x20 127   0   0 Maroon\n\n0 127 127\n255 255   \
+                    0 Yellow\n0 0 0 Black\n255 255 255\tWhite\n";
+        let want = crate::rgbs_to_luvs(&[
+            [253, 120, 138],
+            [127, 0, 0],
+            [0, 127, 127],
+            [255, 255, 0],
+            [0, 0, 0],
+            [255, 255, 255],
+        ]);
+        let palette = super::read_gpl(&mut data.as_bytes()).unwrap();
+        assert_eq!(want, palette);
+
+        let mut buf = Vec::new();
+        super::write_gpl(&palette, &mut buf).unwrap();
+        assert_eq!(palette, super::read_gpl(&mut &buf[..]).unwrap());
+
+        let mut buf = Vec::new();
+        super::write_gpl(&[], &mut buf).unwrap();
+        let empty = super::read_gpl(&mut &buf[..]).unwrap();
+        assert_eq!(Vec::<crate::Luv>::new(), empty);
+    }
+
+    #[test]
+    fn test_invalid() {
+        for data in [
+            "",
+            "255 0 0\n",
+            "GIMP Palette\n255 0\n",
+            "GIMP Palette\n256 0 0\n",
+            "GIMP Palette\nred 0 0\n",
+            "GIMP Palette\nName Test\n",
+        ] {
+            let err = super::read_gpl(&mut data.as_bytes()).unwrap_err();
+            assert_eq!(io::ErrorKind::InvalidData, err.kind(), "{:?}", data);
+        }
+        let err = super::read_gpl(&mut &[0xff, 0xfe][..]).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+    }
+}
@@ -0,0 +1,79 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Parallel processing of images using the `rayon` crate.
+
+use rayon::prelude::*;
+
+use crate::Luv;
+
+/// Maps each pixel to index of the closest palette colour, processing pixels
+/// in parallel.
+///
+/// The result is the same as of serial
+/// `pixels.iter().map(|p| luv::nearest(p, palette).unwrap().0).collect()`
+/// (see [`crate::nearest`]) except that work is split across threads of
+/// rayon’s global thread pool.  This makes a difference when quantising large
+/// images against large palettes where the search dominates running time.
+///
+/// # Panics
+///
+/// Panics if `palette` is empty and `pixels` isn’t.
+///
+/// # Example
+/// ```
+/// let palette = luv::rgbs_to_luvs(&[[0, 0, 0], [255, 0, 0], [255, 255, 255]]);
+/// let pixels = luv::rgbs_to_luvs(&[[200, 20, 30], [10, 10, 10], [240; 3]]);
+/// assert_eq!(vec![1, 0, 2], luv::par_quantize(&pixels, &palette));
+/// ```
+pub fn par_quantize(pixels: &[Luv], palette: &[Luv]) -> Vec<usize> {
+    pixels
+        .par_iter()
+        .map(|luv| crate::nearest(luv, palette).unwrap().0)
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::Luv;
+
+    #[test]
+    fn test_par_quantize() {
+        let palette: Vec<Luv> = (0..64u8)
+            .map(|i| Luv::from_rgb(&[i * 4, 255 - i * 3, i.wrapping_mul(97)]))
+            .collect();
+        let pixels: Vec<Luv> = (0..10_000u32)
+            .map(|i| {
+                let [_, r, g, b] = i.wrapping_mul(2_654_435_761).to_be_bytes();
+                Luv::from_rgb(&[r, g, b])
+            })
+            .collect();
+        let want: Vec<usize> = pixels
+            .iter()
+            .map(|luv| crate::nearest(luv, &palette).unwrap().0)
+            .collect();
+        assert_eq!(want, super::par_quantize(&pixels, &palette));
+        assert_eq!(Vec::<usize>::new(), super::par_quantize(&[], &palette));
+        assert_eq!(Vec::<usize>::new(), super::par_quantize(&[], &[]));
+    }
+}
@@ -0,0 +1,413 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Measurements of precision of the conversions.
+
+use crate::{LCh, Luv};
+
+/// Region of the sRGB gamut over which [`region_roundtrip_error`] measures
+/// conversion error.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GamutRegion {
+    /// All 256 8-bit shades of grey.  Greys have u\* and v\* equal zero so the
+    /// error is the accumulated squared u\* and v\* of the converted colours.
+    Greys,
+    /// The eight corners of the sRGB cube, i.e. black, white, primary and
+    /// secondary colours.
+    Primaries,
+    /// 8-bit sRGB colours whose components are all multiples of fifteen.
+    /// This samples the whole cube with 18³ = 5832 colours.
+    Cube,
+}
+
+/// Returns accumulated conversion error over given gamut region.
+///
+/// For [`GamutRegion::Greys`], each grey is converted to L\*u\*v\* and the
+/// squared distance from the neutral axis is accumulated.  For other regions,
+/// each colour is converted to L\*u\*v\*, then to XYZ and back to L\*u\*v\* and
+/// the squared distance between the two L\*u\*v\* values is accumulated.
+/// The sums are calculated using `f64` arithmetic.
+///
+/// The values are meant for tracking and reporting precision of the crate.
+/// Note that they depend on whether fused multiply-add instructions are
+/// available on the target platform.
+///
+/// # Example
+///
+/// ```
+/// use luv::{region_roundtrip_error, GamutRegion};
+///
+/// assert!(region_roundtrip_error(GamutRegion::Greys) < 1e-6);
+/// assert!(region_roundtrip_error(GamutRegion::Cube) < 1e-3);
+/// ```
+pub fn region_roundtrip_error(region: GamutRegion) -> f64 {
+    match region {
+        GamutRegion::Greys => (0..=255).fold(0.0, |error, i| {
+            let luv = Luv::from_rgb(&[i, i, i]);
+            let mut grey = luv;
+            grey.u = 0.0;
+            grey.v = 0.0;
+            square_error(luv, grey, error)
+        }),
+        GamutRegion::Primaries => {
+            let colours = (0..8).map(|i| {
+                [(i & 1) * 255, (i >> 1 & 1) * 255, (i >> 2 & 1) * 255]
+            });
+            colours.fold(0.0, roundtrip_error)
+        }
+        GamutRegion::Cube => {
+            let steps = || (0..=255).step_by(15);
+            let colours = steps()
+                .flat_map(move |r| steps().map(move |g| (r, g)))
+                .flat_map(move |(r, g)| steps().map(move |b| [r, g, b]));
+            colours.fold(0.0, roundtrip_error)
+        }
+    }
+}
+
+/// Returns accumulated error of Luv→LCh→Luv round trips over given colours.
+///
+/// Each colour is converted to [`LCh`] and back to [`Luv`] and the squared
+/// distance between the source and the result is accumulated using `f64`
+/// arithmetic.  As documented on [`LCh::to_luv`], the conversion isn’t
+/// guaranteed to give back the source colour; the value quantifies how much
+/// it is off for a given sample of colours.
+///
+/// # Example
+///
+/// ```
+/// let sample = luv::rgbs_to_luvs(&[[255, 0, 0], [0, 128, 255], [9, 9, 9]]);
+/// assert!(luv::lch_roundtrip_error(&sample) < 1e-9);
+/// ```
+pub fn lch_roundtrip_error(sample: &[Luv]) -> f64 {
+    sample.iter().fold(0.0, |error, luv| {
+        square_error(*luv, LCh::from_luv(*luv).to_luv(), error)
+    })
+}
+
+/// Returns residual chroma of each 8-bit grey after conversion to L\*u\*v\*.
+///
+/// Greys should have u\* and v\* equal zero but due to limited precision of
+/// the conversion they end up with tiny non-zero values.  For each grey level
+/// `i`, the function converts `[i, i, i]` sRGB colour using
+/// [`Luv::from_rgb`] and returns `(i, hypot(u, v))` pair.  The result has 256
+/// entries ordered by grey level.
+///
+/// This is useful when choosing an epsilon for deciding whether a colour is
+/// grey: any threshold below the maximum residual will misclassify some of
+/// the 8-bit greys.  [`GamutRegion::Greys`] measures the same characteristic
+/// as a single sum of squares.
+///
+/// # Example
+///
+/// ```
+/// let residuals = luv::grey_chroma_residuals();
+/// assert_eq!(256, residuals.len());
+/// assert_eq!((0, 0.0), residuals[0]);
+/// assert!(residuals.iter().all(|&(_, chroma)| chroma < 0.001));
+/// ```
+pub fn grey_chroma_residuals() -> Vec<(u8, f32)> {
+    (0..=255)
+        .map(|i| {
+            let luv = Luv::from_rgb(&[i, i, i]);
+            (i, luv.u.hypot(luv.v))
+        })
+        .collect()
+}
+
+/// Summary of round-trip errors of a conversion path.
+///
+/// See [`precision_report`].
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct ErrorStats {
+    /// The largest error.
+    pub max: f64,
+    /// Arithmetic mean of the errors.
+    pub mean: f64,
+    /// The 99th percentile of the errors, i.e. the smallest error which is
+    /// greater or equal to at least 99% of the errors.
+    pub p99: f64,
+}
+
+impl ErrorStats {
+    /// Calculates statistics of given errors.  For an empty slice all
+    /// statistics are zero.
+    fn new(mut errors: Vec<f64>) -> Self {
+        if errors.is_empty() {
+            return Self::default();
+        }
+        errors.sort_by(f64::total_cmp);
+        // usize::div_ceil requires Rust 1.73.
+        #[allow(clippy::manual_div_ceil)]
+        let rank = (errors.len() * 99 + 99) / 100;
+        Self {
+            max: errors[errors.len() - 1],
+            mean: errors.iter().sum::<f64>() / errors.len() as f64,
+            p99: errors[rank - 1],
+        }
+    }
+}
+
+/// Comparison of sRGB→L\*u\*v\*→sRGB round-trip precision of single and
+/// double precision arithmetic.
+///
+/// See [`precision_report`].
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct PrecisionReport {
+    /// Errors of the conversion as implemented by the crate, i.e. using
+    /// `f32` arithmetic.
+    pub f32: ErrorStats,
+    /// Errors of the same conversion performed with `f64` arithmetic.
+    pub f64: ErrorStats,
+}
+
+/// Measures precision of sRGB→L\*u\*v\*→sRGB round trip over given colours.
+///
+/// Each 8-bit sRGB colour of the sample is converted to L\*u\*v\* and back
+/// to gamma-compressed sRGB without quantising the result to 8 bits.  The
+/// error of a colour is the Euclidean distance between the source and
+/// resulting sRGB components in the 0–255 scale; errors well below 0.5
+/// mean the conversion reproduces 8-bit colours exactly.
+///
+/// The crate performs conversions using `f32` arithmetic only.  To quantify
+/// how much precision that costs, the report also includes errors of the
+/// same round trip performed by a reference implementation of the same
+/// formulæ which uses `f64` arithmetic throughout.  The reference is
+/// private to this function and doesn’t follow the `f32` code path step by
+/// step so it’s meant for comparison only.
+///
+/// For an empty sample all statistics are zero.
+///
+/// # Example
+///
+/// ```
+/// let report = luv::precision_report(&[[255, 0, 0], [0, 128, 255], [9; 3]]);
+/// assert!(report.f32.max < 0.01);
+/// assert!(report.f64.max < 1e-9);
+/// ```
+pub fn precision_report(sample: &[[u8; 3]]) -> PrecisionReport {
+    let error = |rgb: &[u8; 3], got: [f64; 3]| {
+        let d = |i: usize| got[i] * 255.0 - rgb[i] as f64;
+        let (r, g, b) = (d(0), d(1), d(2));
+        r.mul_add(r, g.mul_add(g, b * b)).sqrt()
+    };
+    let single = sample.iter().map(|rgb| {
+        let got = Luv::from_rgb(rgb).to_rgb_normalized();
+        error(rgb, [got[0] as f64, got[1] as f64, got[2] as f64])
+    });
+    let double = sample.iter().map(|rgb| {
+        let normalised = rgb.map(|c| c as f64 / 255.0);
+        error(rgb, double::roundtrip(normalised))
+    });
+    PrecisionReport {
+        f32: ErrorStats::new(single.collect()),
+        f64: ErrorStats::new(double.collect()),
+    }
+}
+
+/// Reference sRGB→L\*u\*v\*→sRGB conversion using `f64` arithmetic.
+mod double {
+    /// Matrix converting linear sRGB to XYZ; see
+    /// [`srgb::xyz::XYZ_FROM_SRGB_MATRIX`].
+    #[rustfmt::skip]
+    const XYZ_FROM_SRGB: [[f64; 3]; 3] = [
+        [4223344.0 / 10240623.0, 14647555.0 / 40962492.0, 14783675.0 / 81924984.0],
+        [2903549.0 / 13654164.0, 14647555.0 / 20481246.0, 2956735.0 / 40962492.0],
+        [263959.0 / 13654164.0, 14647555.0 / 122887476.0, 233582065.0 / 245774952.0],
+    ];
+
+    /// Matrix converting XYZ to linear sRGB; see
+    /// [`srgb::xyz::SRGB_FROM_XYZ_MATRIX`].
+    #[rustfmt::skip]
+    const SRGB_FROM_XYZ: [[f64; 3]; 3] = [
+        [4277208.0 / 1319795.0, -2028932.0 / 1319795.0, -658032.0 / 1319795.0],
+        [-70985202.0 / 73237775.0, 137391598.0 / 73237775.0, 3043398.0 / 73237775.0],
+        [164508.0 / 2956735.0, -603196.0 / 2956735.0, 3125652.0 / 2956735.0],
+    ];
+
+    const KAPPA: f64 = 24389.0 / 27.0;
+    const EPSILON: f64 = 216.0 / 24389.0;
+
+    /// Converts normalised sRGB to L\*u\*v\* and back.
+    pub(super) fn roundtrip(rgb: [f64; 3]) -> [f64; 3] {
+        let linear = rgb.map(|e| {
+            if e > 0.04045 {
+                ((e + 0.055) / 1.055).powf(2.4)
+            } else {
+                e / 12.92
+            }
+        });
+        let xyz = mul(&XYZ_FROM_SRGB, linear);
+        let linear = mul(&SRGB_FROM_XYZ, xyz_from_luv(luv_from_xyz(xyz)));
+        linear.map(|s| {
+            if s > 0.0031308 {
+                1.055f64.mul_add(s.powf(1.0 / 2.4), -0.055)
+            } else {
+                12.92 * s
+            }
+        })
+    }
+
+    fn mul(matrix: &[[f64; 3]; 3], vector: [f64; 3]) -> [f64; 3] {
+        matrix.map(|row| {
+            row[0].mul_add(
+                vector[0],
+                row[1].mul_add(vector[1], row[2] * vector[2]),
+            )
+        })
+    }
+
+    /// Returns u′ and v′ chromaticity coordinates of given XYZ colour.
+    fn uv_prime([x, y, z]: [f64; 3]) -> (f64, f64) {
+        let d = y.mul_add(15.0, z.mul_add(3.0, x));
+        (4.0 * x / d, 9.0 * y / d)
+    }
+
+    fn white() -> [f64; 3] { mul(&XYZ_FROM_SRGB, [1.0, 1.0, 1.0]) }
+
+    fn luv_from_xyz(xyz: [f64; 3]) -> [f64; 3] {
+        let y = xyz[1];
+        if y <= 0.0 {
+            return [0.0; 3];
+        }
+        let l = if y > EPSILON {
+            y.cbrt().mul_add(116.0, -16.0)
+        } else {
+            KAPPA * y
+        };
+        let (u, v) = uv_prime(xyz);
+        let (un, vn) = uv_prime(white());
+        [l, 13.0 * l * (u - un), 13.0 * l * (v - vn)]
+    }
+
+    fn xyz_from_luv([l, u, v]: [f64; 3]) -> [f64; 3] {
+        if l <= 0.0 {
+            return [0.0; 3];
+        }
+        let (un, vn) = uv_prime(white());
+        let u_prime = u / (13.0 * l) + un;
+        let v_prime = v / (13.0 * l) + vn;
+        let y = if l > KAPPA * EPSILON {
+            ((l + 16.0) / 116.0).powi(3)
+        } else {
+            l / KAPPA
+        };
+        let x = y * 9.0 * u_prime / (4.0 * v_prime);
+        let z = y * (12.0 - 3.0 * u_prime - 20.0 * v_prime) / (4.0 * v_prime);
+        [x, y, z]
+    }
+}
+
+/// Adds Luv→XYZ→Luv round-trip error of given sRGB colour to `error`.
+fn roundtrip_error(error: f64, rgb: [u8; 3]) -> f64 {
+    let src = Luv::from_rgb(&rgb);
+    square_error(src, crate::luv_from_xyz(crate::xyz_from_luv(&src)), error)
+}
+
+/// Adds squared distance between two colours to `error`.
+pub(crate) fn square_error(a: Luv, b: Luv, error: f64) -> f64 {
+    let a = (a.l as f64, a.u as f64, a.v as f64);
+    let b = (b.l as f64, b.u as f64, b.v as f64);
+    let (l, u, v) = (a.0 - b.0, a.1 - b.1, a.2 - b.2);
+    l.mul_add(l, u.mul_add(u, v.mul_add(v, error)))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regions() {
+        let primaries = region_roundtrip_error(GamutRegion::Primaries);
+        let cube = region_roundtrip_error(GamutRegion::Cube);
+        assert!(primaries <= cube);
+        assert!(cube < 1e-3, "{}", cube);
+    }
+
+    #[test]
+    fn test_precision_report() {
+        assert_eq!(PrecisionReport::default(), precision_report(&[]));
+
+        let steps = || (0..=255).step_by(15);
+        let sample: Vec<[u8; 3]> = steps()
+            .flat_map(|r| {
+                steps().flat_map(move |g| steps().map(move |b| [r, g, b]))
+            })
+            .collect();
+        let report = precision_report(&sample);
+        for stats in [report.f32, report.f64] {
+            assert!(stats.mean <= stats.p99, "{:?}", stats);
+            assert!(stats.p99 <= stats.max, "{:?}", stats);
+        }
+        assert!(report.f64.max <= report.f32.max, "{:?}", report);
+        assert!(report.f64.mean <= report.f32.mean, "{:?}", report);
+        assert!(report.f64.p99 <= report.f32.p99, "{:?}", report);
+        assert!(report.f32.max < 0.01, "{:?}", report);
+        assert!(report.f64.max < 1e-9, "{:?}", report);
+
+        let stats = ErrorStats::new((1..=200).map(f64::from).collect());
+        assert_eq!(
+            ErrorStats {
+                max: 200.0,
+                mean: 100.5,
+                p99: 198.0
+            },
+            stats
+        );
+    }
+
+    #[test]
+    fn test_grey_chroma_residuals() {
+        let residuals = grey_chroma_residuals();
+        assert_eq!(256, residuals.len());
+        for (i, &(grey, _)) in residuals.iter().enumerate() {
+            assert_eq!(i, grey as usize);
+        }
+
+        let max = residuals.iter().map(|r| r.1).fold(0.0, f32::max);
+        assert_eq!(3.666103e-5, max);
+
+        // Sum of squares matches GamutRegion::Greys measurement.
+        let sum: f64 = residuals.iter().map(|r| (r.1 as f64).powi(2)).sum();
+        let want = region_roundtrip_error(GamutRegion::Greys);
+        assert!((sum - want).abs() < want * 1e-3, "{} vs {}", sum, want);
+    }
+
+    #[test]
+    fn test_lch_roundtrip_error() {
+        assert_eq!(0.0, lch_roundtrip_error(&[]));
+        assert_eq!(0.0, lch_roundtrip_error(&[Luv::BLACK, Luv::WHITE]));
+
+        let steps = || (0..=255).step_by(15);
+        let sample: Vec<Luv> = steps()
+            .flat_map(|r| {
+                steps().flat_map(move |g| steps().map(move |b| [r, g, b]))
+            })
+            .map(|rgb| Luv::from_rgb(&rgb))
+            .collect();
+        // Average squared error per colour is well below 1e-10.
+        let error = lch_roundtrip_error(&sample);
+        assert!(error < 2e-7, "{}", error);
+        assert!(error / (sample.len() as f64) < 1e-10, "{}", error);
+    }
+}
@@ -0,0 +1,273 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Conversion of RGB colours with primaries other than sRGB’s.
+
+use crate::Luv;
+
+/// RGB colour space, i.e. set of primaries and transfer function, of 8-bit
+/// RGB colours.
+///
+/// All of the supported colour spaces use the D65 white point so white and
+/// greys map to the same L\*u\*v\* values regardless of the primaries and no
+/// chromatic adaptation is necessary.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Primaries {
+    /// sRGB (IEC 61966-2-1) primaries and transfer function.  This is what
+    /// [`Luv::from_rgb`] and [`Luv::to_rgb`] assume.
+    Srgb,
+    /// Display P3 colour space, i.e. DCI-P3 primaries with the D65 white point
+    /// and the sRGB transfer function.
+    DisplayP3,
+    /// ITU-R BT.2020 primaries and transfer function, with the components
+    /// using full 0–255 range.
+    Rec2020,
+}
+
+// The matrices are derived from xy chromaticity coordinates of the primaries
+// and of the D65 white point as used by the srgb crate (see
+// srgb::xyz::D65_xyY).  Display P3 primaries are (0.680, 0.320), (0.265,
+// 0.690) and (0.150, 0.060); BT.2020 primaries are (0.708, 0.292), (0.170,
+// 0.797) and (0.131, 0.046).
+
+#[rustfmt::skip]
+const P3_TO_XYZ: [[f32; 3]; 3] = [
+    [8111533.0 / 16670144.0, 53144743.0 / 200041728.0, 13215455.0 / 66680576.0],
+    [477149.0 / 2083768.0, 23062813.0 / 33340288.0, 2643091.0 / 33340288.0],
+    [0.0, 3008193.0 / 66680576.0, 208804189.0 / 200041728.0],
+];
+
+#[rustfmt::skip]
+const XYZ_TO_P3: [[f32; 3]; 3] = [
+    [148715232.0 / 59643625.0, -55548868.0 / 59643625.0, -24018168.0 / 59643625.0],
+    [-103969056.0 / 125341375.0, 220934244.0 / 125341375.0, 2961144.0 / 125341375.0],
+    [11844576.0 / 330386375.0, -25169724.0 / 330386375.0, 10199496.0 / 10657625.0],
+];
+
+#[rustfmt::skip]
+const REC2020_TO_XYZ: [[f32; 3]; 3] = [
+    [7400301501.0 / 11617911394.0, 5040450445.0 / 34853734182.0, 23541397837.0 / 139414936728.0],
+    [3052101749.0 / 11617911394.0, 47261635349.0 / 69707468364.0, 4133222521.0 / 69707468364.0],
+    [0.0, 652293587.0 / 23235822788.0, 147897484121.0 / 139414936728.0],
+];
+
+#[rustfmt::skip]
+const XYZ_TO_REC2020: [[f32; 3]; 3] = [
+    [8971347817.0 / 5226201625.0, -1858762183.0 / 5226201625.0, -1324111183.0 / 5226201625.0],
+    [-4941738066.0 / 7412427125.0, 11982022434.0 / 7412427125.0, 116882934.0 / 7412427125.0],
+    [396299772.0 / 22463165875.0, -16286292.0 / 380731625.0, 21165434772.0 / 22463165875.0],
+];
+
+// Parameters of the BT.2020 transfer function.
+const REC2020_ALPHA: f32 = 1.0992968;
+const REC2020_BETA: f32 = 0.0180540;
+
+impl Luv {
+    /// Constructs a new `Luv` from an 8-bit RGB colour in given colour space.
+    ///
+    /// The components are decoded with the colour space’s transfer function
+    /// and converted to XYZ using matrix derived from its primaries and the
+    /// D65 white point.  With [`Primaries::Srgb`] this is the same as
+    /// [`Luv::from_rgb`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luv::Primaries;
+    ///
+    /// let rgb = [255, 0, 0];
+    /// let srgb = luv::Luv::from_rgb_with_primaries(&rgb, Primaries::Srgb);
+    /// let p3 = luv::Luv::from_rgb_with_primaries(&rgb, Primaries::DisplayP3);
+    /// assert_eq!(luv::Luv::from_rgb(&rgb), srgb);
+    /// // P3 red is more saturated than sRGB red.
+    /// assert!(p3.u.hypot(p3.v) > srgb.u.hypot(srgb.v));
+    /// ```
+    pub fn from_rgb_with_primaries(
+        rgb: &[u8; 3],
+        primaries: Primaries,
+    ) -> Self {
+        let (matrix, expand): (_, fn(f32) -> f32) = match primaries {
+            Primaries::Srgb => return Luv::from_rgb(rgb),
+            Primaries::DisplayP3 => {
+                (&P3_TO_XYZ, srgb::gamma::expand_normalised)
+            }
+            Primaries::Rec2020 => (&REC2020_TO_XYZ, rec2020_expand),
+        };
+        let linear = rgb.map(|e| expand(e as f32 / 255.0));
+        Luv::from_xyz(&crate::matrix_product(matrix, &linear))
+    }
+
+    /// Converts the colour to an 8-bit RGB colour in given colour space.
+    ///
+    /// This is an inverse of [`Luv::from_rgb_with_primaries`].  Colours
+    /// outside of the colour space’s gamut are clamped.  With
+    /// [`Primaries::Srgb`] this is the same as [`Luv::to_rgb`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use luv::Primaries;
+    ///
+    /// let luv = luv::Luv::from_rgb(&[255, 0, 0]);
+    /// assert_eq!([255, 0, 0], luv.to_rgb_with_primaries(Primaries::Srgb));
+    /// let p3 = luv.to_rgb_with_primaries(Primaries::DisplayP3);
+    /// assert_eq!([234, 51, 35], p3);
+    /// ```
+    pub fn to_rgb_with_primaries(&self, primaries: Primaries) -> [u8; 3] {
+        let (matrix, compress): (_, fn(f32) -> f32) = match primaries {
+            Primaries::Srgb => return self.to_rgb(),
+            Primaries::DisplayP3 => {
+                (&XYZ_TO_P3, srgb::gamma::compress_normalised)
+            }
+            Primaries::Rec2020 => (&XYZ_TO_REC2020, rec2020_compress),
+        };
+        crate::matrix_product(matrix, &self.to_xyz())
+            .map(|s| (compress(s.clamp(0.0, 1.0)) * 255.0).round() as u8)
+    }
+}
+
+/// Decodes normalised BT.2020 component into linear value.
+fn rec2020_expand(e: f32) -> f32 {
+    if e < 4.5 * REC2020_BETA {
+        e / 4.5
+    } else {
+        ((e + (REC2020_ALPHA - 1.0)) / REC2020_ALPHA).powf(1.0 / 0.45)
+    }
+}
+
+/// Encodes linear value into normalised BT.2020 component.
+fn rec2020_compress(s: f32) -> f32 {
+    if s < REC2020_BETA {
+        4.5 * s
+    } else {
+        crate::mul_add(REC2020_ALPHA, s.powf(0.45), -(REC2020_ALPHA - 1.0))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Primaries; 3] =
+        [Primaries::Srgb, Primaries::DisplayP3, Primaries::Rec2020];
+
+    #[test]
+    fn test_matrices() {
+        // White maps to D65 and the matrices are inverses of each other.
+        let white = srgb::xyz::D65_XYZ;
+        for (to, from) in
+            [(&P3_TO_XYZ, &XYZ_TO_P3), (&REC2020_TO_XYZ, &XYZ_TO_REC2020)]
+        {
+            let got = crate::matrix_product(to, &[1.0, 1.0, 1.0]);
+            for i in 0..3 {
+                approx::assert_abs_diff_eq!(white[i], got[i], epsilon = 1e-5);
+            }
+            for i in 0..3 {
+                let mut unit = [0.0; 3];
+                unit[i] = 1.0;
+                let got = crate::matrix_product(
+                    from,
+                    &crate::matrix_product(to, &unit),
+                );
+                for j in 0..3 {
+                    approx::assert_abs_diff_eq!(
+                        unit[j],
+                        got[j],
+                        epsilon = 1e-6
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_transfer() {
+        for i in 0..=255 {
+            let e = i as f32 / 255.0;
+            let got = rec2020_compress(rec2020_expand(e));
+            approx::assert_abs_diff_eq!(e, got, epsilon = 1e-6);
+        }
+        // The two pieces meet.
+        let below = rec2020_compress(REC2020_BETA * (1.0 - 1e-6));
+        let above = rec2020_compress(REC2020_BETA);
+        approx::assert_abs_diff_eq!(below, above, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn test_with_primaries() {
+        for rgb in [[0, 0, 0], [255, 255, 255], [12, 200, 99], [253, 120, 138]]
+        {
+            let want = Luv::from_rgb(&rgb);
+            assert_eq!(
+                want,
+                Luv::from_rgb_with_primaries(&rgb, Primaries::Srgb)
+            );
+            assert_eq!(rgb, want.to_rgb_with_primaries(Primaries::Srgb));
+        }
+
+        // Greys are the same in all colour spaces (up to differences in
+        // transfer functions) but other colours differ.
+        for primaries in ALL {
+            let luv = Luv::from_rgb_with_primaries(&[255; 3], primaries);
+            approx::assert_abs_diff_eq!(Luv::WHITE, luv, epsilon = 0.01);
+            let luv = Luv::from_rgb_with_primaries(&[0; 3], primaries);
+            assert_eq!(Luv::BLACK, luv);
+        }
+        let grey =
+            Luv::from_rgb_with_primaries(&[128; 3], Primaries::DisplayP3);
+        approx::assert_abs_diff_eq!(
+            Luv::from_rgb(&[128; 3]),
+            grey,
+            epsilon = 0.01
+        );
+
+        for rgb in [[255, 0, 0], [0, 255, 0], [0, 0, 255], [12, 200, 99]] {
+            let srgb = Luv::from_rgb(&rgb);
+            let p3 = Luv::from_rgb_with_primaries(&rgb, Primaries::DisplayP3);
+            let rec2020 =
+                Luv::from_rgb_with_primaries(&rgb, Primaries::Rec2020);
+            assert!(srgb.squared_distance(&p3) > 1.0, "{:?}", rgb);
+            assert!(p3.squared_distance(&rec2020) > 1.0, "{:?}", rgb);
+        }
+
+        // Bytes survive a round trip.
+        for primaries in ALL {
+            for i in 0..4096u32 {
+                let [_, r, g, b] = i.wrapping_mul(2_654_435_761).to_be_bytes();
+                let luv = Luv::from_rgb_with_primaries(&[r, g, b], primaries);
+                let got = luv.to_rgb_with_primaries(primaries);
+                assert_eq!([r, g, b], got, "{:?}", primaries);
+            }
+        }
+
+        // sRGB colours fit within P3 and Rec.2020 gamuts.
+        for rgb in [[255, 0, 0], [0, 255, 0], [0, 0, 255]] {
+            let luv = Luv::from_rgb(&rgb);
+            for primaries in [Primaries::DisplayP3, Primaries::Rec2020] {
+                let got = luv.to_rgb_with_primaries(primaries);
+                let back = Luv::from_rgb_with_primaries(&got, primaries);
+                assert!(luv.squared_distance(&back) < 1.0, "{:?}", rgb);
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Generation of random colours.
+
+use crate::Luv;
+
+/// Returns a random colour within the sRGB gamut.
+///
+/// The colour is generated by sampling each of the gamma-encoded sRGB
+/// components uniformly from the 0–1 range and converting the result to
+/// L\*u\*v\*.  The result is therefore always displayable (see
+/// [`Luv::is_displayable`]) but note that the distribution is uniform in sRGB
+/// rather than in L\*u\*v\* space.
+///
+/// # Example
+///
+/// ```
+/// use rand::SeedableRng;
+///
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+/// let luv = luv::random_displayable(&mut rng);
+/// assert!(luv.is_displayable());
+/// ```
+pub fn random_displayable<R: rand::Rng + ?Sized>(rng: &mut R) -> Luv {
+    Luv::from_rgb_normalized(&[rng.gen(), rng.gen(), rng.gen()])
+}
+
+/// Returns a random colour with coordinates within typical ranges.
+///
+/// L\* is sampled uniformly from the 0–100 range while u\* and v\* are
+/// sampled uniformly from the -100–100 range.  Many of the resulting colours
+/// lie outside of the sRGB gamut; use [`random_displayable`] if that’s not
+/// desired.
+///
+/// # Example
+///
+/// ```
+/// use rand::SeedableRng;
+///
+/// let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+/// let luv = luv::random_luv(&mut rng);
+/// assert!((0.0..=100.0).contains(&luv.l));
+/// assert!((-100.0..=100.0).contains(&luv.u));
+/// assert!((-100.0..=100.0).contains(&luv.v));
+/// ```
+pub fn random_luv<R: rand::Rng + ?Sized>(rng: &mut R) -> Luv {
+    Luv {
+        l: rng.gen_range(0.0..=100.0),
+        u: rng.gen_range(-100.0..=100.0),
+        v: rng.gen_range(-100.0..=100.0),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_random_displayable() {
+        let mut rng: rand::rngs::StdRng =
+            rand::SeedableRng::from_seed([1u8; 32]);
+        for _ in 0..10000 {
+            let luv = super::random_displayable(&mut rng);
+            assert!(luv.is_displayable(), "{:?}", luv);
+        }
+    }
+
+    #[test]
+    fn test_random_luv() {
+        let mut rng: rand::rngs::StdRng =
+            rand::SeedableRng::from_seed([1u8; 32]);
+        let mut displayable = 0;
+        for _ in 0..10000 {
+            let luv = super::random_luv(&mut rng);
+            assert!((0.0..=100.0).contains(&luv.l), "{:?}", luv);
+            assert!((-100.0..=100.0).contains(&luv.u), "{:?}", luv);
+            assert!((-100.0..=100.0).contains(&luv.v), "{:?}", luv);
+            displayable += luv.is_displayable() as u32;
+        }
+        assert!(displayable > 0 && displayable < 10000);
+    }
+}
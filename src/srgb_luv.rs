@@ -0,0 +1,137 @@
+/* This file is part of luv crate.
+ * Copyright (c) 2020 🐝🐝🐝
+ * Copyright (c) 2021 Michał Nazarewicz <mina86@mina86.com>
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in
+ * all copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE. */
+
+//! Colour type guaranteed to be within sRGB gamut.
+
+use std::convert::TryFrom;
+
+use crate::Luv;
+
+/// A [`Luv`] colour which is known to be displayable in sRGB.
+///
+/// The only ways to construct the object are through [`TryFrom<Luv>`], which
+/// checks [`Luv::is_displayable`], and [`SrgbLuv::from_rgb`], which cannot
+/// fail.  Functions which require displayable colours can therefore accept
+/// `SrgbLuv` and skip validating their arguments.  The underlying colour can
+/// be accessed via [`Deref`](std::ops::Deref).
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// let red = luv::Luv::from_rgb(&[255, 0, 0]);
+/// let srgb = luv::SrgbLuv::try_from(red).unwrap();
+/// assert_eq!(red.l, srgb.l);
+///
+/// let too_red = luv::Luv { l: 53.24, u: 200.0, v: 37.76 };
+/// assert_eq!(Err(luv::NotDisplayable(too_red)), luv::SrgbLuv::try_from(too_red));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SrgbLuv(Luv);
+
+/// Error returned when converting a colour which is outside of sRGB gamut
+/// into [`SrgbLuv`].  Holds the rejected colour.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NotDisplayable(pub Luv);
+
+impl std::fmt::Display for NotDisplayable {
+    fn fmt(&self, fmtr: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(fmtr, "colour {:?} is outside of sRGB gamut", self.0)
+    }
+}
+
+impl std::error::Error for NotDisplayable {}
+
+impl SrgbLuv {
+    /// Constructs a new colour from sRGB components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let srgb = luv::SrgbLuv::from_rgb(&[255, 0, 0]);
+    /// assert_eq!(luv::Luv::from_rgb(&[255, 0, 0]), *srgb);
+    /// ```
+    pub fn from_rgb(rgb: &[u8; 3]) -> Self { Self(Luv::from_rgb(rgb)) }
+
+    /// Returns the underlying colour.
+    pub fn into_inner(self) -> Luv { self.0 }
+}
+
+impl TryFrom<Luv> for SrgbLuv {
+    type Error = NotDisplayable;
+
+    fn try_from(luv: Luv) -> Result<Self, Self::Error> {
+        if luv.is_displayable() {
+            Ok(Self(luv))
+        } else {
+            Err(NotDisplayable(luv))
+        }
+    }
+}
+
+impl std::ops::Deref for SrgbLuv {
+    type Target = Luv;
+
+    fn deref(&self) -> &Luv { &self.0 }
+}
+
+impl From<SrgbLuv> for Luv {
+    fn from(srgb: SrgbLuv) -> Luv { srgb.0 }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from() {
+        for rgb in [[0, 0, 0], [255, 255, 255], [0, 0, 255], [12, 200, 99]] {
+            let luv = Luv::from_rgb(&rgb);
+            let srgb = SrgbLuv::try_from(luv).unwrap();
+            assert_eq!(luv, *srgb);
+            assert_eq!(rgb, srgb.to_rgb());
+            assert_eq!(luv, Luv::from(srgb));
+        }
+
+        for luv in [
+            Luv {
+                l: 53.24,
+                u: 200.0,
+                v: 37.76,
+            },
+            Luv {
+                l: 50.0,
+                u: -150.0,
+                v: -150.0,
+            },
+            Luv {
+                l: 120.0,
+                u: 0.0,
+                v: 0.0,
+            },
+        ] {
+            assert_eq!(Err(NotDisplayable(luv)), SrgbLuv::try_from(luv));
+        }
+    }
+}